@@ -1,20 +1,13 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 use fast_sweeping::{
-    DistanceField, DistanceFieldAlgorithm, Grid, NaiveFastSweepingMethod, Obstacles,
+    DistanceField, DistanceFieldAlgorithm, EikonalFastSweepingMethod, FastMarchingMethod,
+    NaiveFastSweepingMethod, Obstacles,
 };
 
 fn create_test_obstacles(obstacles: &mut Obstacles) {
-    for y in 100..200 {
-        obstacles.set_at(100, y, true);
-    }
-
-    for x in 100..400 {
-        obstacles.set_at(x, 200, true);
-    }
-
-    for x in 100..200 {
-        obstacles.set_at(400 + x, 200 + x, true);
-    }
+    obstacles.draw_line((100, 100), (100, 199));
+    obstacles.draw_line((100, 200), (399, 200));
+    obstacles.draw_line((500, 300), (599, 399));
 }
 
 pub fn criterion_benchmark(c: &mut Criterion) {
@@ -95,5 +88,164 @@ pub fn criterion_benchmark(c: &mut Criterion) {
     });
 }
 
-criterion_group!(benches, criterion_benchmark);
+pub fn solver_comparison_benchmark(c: &mut Criterion) {
+    for (width, height) in [(640, 480), (1280, 960)] {
+        let mut obstacles = Obstacles::new(width, height);
+        create_test_obstacles(&mut obstacles);
+        let distance_field = DistanceField::from(&obstacles);
+
+        let mut group = c.benchmark_group(format!("{width}×{height}, step 0.1, solvers"));
+
+        let naive = NaiveFastSweepingMethod::default().with_step_size(0.1);
+        group.bench_function("naive fast sweeping, convergence", |b| {
+            b.iter(|| {
+                let mut df = distance_field.clone();
+                naive.calculate_distance_field(black_box(&mut df), black_box(&obstacles));
+                df
+            })
+        });
+
+        let marching = FastMarchingMethod::default().with_step_size(0.1);
+        group.bench_function("fast marching", |b| {
+            b.iter(|| {
+                let mut df = distance_field.clone();
+                marching.calculate_distance_field(black_box(&mut df), black_box(&obstacles));
+                df
+            })
+        });
+
+        group.finish();
+    }
+}
+
+pub fn max_distance_benchmark(c: &mut Criterion) {
+    fn bench<A: DistanceFieldAlgorithm>(
+        group: &mut criterion::BenchmarkGroup<'_, criterion::measurement::WallTime>,
+        name: &str,
+        algorithm: A,
+        distance_field: &DistanceField,
+        obstacles: &Obstacles,
+    ) {
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                let mut df = distance_field.clone();
+                algorithm.calculate_distance_field(black_box(&mut df), black_box(obstacles));
+                df
+            })
+        });
+    }
+
+    for (width, height) in [(640, 480), (1280, 960)] {
+        let mut obstacles = Obstacles::new(width, height);
+        create_test_obstacles(&mut obstacles);
+        let distance_field = DistanceField::from(&obstacles);
+
+        let mut group = c.benchmark_group(format!("{width}×{height}, step 0.1, max distance"));
+        let naive = NaiveFastSweepingMethod::default().with_step_size(0.1);
+        let eikonal = EikonalFastSweepingMethod::default().with_step_size(0.1);
+        let marching = FastMarchingMethod::default().with_step_size(0.1);
+        let df = &distance_field;
+
+        bench(
+            &mut group,
+            "naive fast sweeping, full",
+            naive,
+            df,
+            &obstacles,
+        );
+        let naive = naive.with_max_distance(3.0);
+        bench(
+            &mut group,
+            "naive fast sweeping, cutoff 3",
+            naive,
+            df,
+            &obstacles,
+        );
+        bench(
+            &mut group,
+            "eikonal fast sweeping, full",
+            eikonal,
+            df,
+            &obstacles,
+        );
+        let eikonal = eikonal.with_max_distance(3.0);
+        bench(
+            &mut group,
+            "eikonal fast sweeping, cutoff 3",
+            eikonal,
+            df,
+            &obstacles,
+        );
+        bench(&mut group, "fast marching, full", marching, df, &obstacles);
+        let marching = marching.with_max_distance(3.0);
+        bench(
+            &mut group,
+            "fast marching, cutoff 3",
+            marching,
+            df,
+            &obstacles,
+        );
+
+        group.finish();
+    }
+}
+
+#[cfg(feature = "rayon")]
+pub fn parallel_benchmark(c: &mut Criterion) {
+    use fast_sweeping::ParallelFastSweepingMethod;
+
+    for (width, height) in [(640, 480), (1280, 960)] {
+        let mut obstacles = Obstacles::new(width, height);
+        create_test_obstacles(&mut obstacles);
+        let distance_field = DistanceField::from(&obstacles);
+
+        let mut group =
+            c.benchmark_group(format!("{width}×{height}, step 0.1, convergence, parallel"));
+
+        let naive = NaiveFastSweepingMethod::default().with_step_size(0.1);
+        group.bench_function("serial", |b| {
+            b.iter(|| {
+                let mut df = distance_field.clone();
+                naive.calculate_distance_field(black_box(&mut df), black_box(&obstacles));
+                df
+            })
+        });
+
+        let parallel = ParallelFastSweepingMethod::from(naive);
+        for threads in [1, 2, 4, 8] {
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .unwrap();
+            group.bench_function(format!("parallel, {threads} threads"), |b| {
+                b.iter(|| {
+                    pool.install(|| {
+                        let mut df = distance_field.clone();
+                        parallel
+                            .calculate_distance_field(black_box(&mut df), black_box(&obstacles));
+                        df
+                    })
+                })
+            });
+        }
+
+        group.finish();
+    }
+}
+
+#[cfg(not(feature = "rayon"))]
+criterion_group!(
+    benches,
+    criterion_benchmark,
+    solver_comparison_benchmark,
+    max_distance_benchmark
+);
+#[cfg(feature = "rayon")]
+criterion_group!(
+    benches,
+    criterion_benchmark,
+    solver_comparison_benchmark,
+    max_distance_benchmark,
+    parallel_benchmark
+);
 criterion_main!(benches);
@@ -1,19 +1,12 @@
 use fast_sweeping::{
-    DistanceField, DistanceFieldAlgorithm, Grid, NaiveFastSweepingMethod, Obstacles, SavePgm,
+    Colormap, DistanceField, DistanceFieldAlgorithm, NaiveFastSweepingMethod, Obstacles, SavePgm,
+    Transfer, Visualization,
 };
 
 fn create_test_obstacles(obstacles: &mut Obstacles) {
-    for y in 100..200 {
-        obstacles.set_at(100, y, true);
-    }
-
-    for x in 100..400 {
-        obstacles.set_at(x, 200, true);
-    }
-
-    for x in 100..200 {
-        obstacles.set_at(400 + x, 200 + x, true);
-    }
+    obstacles.draw_line((100, 100), (100, 199));
+    obstacles.draw_line((100, 200), (399, 200));
+    obstacles.draw_line((500, 300), (599, 399));
 }
 
 fn main() {
@@ -31,6 +24,14 @@ fn main() {
     println!("Saving distance field to PGM ...");
     distance_field.save_pgm("test-distances.pgm").unwrap();
 
+    println!("Saving distance field visualization to PPM ...");
+    Visualization::default()
+        .with_colormap(Colormap::Viridis)
+        .with_transfer(Transfer::Sqrt)
+        .with_isolines([25.0, 50.0, 100.0, 200.0])
+        .save_ppm(&distance_field, "test-distances.ppm")
+        .unwrap();
+
     println!("Saving obstacles to PGM ...");
     obstacles.save_pgm("test-obstacles.pgm").unwrap();
 }
@@ -0,0 +1,181 @@
+use crate::obstacles::Obstacles;
+use crate::{Error, Grid, GridBounds, GridN};
+
+/// A 2D grid representing the cost of traversing each cell.
+///
+/// The cost is the inverse of the local propagation speed, i.e. a cell with cost `2.0`
+/// takes twice as long to cross as a cell with cost `1.0`. Cells with an infinite cost
+/// are impassable.
+///
+/// Every cost must be greater than zero. Zero, negative and NaN costs are rejected by
+/// [`TravelTimeAlgorithm::try_calculate_travel_time`](crate::TravelTimeAlgorithm::try_calculate_travel_time)
+/// and produce meaningless travel times otherwise.
+#[derive(Debug, Clone)]
+pub struct CostField {
+    costs: GridN<f32, 2>,
+}
+
+impl CostField {
+    /// Cost value marking a cell as impassable.
+    pub const IMPASSABLE: f32 = f32::INFINITY;
+
+    /// Creates a new cost field with the given dimensions.
+    ///
+    /// All cells are initialized to a uniform cost of `1.0`.
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            costs: GridN::new([width, height], 1.0),
+        }
+    }
+
+    /// Returns the width of the cost field.
+    pub const fn width(&self) -> usize {
+        self.costs.shape()[0]
+    }
+
+    /// Returns the height of the cost field.
+    pub const fn height(&self) -> usize {
+        self.costs.shape()[1]
+    }
+
+    /// Returns the costs as a generic 2D grid.
+    pub const fn as_grid(&self) -> &GridN<f32, 2> {
+        &self.costs
+    }
+
+    /// Returns an iterator over the cost values.
+    pub fn iter(&self) -> std::slice::Iter<'_, f32> {
+        self.costs.iter()
+    }
+
+    /// Returns a mutable iterator over the cost values.
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, f32> {
+        self.costs.iter_mut()
+    }
+
+    /// Returns the cost values as a row-major slice.
+    pub(crate) fn as_slice(&self) -> &[f32] {
+        self.costs.as_slice()
+    }
+
+    /// Returns an error for the first cost that is not greater than zero.
+    pub(crate) fn check_costs(&self) -> Result<(), Error> {
+        match self
+            .costs
+            .iter()
+            .position(|&cost| cost.is_nan() || cost <= 0.0)
+        {
+            Some(index) => Err(Error::InvalidCost {
+                x: index % self.width(),
+                y: index / self.width(),
+                value: self.costs.as_slice()[index],
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Panics if the cost field does not have the given `(width, height)`.
+    pub(crate) fn assert_dimensions(&self, expected: (usize, usize)) {
+        if let Err(error) = Error::check_dimensions(expected, (self.width(), self.height())) {
+            panic!("{error}");
+        }
+    }
+}
+
+impl From<&Obstacles> for CostField {
+    fn from(value: &Obstacles) -> Self {
+        Self::new(value.width(), value.height())
+    }
+}
+
+impl From<GridN<f32, 2>> for CostField {
+    fn from(costs: GridN<f32, 2>) -> Self {
+        Self { costs }
+    }
+}
+
+impl Grid for CostField {
+    type Item = f32;
+
+    fn get_at(&self, x: usize, y: usize) -> &Self::Item {
+        &self.costs[[x, y]]
+    }
+
+    fn set_at(&mut self, x: usize, y: usize, value: Self::Item) {
+        self.costs[[x, y]] = value
+    }
+}
+
+impl GridBounds for CostField {
+    fn width(&self) -> usize {
+        self.width()
+    }
+
+    fn height(&self) -> usize {
+        self.height()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_dimensions() {
+        let costs = CostField::new(10, 20);
+        assert_eq!(costs.width(), 10);
+        assert_eq!(costs.height(), 20);
+        assert_eq!(costs.iter().count(), 200);
+    }
+
+    #[test]
+    fn test_new_initial_values() {
+        let costs = CostField::new(5, 5);
+        for &c in costs.iter() {
+            assert_eq!(c, 1.0);
+        }
+    }
+
+    #[test]
+    fn test_grid_get_set_roundtrip() {
+        let mut costs = CostField::new(5, 5);
+        costs.set_at(2, 3, CostField::IMPASSABLE);
+        assert!(costs.get_at(2, 3).is_infinite());
+        assert_eq!(costs.costs.as_slice()[3 * 5 + 2], f32::INFINITY);
+    }
+
+    #[test]
+    fn test_from_grid() {
+        let costs = CostField::from(GridN::from_vec([3, 2], vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]));
+        assert_eq!((costs.width(), costs.height()), (3, 2));
+        assert_eq!(*costs.get_at(0, 1), 4.0);
+        assert_eq!(costs.as_grid()[[2, 0]], 3.0);
+    }
+
+    #[test]
+    fn test_check_costs() {
+        let mut costs = CostField::new(4, 3);
+        costs.set_at(1, 0, CostField::IMPASSABLE);
+        assert_eq!(costs.check_costs(), Ok(()));
+        for value in [0.0, -1.0, f32::NEG_INFINITY] {
+            costs.set_at(2, 1, value);
+            assert_eq!(
+                costs.check_costs(),
+                Err(Error::InvalidCost { x: 2, y: 1, value })
+            );
+        }
+        costs.set_at(2, 1, f32::NAN);
+        assert!(matches!(
+            costs.check_costs(),
+            Err(Error::InvalidCost { x: 2, y: 1, value }) if value.is_nan()
+        ));
+    }
+
+    #[test]
+    fn test_from_obstacles() {
+        let obs = Obstacles::new(8, 12);
+        let costs = CostField::from(&obs);
+        assert_eq!(costs.width(), 8);
+        assert_eq!(costs.height(), 12);
+    }
+}
@@ -0,0 +1,106 @@
+use crate::{DistanceField, Obstacles};
+use std::ops::Range;
+
+/// How cells farther than the maximum distance of a solver are represented.
+///
+/// See e.g. [`EikonalFastSweepingMethod::with_max_distance`](crate::EikonalFastSweepingMethod::with_max_distance).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Cutoff {
+    /// Distances beyond the maximum are clamped to the maximum distance.
+    #[default]
+    Clamp,
+    /// Distances beyond the maximum are left at [`DistanceField::MAX_DISTANCE`].
+    Unreached,
+}
+
+impl Cutoff {
+    /// Replaces all distances beyond `max_distance`.
+    pub(crate) fn apply<'a>(
+        self,
+        distances: impl IntoIterator<Item = &'a mut f32>,
+        max_distance: f32,
+    ) {
+        let replacement = match self {
+            Self::Clamp => max_distance,
+            Self::Unreached => DistanceField::MAX_DISTANCE,
+        };
+        for distance in distances.into_iter().filter(|d| **d > max_distance) {
+            *distance = replacement;
+        }
+    }
+}
+
+/// Returns for every row the columns within `radius` cells of an obstacle along both axes.
+///
+/// Distances grow by at least one step per cell, so cells outside of these spans are farther
+/// from every obstacle than `radius` steps and the sweeps can skip them. A negative or NaN
+/// radius gives no bound, so every row spans the whole grid.
+pub(crate) fn row_spans(obstacles: &Obstacles, radius: f32) -> Vec<Range<usize>> {
+    let (width, height) = (obstacles.width(), obstacles.height());
+    if radius.is_nan() || radius < 0.0 || radius >= width.max(height) as f32 {
+        return vec![0..width; height];
+    }
+
+    let radius = radius as usize;
+    let mut spans = vec![width..0; height];
+    let rows = obstacles.iter().as_slice().chunks_exact(width.max(1));
+    for (y, row) in rows.enumerate() {
+        let Some(first) = row.iter().position(|&is_obstacle| is_obstacle) else {
+            continue;
+        };
+        let last = row
+            .iter()
+            .rposition(|&is_obstacle| is_obstacle)
+            .unwrap_or(first);
+        let columns = first.saturating_sub(radius)..(last + radius + 1).min(width);
+        let rows = y.saturating_sub(radius)..(y + radius + 1).min(height);
+        for span in &mut spans[rows] {
+            span.start = span.start.min(columns.start);
+            span.end = span.end.max(columns.end);
+        }
+    }
+
+    for span in &mut spans {
+        if span.start >= span.end {
+            *span = 0..0;
+        }
+    }
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Grid;
+
+    #[test]
+    fn test_apply() {
+        let mut distances = [0.0, 1.5, 2.5, DistanceField::MAX_DISTANCE];
+        Cutoff::Clamp.apply(&mut distances, 2.0);
+        assert_eq!(distances, [0.0, 1.5, 2.0, 2.0]);
+
+        let mut distances = [0.0, 1.5, 2.5, DistanceField::MAX_DISTANCE];
+        Cutoff::Unreached.apply(&mut distances, 2.0);
+        let max = DistanceField::MAX_DISTANCE;
+        assert_eq!(distances, [0.0, 1.5, max, max]);
+    }
+
+    #[test]
+    fn test_row_spans() {
+        let mut obstacles = Obstacles::new(20, 10);
+        obstacles.set_at(3, 2, true);
+        obstacles.set_at(15, 3, true);
+
+        let spans = row_spans(&obstacles, 2.5);
+        assert_eq!(spans[0], 1..6);
+        assert_eq!(spans[1], 1..18);
+        assert_eq!(spans[4], 1..18);
+        assert_eq!(spans[5], 13..18);
+        assert!(spans[6..].iter().all(Range::is_empty));
+
+        assert!(row_spans(&obstacles, 20.0).iter().all(|s| *s == (0..20)));
+        for radius in [f32::INFINITY, f32::NEG_INFINITY, -1.0, f32::NAN] {
+            assert!(row_spans(&obstacles, radius).iter().all(|s| *s == (0..20)));
+        }
+    }
+}
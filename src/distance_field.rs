@@ -1,17 +1,19 @@
 use crate::obstacles::Obstacles;
-use crate::{Grid, SavePgm};
+use crate::{npy, pfm};
+use crate::{Error, Grid, GridBounds, GridGeometry, GridN, LoadError, Sampler, SavePgm};
 use std::fs::File;
 use std::io::Write;
 use std::path::Path;
 
 /// A 2D grid representing computed distance values.
 ///
-/// Each cell contains the distance to the nearest obstacle.
+/// Each cell contains the distance to the nearest obstacle. Distances are unsigned unless
+/// computed with [`SignedDistance`](crate::SignedDistance), in which case cells inside
+/// obstacles hold negative distances.
 #[derive(Debug, Clone)]
 pub struct DistanceField {
-    distances: Vec<f32>,
-    width: usize,
-    height: usize,
+    distances: GridN<f32, 2>,
+    geometry: Option<GridGeometry>,
 }
 
 impl DistanceField {
@@ -23,20 +25,106 @@ impl DistanceField {
     /// All distances are initialized to [`MAX_DISTANCE`](DistanceField::MAX_DISTANCE).
     pub fn new(width: usize, height: usize) -> Self {
         Self {
-            distances: vec![Self::MAX_DISTANCE; width * height],
-            width,
-            height,
+            distances: GridN::new([width, height], Self::MAX_DISTANCE),
+            geometry: None,
         }
     }
 
     /// Returns the width of the distance field.
     pub const fn width(&self) -> usize {
-        self.width
+        self.distances.shape()[0]
     }
 
     /// Returns the height of the distance field.
     pub const fn height(&self) -> usize {
-        self.height
+        self.distances.shape()[1]
+    }
+
+    /// Returns the placement of the grid in world coordinates, if known.
+    pub const fn geometry(&self) -> Option<&GridGeometry> {
+        self.geometry.as_ref()
+    }
+
+    /// Sets the placement of the grid in world coordinates.
+    #[must_use]
+    pub fn with_geometry(mut self, geometry: GridGeometry) -> Self {
+        self.geometry = Some(geometry);
+        self
+    }
+
+    /// Sets or clears the placement of the grid in world coordinates.
+    pub fn set_geometry(&mut self, geometry: Option<GridGeometry>) {
+        self.geometry = geometry;
+    }
+
+    /// Returns the world coordinates of the center of cell `(x, y)`.
+    ///
+    /// Without a geometry, cells are one unit apart with the origin at the corner of cell `(0, 0)`.
+    pub fn cell_to_world(&self, x: usize, y: usize) -> (f64, f64) {
+        self.geometry.unwrap_or_default().cell_to_world(x, y)
+    }
+
+    /// Returns the cell containing the given world coordinates, or `None` if it lies outside.
+    ///
+    /// Without a geometry, cells are one unit apart with the origin at the corner of cell `(0, 0)`.
+    pub fn world_to_cell(&self, wx: f64, wy: f64) -> Option<(usize, usize)> {
+        let (x, y) = self.geometry.unwrap_or_default().world_to_cell(wx, wy)?;
+        (x < self.width() && y < self.height()).then_some((x, y))
+    }
+
+    /// Samples the distance at a continuous position using bilinear interpolation.
+    ///
+    /// Positions outside of the grid are clamped; see [`Sampler`] for other options.
+    pub fn sample(&self, x: f32, y: f32) -> Result<f32, Error> {
+        Sampler::default().sample(self, x, y)
+    }
+
+    /// Saves the distances losslessly as a grayscale Portable Float Map.
+    ///
+    /// Unlike [`save_pgm`](SavePgm::save_pgm), values are neither rescaled nor quantized and
+    /// infinite distances are preserved.
+    pub fn save_pfm<P: AsRef<Path>>(&self, path: P) -> std::io::Result<()> {
+        std::fs::write(
+            path,
+            pfm::encode(self.width(), self.height(), self.distances.as_slice()),
+        )
+    }
+
+    /// Loads distances from a grayscale Portable Float Map.
+    pub fn load_pfm<P: AsRef<Path>>(path: P) -> Result<Self, LoadError> {
+        let (width, height, distances) = pfm::decode(&std::fs::read(path)?)?;
+        Ok(Self::from_values(width, height, distances))
+    }
+
+    /// Saves the distances losslessly as a NumPy `.npy` file holding a `float32` array of
+    /// shape `(height, width)`, preserving infinite distances.
+    pub fn save_npy<P: AsRef<Path>>(&self, path: P) -> std::io::Result<()> {
+        std::fs::write(
+            path,
+            npy::encode(self.width(), self.height(), self.distances.as_slice()),
+        )
+    }
+
+    /// Loads distances from a NumPy `.npy` file holding a `float32` or `float64` array of
+    /// shape `(height, width)`. One-dimensional arrays are read as a single row.
+    pub fn from_npy<P: AsRef<Path>>(path: P) -> Result<Self, LoadError> {
+        let (width, height, distances) = npy::decode(&std::fs::read(path)?)?;
+        Ok(Self::from_values(width, height, distances))
+    }
+
+    fn from_values(width: usize, height: usize, distances: Vec<f32>) -> Self {
+        Self::from(GridN::from_vec([width, height], distances))
+    }
+
+    /// Returns the distances as a generic 2D grid, e.g. for
+    /// [`DistanceFieldAlgorithmN`](crate::DistanceFieldAlgorithmN).
+    pub const fn as_grid(&self) -> &GridN<f32, 2> {
+        &self.distances
+    }
+
+    /// Returns the distances as a mutable generic 2D grid.
+    pub fn as_grid_mut(&mut self) -> &mut GridN<f32, 2> {
+        &mut self.distances
     }
 
     /// Returns an iterator over the distance values.
@@ -49,21 +137,38 @@ impl DistanceField {
         self.distances.iter_mut()
     }
 
+    /// Returns the distance values as a mutable row-major slice.
+    pub(crate) fn as_mut_slice(&mut self) -> &mut [f32] {
+        self.distances.as_mut_slice()
+    }
+
     /// Returns mutable slices for two adjacent rows.
     ///
     /// Returns `(row_y, row_y_plus_1)`. The caller must ensure `y + 1 < height`.
     pub fn get_rows_mut(&mut self, y: usize) -> (&mut [f32], &mut [f32]) {
-        let top = y * self.width;
-        let bottom = (y + 1) * self.width;
-        let (top_part, bottom_part) = self.distances.split_at_mut(bottom);
-        let (row_y_plus_1, _rest) = bottom_part.split_at_mut(self.width);
+        let width = self.width();
+        let top = y * width;
+        let bottom = (y + 1) * width;
+        let (top_part, bottom_part) = self.distances.as_mut_slice().split_at_mut(bottom);
+        let (row_y_plus_1, _rest) = bottom_part.split_at_mut(width);
         (&mut top_part[top..], row_y_plus_1)
     }
 }
 
 impl From<&Obstacles> for DistanceField {
     fn from(value: &Obstacles) -> Self {
-        Self::new(value.width(), value.height())
+        let mut distance_field = Self::new(value.width(), value.height());
+        distance_field.set_geometry(value.geometry().copied());
+        distance_field
+    }
+}
+
+impl From<GridN<f32, 2>> for DistanceField {
+    fn from(distances: GridN<f32, 2>) -> Self {
+        Self {
+            distances,
+            geometry: None,
+        }
     }
 }
 
@@ -71,11 +176,22 @@ impl Grid for DistanceField {
     type Item = f32;
 
     fn get_at(&self, x: usize, y: usize) -> &Self::Item {
-        &self.distances[y * self.width + x]
+        &self.distances.as_slice()[y * self.width() + x]
     }
 
     fn set_at(&mut self, x: usize, y: usize, value: Self::Item) {
-        self.distances[y * self.width + x] = value
+        let width = self.width();
+        self.distances.as_mut_slice()[y * width + x] = value
+    }
+}
+
+impl GridBounds for DistanceField {
+    fn width(&self) -> usize {
+        self.width()
+    }
+
+    fn height(&self) -> usize {
+        self.height()
     }
 }
 
@@ -94,7 +210,14 @@ impl SavePgm for DistanceField {
             1.0
         };
 
-        let header = format!("P5\n{} {}\n{}\n", self.width, self.height, max_value);
+        let comments = self.geometry.map(|g| g.pgm_comments()).unwrap_or_default();
+        let header = format!(
+            "P5\n{}{} {}\n{}\n",
+            comments,
+            self.width(),
+            self.height(),
+            max_value
+        );
         file.write_all(header.as_bytes())?;
 
         for distance in self.iter() {
@@ -178,13 +301,13 @@ mod tests {
     fn test_grid_coordinate_mapping() {
         let mut df = DistanceField::new(10, 10);
         df.set_at(1, 2, 99.0);
-        assert_eq!(df.distances[2 * 10 + 1], 99.0);
+        assert_eq!(df.distances.as_slice()[2 * 10 + 1], 99.0);
 
         df.set_at(0, 0, 1.0);
-        assert_eq!(df.distances[0], 1.0);
+        assert_eq!(df.distances.as_slice()[0], 1.0);
 
         df.set_at(9, 9, 2.0);
-        assert_eq!(df.distances[99], 2.0);
+        assert_eq!(df.distances.as_slice()[99], 2.0);
     }
 
     #[test]
@@ -215,7 +338,7 @@ mod tests {
         let path = "test_distance_field_scale.pgm";
         df.save_pgm(path).unwrap();
         let content = fs::read(path).unwrap();
-        let header = format!("P5\n3 3\n255\n");
+        let header = "P5\n3 3\n255\n";
         assert!(content.starts_with(header.as_bytes()));
         let data = &content[header.len()..];
         assert_eq!(data.len(), 9);
@@ -224,6 +347,49 @@ mod tests {
         fs::remove_file(path).unwrap();
     }
 
+    #[test]
+    fn test_geometry_travels_from_obstacles() {
+        let geometry = GridGeometry::new(-2.0, 1.0, 0.05);
+        let obstacles = Obstacles::new(40, 20).with_geometry(geometry);
+        let df = DistanceField::from(&obstacles);
+        assert_eq!(df.geometry(), Some(&geometry));
+        assert!(DistanceField::from(&Obstacles::new(4, 4))
+            .geometry()
+            .is_none());
+    }
+
+    #[test]
+    fn test_world_to_cell_is_bounded() {
+        let df = DistanceField::new(40, 20).with_geometry(GridGeometry::new(-2.0, 1.0, 0.05));
+        assert_eq!(df.world_to_cell(-2.0, 1.0), Some((0, 0)));
+        assert_eq!(df.world_to_cell(-0.01, 1.99), Some((39, 19)));
+        assert_eq!(df.world_to_cell(0.0, 1.5), None);
+        assert_eq!(df.world_to_cell(-1.0, 2.0), None);
+
+        let (wx, wy) = df.cell_to_world(39, 19);
+        assert!((wx + 0.025).abs() < 1e-9 && (wy - 1.975).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_world_to_cell_without_geometry() {
+        let df = DistanceField::new(4, 3);
+        assert_eq!(df.cell_to_world(1, 2), (1.5, 2.5));
+        assert_eq!(df.world_to_cell(3.9, 2.1), Some((3, 2)));
+        assert_eq!(df.world_to_cell(4.0, 0.0), None);
+    }
+
+    #[test]
+    fn test_save_pgm_writes_geometry() {
+        let df = DistanceField::new(3, 2).with_geometry(GridGeometry::new(0.5, -1.0, 0.1));
+        let path = "test_distance_field_geometry.pgm";
+        df.save_pgm(path).unwrap();
+        let content = fs::read(path).unwrap();
+        let header = "P5\n# resolution: 0.1\n# origin: 0.5 -1 0\n3 2\n255\n";
+        assert!(content.starts_with(header.as_bytes()));
+        assert_eq!(content.len(), header.len() + 6);
+        fs::remove_file(path).unwrap();
+    }
+
     #[test]
     fn test_save_pgm_ignores_infinity() {
         let mut df = DistanceField::new(3, 3);
@@ -244,4 +410,50 @@ mod tests {
         assert!(data[4] > 0);
         fs::remove_file(path).unwrap();
     }
+
+    fn create_test_field() -> DistanceField {
+        let mut df = DistanceField::new(5, 3);
+        for (i, d) in df.iter_mut().enumerate() {
+            *d = i as f32 * 0.37 - 1.0;
+        }
+        df.set_at(4, 2, f32::INFINITY);
+        df.set_at(0, 1, f32::NEG_INFINITY);
+        df
+    }
+
+    #[test]
+    fn test_pfm_roundtrip() {
+        let df = create_test_field();
+        let path = "test_distance_field_roundtrip.pfm";
+        df.save_pfm(path).unwrap();
+        let loaded = DistanceField::load_pfm(path).unwrap();
+        fs::remove_file(path).unwrap();
+
+        assert_eq!((loaded.width(), loaded.height()), (5, 3));
+        assert!(loaded.iter().eq(df.iter()));
+    }
+
+    #[test]
+    fn test_npy_roundtrip() {
+        let df = create_test_field();
+        let path = "test_distance_field_roundtrip.npy";
+        df.save_npy(path).unwrap();
+        let loaded = DistanceField::from_npy(path).unwrap();
+        fs::remove_file(path).unwrap();
+
+        assert_eq!((loaded.width(), loaded.height()), (5, 3));
+        assert!(loaded.iter().eq(df.iter()));
+    }
+
+    #[test]
+    fn test_load_missing_file() {
+        assert!(matches!(
+            DistanceField::load_pfm("does_not_exist.pfm"),
+            Err(LoadError::Io(_))
+        ));
+        assert!(matches!(
+            DistanceField::from_npy("does_not_exist.npy"),
+            Err(LoadError::Io(_))
+        ));
+    }
 }
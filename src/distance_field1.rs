@@ -0,0 +1,134 @@
+use crate::{DistanceField, Grid, GridBounds, Obstacles1};
+
+/// A 1D line of computed distance values, such as along a single lidar ray or a timeline.
+///
+/// This is stored as a [`DistanceField`] with a height of one, so it can be computed by every
+/// [`DistanceFieldAlgorithm`](crate::DistanceFieldAlgorithm) through
+/// [`calculate_distance_field1`](crate::DistanceFieldAlgorithm::calculate_distance_field1).
+#[derive(Debug, Clone)]
+pub struct DistanceField1 {
+    distances: DistanceField,
+}
+
+impl DistanceField1 {
+    /// Maximum distance value used for initialization.
+    pub const MAX_DISTANCE: f32 = DistanceField::MAX_DISTANCE;
+
+    /// Creates a new distance line with the given number of cells.
+    ///
+    /// All distances are initialized to [`MAX_DISTANCE`](DistanceField1::MAX_DISTANCE).
+    pub fn new(len: usize) -> Self {
+        Self {
+            distances: DistanceField::new(len, 1),
+        }
+    }
+
+    /// Returns the number of cells.
+    pub const fn len(&self) -> usize {
+        self.distances.width()
+    }
+
+    /// Returns `true` if the line has no cells.
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the distance at `x`.
+    pub fn get(&self, x: usize) -> f32 {
+        *self.distances.get_at(x, 0)
+    }
+
+    /// Returns an iterator over the distance values.
+    pub fn iter(&self) -> std::slice::Iter<'_, f32> {
+        self.distances.iter()
+    }
+
+    /// Returns a mutable iterator over the distance values.
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, f32> {
+        self.distances.iter_mut()
+    }
+}
+
+impl From<&Obstacles1> for DistanceField1 {
+    fn from(value: &Obstacles1) -> Self {
+        Self::new(value.len())
+    }
+}
+
+impl AsRef<DistanceField> for DistanceField1 {
+    fn as_ref(&self) -> &DistanceField {
+        &self.distances
+    }
+}
+
+impl AsMut<DistanceField> for DistanceField1 {
+    fn as_mut(&mut self) -> &mut DistanceField {
+        &mut self.distances
+    }
+}
+
+impl Grid for DistanceField1 {
+    type Item = f32;
+
+    fn get_at(&self, x: usize, y: usize) -> &Self::Item {
+        self.distances.get_at(x, y)
+    }
+
+    fn set_at(&mut self, x: usize, y: usize, value: Self::Item) {
+        self.distances.set_at(x, y, value)
+    }
+}
+
+impl GridBounds for DistanceField1 {
+    fn width(&self) -> usize {
+        self.distances.width()
+    }
+
+    fn height(&self) -> usize {
+        1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        DistanceFieldAlgorithm, EikonalFastSweepingMethod, EuclideanDistanceTransform,
+        FastMarchingMethod, NaiveFastSweepingMethod,
+    };
+
+    fn create_test_obstacles() -> Obstacles1 {
+        let mut obstacles = Obstacles1::new(12);
+        obstacles.set(3, true);
+        obstacles.set(10, true);
+        obstacles
+    }
+
+    #[test]
+    fn test_new_initial_values() {
+        let df = DistanceField1::from(&Obstacles1::new(5));
+        assert_eq!(df.len(), 5);
+        assert_eq!(df.height(), 1);
+        assert!(df.iter().all(|d| d.is_infinite()));
+    }
+
+    #[test]
+    fn test_all_algorithms_agree() {
+        let obstacles = create_test_obstacles();
+        let expected = [3.0, 2.0, 1.0, 0.0, 1.0, 2.0, 3.0, 3.0, 2.0, 1.0, 0.0, 1.0];
+
+        let algorithms: [&dyn DistanceFieldAlgorithm; 4] = [
+            &NaiveFastSweepingMethod::default(),
+            &EikonalFastSweepingMethod::default(),
+            &EuclideanDistanceTransform::default(),
+            &FastMarchingMethod::default(),
+        ];
+        for algorithm in algorithms {
+            let mut df = DistanceField1::from(&obstacles);
+            algorithm.calculate_distance_field1(&mut df, &obstacles);
+            for (x, &expected) in expected.iter().enumerate() {
+                assert_eq!(df.get(x), expected, "Mismatch at {x}");
+            }
+        }
+    }
+}
@@ -0,0 +1,148 @@
+use crate::obstacles3::Obstacles3;
+use crate::{Grid3, GridN};
+
+/// A 3D grid representing computed distance values.
+///
+/// Each cell contains the distance to the nearest obstacle.
+#[derive(Debug, Clone)]
+pub struct DistanceField3 {
+    distances: GridN<f32, 3>,
+}
+
+impl DistanceField3 {
+    /// Maximum distance value used for initialization.
+    pub const MAX_DISTANCE: f32 = f32::INFINITY;
+
+    /// Creates a new distance field with the given dimensions.
+    ///
+    /// All distances are initialized to [`MAX_DISTANCE`](DistanceField3::MAX_DISTANCE).
+    pub fn new(width: usize, height: usize, depth: usize) -> Self {
+        Self {
+            distances: GridN::new([width, height, depth], Self::MAX_DISTANCE),
+        }
+    }
+
+    /// Returns the width of the distance field.
+    pub const fn width(&self) -> usize {
+        self.distances.shape()[0]
+    }
+
+    /// Returns the height of the distance field.
+    pub const fn height(&self) -> usize {
+        self.distances.shape()[1]
+    }
+
+    /// Returns the depth of the distance field.
+    pub const fn depth(&self) -> usize {
+        self.distances.shape()[2]
+    }
+
+    /// Returns an iterator over the distance values.
+    pub fn iter(&self) -> std::slice::Iter<'_, f32> {
+        self.distances.iter()
+    }
+
+    /// Returns a mutable iterator over the distance values.
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, f32> {
+        self.distances.iter_mut()
+    }
+
+    /// Returns the distances as a generic 3D grid, e.g. for
+    /// [`DistanceFieldAlgorithmN`](crate::DistanceFieldAlgorithmN).
+    pub const fn as_grid(&self) -> &GridN<f32, 3> {
+        &self.distances
+    }
+
+    /// Returns the distances as a mutable generic 3D grid.
+    pub fn as_grid_mut(&mut self) -> &mut GridN<f32, 3> {
+        &mut self.distances
+    }
+}
+
+impl From<GridN<f32, 3>> for DistanceField3 {
+    fn from(distances: GridN<f32, 3>) -> Self {
+        Self { distances }
+    }
+}
+
+impl From<&Obstacles3> for DistanceField3 {
+    fn from(value: &Obstacles3) -> Self {
+        Self::new(value.width(), value.height(), value.depth())
+    }
+}
+
+impl Grid3 for DistanceField3 {
+    type Item = f32;
+
+    fn get_at(&self, x: usize, y: usize, z: usize) -> &Self::Item {
+        &self.distances[[x, y, z]]
+    }
+
+    fn set_at(&mut self, x: usize, y: usize, z: usize, value: Self::Item) {
+        self.distances[[x, y, z]] = value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_dimensions() {
+        let df = DistanceField3::new(10, 20, 30);
+        assert_eq!(df.width(), 10);
+        assert_eq!(df.height(), 20);
+        assert_eq!(df.depth(), 30);
+    }
+
+    #[test]
+    fn test_new_initial_values() {
+        let df = DistanceField3::new(5, 5, 5);
+        for &d in df.iter() {
+            assert_eq!(d, DistanceField3::MAX_DISTANCE);
+        }
+    }
+
+    #[test]
+    fn test_iter_length() {
+        let df = DistanceField3::new(10, 20, 3);
+        assert_eq!(df.iter().count(), 600);
+    }
+
+    #[test]
+    fn test_iter_mut_modifies() {
+        let mut df = DistanceField3::new(3, 3, 3);
+        for d in df.iter_mut() {
+            *d = 42.0;
+        }
+        for &d in df.iter() {
+            assert_eq!(d, 42.0);
+        }
+    }
+
+    #[test]
+    fn test_grid_get_set_roundtrip() {
+        let mut df = DistanceField3::new(5, 5, 5);
+        df.set_at(2, 3, 4, 7.5);
+        assert_eq!(*df.get_at(2, 3, 4), 7.5);
+    }
+
+    #[test]
+    fn test_grid_coordinate_mapping() {
+        let mut df = DistanceField3::new(10, 10, 10);
+        df.set_at(1, 2, 3, 99.0);
+        assert_eq!(df.distances.as_slice()[(3 * 10 + 2) * 10 + 1], 99.0);
+
+        df.set_at(9, 9, 9, 2.0);
+        assert_eq!(df.distances.as_slice()[999], 2.0);
+    }
+
+    #[test]
+    fn test_from_obstacles() {
+        let obs = Obstacles3::new(8, 12, 4);
+        let df = DistanceField3::from(&obs);
+        assert_eq!(df.width(), 8);
+        assert_eq!(df.height(), 12);
+        assert_eq!(df.depth(), 4);
+    }
+}
@@ -0,0 +1,501 @@
+use crate::{Grid, Obstacles};
+
+/// Rules deciding which regions of a self-intersecting polygon are filled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FillRule {
+    /// Regions enclosed an odd number of times are filled.
+    #[default]
+    EvenOdd,
+    /// Regions with a non-zero winding number are filled.
+    NonZero,
+}
+
+/// Drawing primitives for building obstacle maps.
+///
+/// Coordinates are in cells and may lie outside of the grid; all shapes are clipped to the
+/// grid bounds. Strokes with a thickness of zero draw nothing.
+impl Obstacles {
+    /// Draws a one cell wide line from `from` to `to`, including both endpoints, using
+    /// Bresenham's algorithm.
+    ///
+    /// Only the steps along the major axis that lie inside the grid are visited, so the cost
+    /// is bounded by the grid size regardless of how far the endpoints lie outside of it.
+    pub fn draw_line(&mut self, from: (isize, isize), to: (isize, isize)) {
+        let (dx, dy) = (to.0.abs_diff(from.0) as u128, to.1.abs_diff(from.1) as u128);
+        let (sx, sy) = (to.0.cmp(&from.0) as i128, to.1.cmp(&from.1) as i128);
+        let from = (from.0 as i128, from.1 as i128);
+        let x_major = dx >= dy;
+        let (steps, minor_steps) = if x_major { (dx, dy) } else { (dy, dx) };
+        let (start, step, len) = if x_major {
+            (from.0, sx, self.width() as i128)
+        } else {
+            (from.1, sy, self.height() as i128)
+        };
+
+        // Clip the steps to those whose major coordinate lies inside the grid.
+        let (first, last) = if step < 0 {
+            (start - len + 1, start)
+        } else {
+            (-start, len - 1 - start)
+        };
+        for i in first.max(0)..=last.min(steps as i128) {
+            // After `i` major steps, Bresenham has taken `round(i * minor_steps / steps)` minor
+            // steps, rounding halves up.
+            let product = i as u128 * minor_steps;
+            let minor = product.checked_div(steps).map_or(0, |quotient| {
+                quotient + u128::from(2 * (product % steps) >= steps)
+            }) as i128;
+            let major = start + step * i;
+            if x_major {
+                self.mark(major, from.1 + sy * minor);
+            } else {
+                self.mark(from.0 + sx * minor, major);
+            }
+        }
+    }
+
+    /// Draws a line with round caps that covers all cells whose centers lie within
+    /// `thickness / 2` of the segment. A thickness of one draws a Bresenham line.
+    pub fn draw_thick_line(&mut self, from: (isize, isize), to: (isize, isize), thickness: usize) {
+        match thickness {
+            0 => {}
+            1 => self.draw_line(from, to),
+            _ => {
+                let radius = thickness as f32 / 2.0;
+                let margin = radius.ceil() as isize;
+                let (ax, ay) = (from.0 as f32, from.1 as f32);
+                let (vx, vy) = (to.0 as f32 - ax, to.1 as f32 - ay);
+                let length_squared = vx * vx + vy * vy;
+                self.fill_where(
+                    (
+                        from.0.min(to.0).saturating_sub(margin),
+                        from.1.min(to.1).saturating_sub(margin),
+                    ),
+                    (
+                        from.0.max(to.0).saturating_add(margin),
+                        from.1.max(to.1).saturating_add(margin),
+                    ),
+                    |x, y| {
+                        let (px, py) = (x as f32 - ax, y as f32 - ay);
+                        let t = if length_squared > 0.0 {
+                            ((px * vx + py * vy) / length_squared).clamp(0.0, 1.0)
+                        } else {
+                            0.0
+                        };
+                        let (qx, qy) = (px - t * vx, py - t * vy);
+                        qx * qx + qy * qy <= radius * radius
+                    },
+                );
+            }
+        }
+    }
+
+    /// Draws connected line segments through `points`. Repeat the first point at the end
+    /// to close the outline.
+    pub fn draw_polyline(&mut self, points: &[(isize, isize)], thickness: usize) {
+        if let [point] = points {
+            self.draw_thick_line(*point, *point, thickness);
+        }
+        for segment in points.windows(2) {
+            self.draw_thick_line(segment[0], segment[1], thickness);
+        }
+    }
+
+    /// Fills the rectangle spanned by the inclusive corners `min` and `max`.
+    pub fn fill_rect(&mut self, min: (isize, isize), max: (isize, isize)) {
+        self.fill_where(min, max, |_, _| true);
+    }
+
+    /// Draws the outline of the rectangle spanned by the inclusive corners `min` and `max`,
+    /// with the stroke lying inside the rectangle.
+    pub fn draw_rect(&mut self, min: (isize, isize), max: (isize, isize), thickness: usize) {
+        let t = thickness as i128;
+        let (min_x, min_y, max_x, max_y) =
+            (min.0 as i128, min.1 as i128, max.0 as i128, max.1 as i128);
+        self.fill_where(min, max, |x, y| {
+            let (x, y) = (x as i128, y as i128);
+            x - min_x < t || max_x - x < t || y - min_y < t || max_y - y < t
+        });
+    }
+
+    /// Fills all cells whose centers lie closer than `radius + 0.5` to `center`.
+    pub fn fill_disk(&mut self, center: (isize, isize), radius: usize) {
+        self.fill_ring(center, radius, None);
+    }
+
+    /// Draws the outline of the disk filled by [`fill_disk`](Self::fill_disk), with the stroke
+    /// lying inside the disk.
+    pub fn draw_circle(&mut self, center: (isize, isize), radius: usize, thickness: usize) {
+        if thickness > 0 {
+            self.fill_ring(center, radius, radius.checked_sub(thickness));
+        }
+    }
+
+    /// Fills the polygon with the given vertices, closing it implicitly.
+    ///
+    /// Vertices are continuous positions with cell centers at integer coordinates. A cell is
+    /// filled if its center lies inside the polygon; centers on the left and top edges count
+    /// as inside, those on the right and bottom edges do not.
+    pub fn fill_polygon(&mut self, vertices: &[(f32, f32)], rule: FillRule) {
+        if vertices.len() < 3 {
+            return;
+        }
+
+        let mut crossings: Vec<(f32, i32)> = Vec::new();
+        for y in 0..self.height() {
+            let center = y as f32;
+            crossings.clear();
+            for (i, &(ax, ay)) in vertices.iter().enumerate() {
+                let (bx, by) = vertices[(i + 1) % vertices.len()];
+                let direction = if ay <= center && center < by {
+                    1
+                } else if by <= center && center < ay {
+                    -1
+                } else {
+                    continue;
+                };
+                crossings.push((ax + (center - ay) * (bx - ax) / (by - ay), direction));
+            }
+            crossings.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+            let mut winding = 0;
+            for pair in crossings.windows(2) {
+                winding += pair[0].1;
+                let inside = match rule {
+                    FillRule::EvenOdd => winding % 2 != 0,
+                    FillRule::NonZero => winding != 0,
+                };
+                if inside {
+                    let start = pair[0].0.ceil().max(0.0);
+                    let end = pair[1].0.ceil().min(self.width() as f32);
+                    for x in start as usize..end.max(start) as usize {
+                        self.set_at(x, y, true);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Fills the disk of `radius` around `center`, leaving out the disk of radius `hole`.
+    fn fill_ring(&mut self, center: (isize, isize), radius: usize, hole: Option<usize>) {
+        // Squared distances are compared in `u128`, where `r * (r + 1)` cannot overflow and
+        // saturated distances lie outside of every circle.
+        let limit = |r: usize| r as u128 * (r as u128 + 1);
+        let (outer_limit, inner_limit) = (limit(radius), hole.map(limit));
+        let squared = |a: isize, b: isize| {
+            let d = (a as i128 - b as i128).unsigned_abs();
+            d.saturating_mul(d)
+        };
+        self.fill_where(
+            (
+                center.0.saturating_sub_unsigned(radius),
+                center.1.saturating_sub_unsigned(radius),
+            ),
+            (
+                center.0.saturating_add_unsigned(radius),
+                center.1.saturating_add_unsigned(radius),
+            ),
+            |x, y| {
+                let d = squared(x, center.0).saturating_add(squared(y, center.1));
+                d <= outer_limit && inner_limit.map_or(true, |inner_limit| d > inner_limit)
+            },
+        );
+    }
+
+    /// Marks a cell as an obstacle if it lies inside the grid.
+    fn mark(&mut self, x: i128, y: i128) {
+        if (0..self.width() as i128).contains(&x) && (0..self.height() as i128).contains(&y) {
+            self.set_at(x as usize, y as usize, true);
+        }
+    }
+
+    /// Marks the cells within the inclusive bounds, clipped to the grid, that satisfy
+    /// `predicate`.
+    fn fill_where(
+        &mut self,
+        min: (isize, isize),
+        max: (isize, isize),
+        predicate: impl Fn(isize, isize) -> bool,
+    ) {
+        let clip = |value: isize, len: usize| value.clamp(0, len as isize);
+        let (x0, x1) = (
+            clip(min.0, self.width()),
+            clip(max.0.saturating_add(1), self.width()),
+        );
+        let (y0, y1) = (
+            clip(min.1, self.height()),
+            clip(max.1.saturating_add(1), self.height()),
+        );
+        for y in y0..y1 {
+            for x in x0..x1 {
+                if predicate(x, y) {
+                    self.set_at(x as usize, y as usize, true);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cells(obstacles: &Obstacles) -> Vec<(usize, usize)> {
+        let width = obstacles.width();
+        obstacles
+            .iter()
+            .enumerate()
+            .filter(|(_, &b)| b)
+            .map(|(i, _)| (i % width, i / width))
+            .collect()
+    }
+
+    fn count(obstacles: &Obstacles) -> usize {
+        obstacles.iter().filter(|&&b| b).count()
+    }
+
+    #[test]
+    fn test_draw_line() {
+        let mut obstacles = Obstacles::new(8, 8);
+        obstacles.draw_line((1, 1), (6, 3));
+        assert_eq!(
+            cells(&obstacles),
+            vec![(1, 1), (2, 1), (3, 2), (4, 2), (5, 3), (6, 3)]
+        );
+
+        // Lines are symmetric in their endpoints for axis-aligned and diagonal directions.
+        let mut forward = Obstacles::new(8, 8);
+        let mut backward = Obstacles::new(8, 8);
+        forward.draw_line((0, 7), (7, 0));
+        backward.draw_line((7, 0), (0, 7));
+        assert_eq!(cells(&forward), cells(&backward));
+        assert_eq!(count(&forward), 8);
+
+        let mut point = Obstacles::new(3, 3);
+        point.draw_line((1, 1), (1, 1));
+        assert_eq!(cells(&point), vec![(1, 1)]);
+    }
+
+    #[test]
+    fn test_draw_line_matches_stepwise_bresenham() {
+        fn stepwise(
+            width: usize,
+            height: usize,
+            from: (isize, isize),
+            to: (isize, isize),
+        ) -> Obstacles {
+            let mut obstacles = Obstacles::new(width, height);
+            let (dx, dy) = ((to.0 - from.0).abs(), -(to.1 - from.1).abs());
+            let (sx, sy) = ((to.0 - from.0).signum(), (to.1 - from.1).signum());
+            let (mut x, mut y) = from;
+            let mut error = dx + dy;
+            loop {
+                obstacles.mark(x as i128, y as i128);
+                if (x, y) == to {
+                    return obstacles;
+                }
+                let doubled = 2 * error;
+                if doubled >= dy {
+                    error += dy;
+                    x += sx;
+                }
+                if doubled <= dx {
+                    error += dx;
+                    y += sy;
+                }
+            }
+        }
+
+        let coordinates = [-13, -4, -1, 0, 2, 3, 5, 6, 7, 11, 19];
+        for &x0 in &coordinates {
+            for &y0 in &coordinates {
+                for &x1 in &coordinates {
+                    for &y1 in &coordinates {
+                        let mut obstacles = Obstacles::new(7, 6);
+                        obstacles.draw_line((x0, y0), (x1, y1));
+                        let expected = stepwise(7, 6, (x0, y0), (x1, y1));
+                        assert!(
+                            obstacles.iter().eq(expected.iter()),
+                            "({x0}, {y0}) to ({x1}, {y1})"
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_far_off_grid_lines() {
+        let mut obstacles = Obstacles::new(5, 5);
+        obstacles.draw_line((-2_000_000_000, 2), (2_000_000_000, 2));
+        assert_eq!(
+            cells(&obstacles),
+            (0..5).map(|x| (x, 2)).collect::<Vec<_>>()
+        );
+
+        let mut extreme = Obstacles::new(5, 5);
+        extreme.draw_line((isize::MIN, isize::MIN), (isize::MAX, isize::MAX));
+        assert_eq!(cells(&extreme), (0..5).map(|i| (i, i)).collect::<Vec<_>>());
+        extreme.draw_line((isize::MAX, isize::MIN), (isize::MIN, isize::MIN));
+        extreme.draw_thick_line((isize::MIN, 0), (isize::MAX, 0), usize::MAX);
+        assert_eq!(count(&extreme), 25);
+    }
+
+    #[test]
+    fn test_l_shape_scene() {
+        let mut expected = Obstacles::new(640, 480);
+        for y in 100..200 {
+            expected.set_at(100, y, true);
+        }
+        for x in 100..400 {
+            expected.set_at(x, 200, true);
+        }
+        for x in 100..200 {
+            expected.set_at(400 + x, 200 + x, true);
+        }
+
+        let mut obstacles = Obstacles::new(640, 480);
+        obstacles.draw_line((100, 100), (100, 199));
+        obstacles.draw_line((100, 200), (399, 200));
+        obstacles.draw_line((500, 300), (599, 399));
+        assert!(obstacles.iter().eq(expected.iter()));
+    }
+
+    #[test]
+    fn test_lines_are_clipped() {
+        let mut obstacles = Obstacles::new(5, 5);
+        obstacles.draw_line((-3, 2), (10, 2));
+        obstacles.draw_line((-10, -10), (-1, -1));
+        assert_eq!(
+            cells(&obstacles),
+            (0..5).map(|x| (x, 2)).collect::<Vec<_>>()
+        );
+
+        obstacles.draw_thick_line((2, -5), (2, 20), 3);
+        assert_eq!(count(&obstacles), 5 + 3 * 5 - 3);
+    }
+
+    #[test]
+    fn test_draw_thick_line() {
+        let mut obstacles = Obstacles::new(20, 20);
+        obstacles.draw_thick_line((5, 10), (14, 10), 5);
+        for x in 5..=14 {
+            for y in 8..=12 {
+                assert!(obstacles.get_at(x, y), "({x}, {y})");
+            }
+        }
+        // The caps are round.
+        assert!(obstacles.get_at(3, 10));
+        assert!(!obstacles.get_at(3, 8));
+        assert!(!obstacles.get_at(10, 13));
+
+        let mut empty = Obstacles::new(5, 5);
+        empty.draw_thick_line((0, 0), (4, 4), 0);
+        assert_eq!(count(&empty), 0);
+    }
+
+    #[test]
+    fn test_draw_polyline() {
+        let mut obstacles = Obstacles::new(10, 10);
+        obstacles.draw_polyline(&[(1, 1), (8, 1), (8, 8), (1, 8), (1, 1)], 1);
+        assert_eq!(count(&obstacles), 28);
+        assert!(!obstacles.get_at(4, 4));
+    }
+
+    #[test]
+    fn test_rectangles() {
+        let mut filled = Obstacles::new(10, 10);
+        filled.fill_rect((2, 3), (5, 4));
+        assert_eq!(count(&filled), 8);
+        assert!(*filled.get_at(2, 3) && *filled.get_at(5, 4));
+
+        let mut outlined = Obstacles::new(10, 10);
+        outlined.draw_rect((1, 1), (8, 8), 2);
+        assert_eq!(count(&outlined), 64 - 16);
+        assert!(!outlined.get_at(3, 3) && *outlined.get_at(2, 2));
+
+        let mut clipped = Obstacles::new(4, 4);
+        clipped.fill_rect((-5, -5), (1, 100));
+        assert_eq!(count(&clipped), 8);
+    }
+
+    #[test]
+    fn test_disks_and_circles() {
+        let mut disk = Obstacles::new(11, 11);
+        disk.fill_disk((5, 5), 3);
+        assert!(*disk.get_at(8, 5) && *disk.get_at(7, 7));
+        assert!(!disk.get_at(9, 5) && !disk.get_at(8, 8));
+        assert_eq!(count(&disk), 37);
+
+        let mut circle = Obstacles::new(11, 11);
+        circle.draw_circle((5, 5), 3, 1);
+        assert!(!circle.get_at(5, 5) && !circle.get_at(6, 6));
+        assert!(*circle.get_at(8, 5) && *circle.get_at(7, 7));
+        assert_eq!(count(&circle), count(&disk) - 21);
+
+        // Circles and disks are clipped at the grid border.
+        let mut corner = Obstacles::new(11, 11);
+        corner.fill_disk((0, 0), 3);
+        assert_eq!(count(&corner), 13);
+
+        // Huge radii and far away centers neither overflow nor visit off-grid cells.
+        let mut huge = Obstacles::new(4, 4);
+        huge.fill_disk((isize::MIN, isize::MAX), 3);
+        huge.draw_circle((isize::MAX, 0), usize::MAX / 4, usize::MAX);
+        assert_eq!(count(&huge), 0);
+        huge.fill_disk((2, 2), usize::MAX);
+        assert_eq!(count(&huge), 16);
+
+        let mut ring = Obstacles::new(4, 4);
+        ring.draw_circle((0, 0), usize::MAX, 1);
+        assert_eq!(count(&ring), 0);
+
+        let mut rect = Obstacles::new(4, 4);
+        rect.draw_rect(
+            (isize::MIN, isize::MIN),
+            (isize::MAX, isize::MAX),
+            usize::MAX,
+        );
+        assert_eq!(count(&rect), 16);
+    }
+
+    #[test]
+    fn test_fill_polygon() {
+        let mut square = Obstacles::new(10, 10);
+        square.fill_polygon(
+            &[(1.5, 1.5), (5.5, 1.5), (5.5, 4.5), (1.5, 4.5)],
+            FillRule::EvenOdd,
+        );
+        let expected: Vec<_> = (2..5).flat_map(|y| (2..6).map(move |x| (x, y))).collect();
+        assert_eq!(cells(&square), expected);
+
+        let mut triangle = Obstacles::new(10, 10);
+        triangle.fill_polygon(
+            &[(-5.0, -5.0), (20.0, -5.0), (-5.0, 20.0)],
+            FillRule::NonZero,
+        );
+        assert!(*triangle.get_at(0, 0) && *triangle.get_at(7, 0) && *triangle.get_at(0, 9));
+        assert!(!triangle.get_at(9, 9));
+    }
+
+    #[test]
+    fn test_fill_rules() {
+        let star: Vec<(f32, f32)> = (0..5)
+            .map(|k| {
+                let angle = (-90.0 + 144.0 * k as f32).to_radians();
+                (10.0 + 9.0 * angle.cos(), 10.0 + 9.0 * angle.sin())
+            })
+            .collect();
+
+        let mut even_odd = Obstacles::new(21, 21);
+        even_odd.fill_polygon(&star, FillRule::EvenOdd);
+        let mut non_zero = Obstacles::new(21, 21);
+        non_zero.fill_polygon(&star, FillRule::NonZero);
+
+        assert!(!even_odd.get_at(10, 10));
+        assert!(non_zero.get_at(10, 10));
+        assert!(*even_odd.get_at(10, 2) && *non_zero.get_at(10, 2));
+        assert!(count(&non_zero) > count(&even_odd));
+        assert!(even_odd.iter().zip(non_zero.iter()).all(|(&e, &n)| !e || n));
+    }
+}
@@ -0,0 +1,383 @@
+use crate::fast_marching::Trial;
+use crate::{
+    DistanceField, DistanceFieldAlgorithm, EikonalFastSweepingMethod, Error, Grid, GridBounds,
+    Obstacles,
+};
+use std::collections::{BinaryHeap, HashMap};
+
+/// A distance field that is repaired incrementally as obstacles are added or removed.
+///
+/// Instead of recomputing the whole field, [`update`](Self::update) only revisits the cells
+/// whose distance depends on the changed obstacles. Removed obstacles start a raise
+/// wavefront that resets every cell which is no longer supported by its neighbors; a lower
+/// wavefront then propagates the distances from added obstacles and from the border of the
+/// reset region, like the Fast Marching Method. The result equals a full solve with
+/// [`EikonalFastSweepingMethod`] on the updated obstacles, up to floating point rounding.
+#[derive(Debug, Clone)]
+pub struct DynamicDistanceMap {
+    solver: EikonalFastSweepingMethod,
+    obstacles: Obstacles,
+    distance_field: DistanceField,
+}
+
+impl DynamicDistanceMap {
+    /// Computes the initial distance field of `obstacles` with `solver`.
+    ///
+    /// The [maximum distance](EikonalFastSweepingMethod::with_max_distance) of `solver` is
+    /// ignored, as the repair needs the exact distances of all cells.
+    pub fn new(solver: EikonalFastSweepingMethod, obstacles: Obstacles) -> Self {
+        let solver = solver.with_max_distance(DistanceField::MAX_DISTANCE);
+        let mut distance_field = DistanceField::from(&obstacles);
+        solver.calculate_distance_field(&mut distance_field, &obstacles);
+        Self {
+            solver,
+            obstacles,
+            distance_field,
+        }
+    }
+
+    /// Continues from a distance field that `solver` computed for `obstacles`.
+    ///
+    /// The field must have converged, i.e. it must not have been cut short by
+    /// [`with_max_iterations`](EikonalFastSweepingMethod::with_max_iterations),
+    /// [`with_tolerance`](EikonalFastSweepingMethod::with_tolerance) or
+    /// [`with_max_distance`](EikonalFastSweepingMethod::with_max_distance).
+    pub fn from_distance_field(
+        solver: EikonalFastSweepingMethod,
+        obstacles: Obstacles,
+        distance_field: DistanceField,
+    ) -> Result<Self, Error> {
+        let solver = solver.with_max_distance(DistanceField::MAX_DISTANCE);
+        Error::check_dimensions(
+            (obstacles.width(), obstacles.height()),
+            (distance_field.width(), distance_field.height()),
+        )?;
+        Ok(Self {
+            solver,
+            obstacles,
+            distance_field,
+        })
+    }
+
+    /// Returns the current obstacles.
+    pub const fn obstacles(&self) -> &Obstacles {
+        &self.obstacles
+    }
+
+    /// Returns the current distance field.
+    pub const fn distance_field(&self) -> &DistanceField {
+        &self.distance_field
+    }
+
+    /// Returns the distance field, discarding the obstacles.
+    pub fn into_distance_field(self) -> DistanceField {
+        self.distance_field
+    }
+
+    /// Adds and removes obstacle cells and repairs the affected part of the distance field.
+    ///
+    /// Cells that already have the requested state are ignored, and removals are applied
+    /// after additions. Returns the number of cells whose distance changed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a cell lies outside of the grid. All cells are checked before any of them is
+    /// applied.
+    pub fn update(&mut self, added: &[(usize, usize)], removed: &[(usize, usize)]) -> usize {
+        for &(x, y) in added.iter().chain(removed) {
+            if let Err(error) = self.obstacles.check_bounds(x, y) {
+                panic!("{error}");
+            }
+        }
+        let width = self.obstacles.width();
+        let mut previous = HashMap::new();
+        let mut lower = BinaryHeap::new();
+        let mut raise = Vec::new();
+
+        for &(x, y) in added {
+            if !*self.obstacles.get_at(x, y) {
+                self.obstacles.set_at(x, y, true);
+                self.set_distance(x, y, 0.0, &mut previous);
+                lower.push(Trial {
+                    distance: 0.0,
+                    index: y * width + x,
+                });
+            }
+        }
+        for &(x, y) in removed {
+            if *self.obstacles.get_at(x, y) {
+                self.obstacles.set_at(x, y, false);
+                self.set_distance(x, y, DistanceField::MAX_DISTANCE, &mut previous);
+                raise.push((x, y));
+            }
+        }
+
+        // Raise: reset cells whose value can no longer be derived from their neighbors.
+        let mut reset = Vec::new();
+        while let Some((x, y)) = raise.pop() {
+            reset.push((x, y));
+            for (nx, ny) in self.neighbors(x, y) {
+                let distance = *self.distance_field.get_at(nx, ny);
+                if !*self.obstacles.get_at(nx, ny)
+                    && distance != DistanceField::MAX_DISTANCE
+                    && distance < self.solver.update_cell(&self.distance_field, nx, ny)
+                {
+                    self.set_distance(nx, ny, DistanceField::MAX_DISTANCE, &mut previous);
+                    raise.push((nx, ny));
+                }
+            }
+        }
+
+        // Lower: refill the reset cells from their border and propagate added obstacles.
+        for (x, y) in reset {
+            self.lower(x, y, &mut lower, &mut previous);
+        }
+        while let Some(Trial { distance, index }) = lower.pop() {
+            let (x, y) = (index % width, index / width);
+            if distance > *self.distance_field.get_at(x, y) {
+                continue;
+            }
+            for (nx, ny) in self.neighbors(x, y) {
+                self.lower(nx, ny, &mut lower, &mut previous);
+            }
+        }
+
+        previous
+            .into_iter()
+            .filter(|&(index, distance)| {
+                *self.distance_field.get_at(index % width, index / width) != distance
+            })
+            .count()
+    }
+
+    /// Sets the distance of a cell, remembering its value from before the update.
+    fn set_distance(
+        &mut self,
+        x: usize,
+        y: usize,
+        distance: f32,
+        previous: &mut HashMap<usize, f32>,
+    ) {
+        let index = y * self.obstacles.width() + x;
+        previous
+            .entry(index)
+            .or_insert(*self.distance_field.get_at(x, y));
+        self.distance_field.set_at(x, y, distance);
+    }
+
+    /// Lowers a free cell to the update from its neighbors and queues it if it improved.
+    fn lower(
+        &mut self,
+        x: usize,
+        y: usize,
+        queue: &mut BinaryHeap<Trial>,
+        previous: &mut HashMap<usize, f32>,
+    ) {
+        if *self.obstacles.get_at(x, y) {
+            return;
+        }
+        let distance = self.solver.update_cell(&self.distance_field, x, y);
+        if distance < *self.distance_field.get_at(x, y) {
+            self.set_distance(x, y, distance, previous);
+            queue.push(Trial {
+                distance,
+                index: y * self.obstacles.width() + x,
+            });
+        }
+    }
+
+    /// Returns the 4-connected neighbors of a cell within the grid.
+    fn neighbors(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> {
+        let (width, height) = (self.obstacles.width(), self.obstacles.height());
+        [
+            (x.wrapping_sub(1), y),
+            (x + 1, y),
+            (x, y.wrapping_sub(1)),
+            (x, y + 1),
+        ]
+        .into_iter()
+        .filter(move |&(x, y)| x < width && y < height)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A small xorshift generator, so that the randomized tests are reproducible.
+    struct Random(u64);
+
+    impl Random {
+        fn next(&mut self, bound: usize) -> usize {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            (self.0 % bound as u64) as usize
+        }
+
+        fn cells(&mut self, count: usize, width: usize, height: usize) -> Vec<(usize, usize)> {
+            (0..count)
+                .map(|_| (self.next(width), self.next(height)))
+                .collect()
+        }
+    }
+
+    fn full_solve(solver: EikonalFastSweepingMethod, obstacles: &Obstacles) -> DistanceField {
+        let mut df = DistanceField::from(obstacles);
+        solver.calculate_distance_field(&mut df, obstacles);
+        df
+    }
+
+    fn assert_matches_full_solve(map: &DynamicDistanceMap, solver: EikonalFastSweepingMethod) {
+        let expected = full_solve(solver, map.obstacles());
+        for (i, (actual, expected)) in map.distance_field().iter().zip(expected.iter()).enumerate()
+        {
+            // Different update orders may round the same solution differently.
+            let equal = actual == expected || (actual - expected).abs() <= 1e-5 * expected;
+            assert!(equal, "cell {i}: {actual} vs {expected}");
+        }
+    }
+
+    #[test]
+    fn test_add_obstacle() {
+        let mut obstacles = Obstacles::new(20, 20);
+        obstacles.set_at(2, 2, true);
+        let solver = EikonalFastSweepingMethod::default();
+        let mut map = DynamicDistanceMap::new(solver, obstacles);
+
+        let changed = map.update(&[(15, 15)], &[]);
+        assert_eq!(*map.distance_field().get_at(15, 15), 0.0);
+        assert_eq!(*map.distance_field().get_at(17, 15), 2.0);
+        assert!(changed > 0 && changed < 20 * 20);
+        assert_matches_full_solve(&map, solver);
+    }
+
+    #[test]
+    fn test_remove_obstacle() {
+        let mut obstacles = Obstacles::new(20, 20);
+        obstacles.set_at(2, 2, true);
+        obstacles.set_at(15, 15, true);
+        let solver = EikonalFastSweepingMethod::default();
+        let mut map = DynamicDistanceMap::new(solver, obstacles);
+
+        map.update(&[], &[(15, 15)]);
+        assert!(!map.obstacles().get_at(15, 15));
+        assert_matches_full_solve(&map, solver);
+
+        // Removing the last obstacle leaves every cell unreachable.
+        map.update(&[], &[(2, 2)]);
+        assert!(map
+            .distance_field()
+            .iter()
+            .all(|&d| d == DistanceField::MAX_DISTANCE));
+    }
+
+    #[test]
+    fn test_unchanged_cells_are_ignored() {
+        let mut obstacles = Obstacles::new(10, 10);
+        obstacles.set_at(5, 5, true);
+        let mut map = DynamicDistanceMap::new(EikonalFastSweepingMethod::default(), obstacles);
+        assert_eq!(map.update(&[(5, 5)], &[(1, 1)]), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "cell (10, 0) is outside of the 10x10 grid")]
+    fn test_update_rejects_cells_past_the_row_end() {
+        let obstacles = Obstacles::new(10, 10);
+        let mut map = DynamicDistanceMap::new(EikonalFastSweepingMethod::default(), obstacles);
+        map.update(&[(10, 0)], &[]);
+    }
+
+    #[test]
+    fn test_local_change_touches_few_cells() {
+        let mut obstacles = Obstacles::new(100, 100);
+        obstacles.draw_line((0, 50), (99, 50));
+        let mut map = DynamicDistanceMap::new(EikonalFastSweepingMethod::default(), obstacles);
+
+        // A bump on the wall only changes the distances in a cone above it.
+        let changed = map.update(&[(50, 51)], &[]);
+        assert!(
+            changed > 0 && changed < 100 * 100 / 10,
+            "{changed} cells changed"
+        );
+        let changed = map.update(&[], &[(50, 51)]);
+        assert!(
+            changed > 0 && changed < 100 * 100 / 10,
+            "{changed} cells changed"
+        );
+    }
+
+    #[test]
+    fn test_from_distance_field_checks_dimensions() {
+        let solver = EikonalFastSweepingMethod::default();
+        let obstacles = Obstacles::new(4, 3);
+        let result =
+            DynamicDistanceMap::from_distance_field(solver, obstacles, DistanceField::new(3, 4));
+        assert!(matches!(result, Err(Error::DimensionMismatch { .. })));
+    }
+
+    #[test]
+    fn test_random_updates_match_full_solve() {
+        let mut random = Random(0x9e37_79b9_7f4a_7c15);
+        for solver in [
+            EikonalFastSweepingMethod::default(),
+            EikonalFastSweepingMethod::default().with_spacing(0.5, 1.25),
+        ] {
+            let (width, height) = (37, 29);
+            let mut obstacles = Obstacles::new(width, height);
+            for (x, y) in random.cells(20, width, height) {
+                obstacles.set_at(x, y, true);
+            }
+            let df = full_solve(solver, &obstacles);
+            let mut map = DynamicDistanceMap::from_distance_field(solver, obstacles, df).unwrap();
+
+            for round in 0..50 {
+                let count = random.next(6);
+                let added = random.cells(count, width, height);
+                let count = random.next(6);
+                let mut removed = random.cells(count, width, height);
+                // Also remove existing obstacles, which random cells rarely hit.
+                let existing: Vec<_> = (0..width * height)
+                    .map(|i| (i % width, i / width))
+                    .filter(|&(x, y)| *map.obstacles().get_at(x, y))
+                    .collect();
+                if round % 2 == 0 && !existing.is_empty() {
+                    removed.push(existing[random.next(existing.len())]);
+                }
+                let before = map.distance_field().clone();
+                let changed = map.update(&added, &removed);
+                assert_matches_full_solve(&map, solver);
+                let expected = map
+                    .distance_field()
+                    .iter()
+                    .zip(before.iter())
+                    .filter(|(current, before)| current != before)
+                    .count();
+                assert_eq!(changed, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_random_walls_match_full_solve() {
+        let mut random = Random(42);
+        let solver = EikonalFastSweepingMethod::default();
+        let (width, height) = (48, 40);
+        let mut map = DynamicDistanceMap::new(solver, Obstacles::new(width, height));
+
+        // Walls appear and disappear, as when a sensor sweeps past moving objects.
+        let mut walls: Vec<Vec<(usize, usize)>> = Vec::new();
+        for _ in 0..40 {
+            if walls.len() > 3 && random.next(2) == 0 {
+                let wall = walls.swap_remove(random.next(walls.len()));
+                map.update(&[], &wall);
+            } else {
+                let (x, y) = (random.next(width - 8), random.next(height));
+                let wall: Vec<_> = (x..x + 8).map(|x| (x, y)).collect();
+                map.update(&wall, &[]);
+                walls.push(wall);
+            }
+            assert_matches_full_solve(&map, solver);
+        }
+    }
+}
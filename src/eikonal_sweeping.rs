@@ -0,0 +1,1414 @@
+use crate::cutoff::row_spans;
+use crate::nearest_obstacles;
+use crate::solve_report::{iterate_sweeps, SweepStats};
+use crate::{
+    CostField, Cutoff, DistanceField, DistanceField3, DistanceFieldAlgorithm,
+    DistanceFieldAlgorithm3, DistanceFieldAlgorithmN, DistanceFieldN, FeatureTransformAlgorithm,
+    Grid, NearestObstacles, Obstacles, Obstacles3, ObstaclesN, SolveReport, TravelTimeAlgorithm,
+};
+use std::ops::Range;
+
+/// Configuration and implementation of the Eikonal fast sweeping method.
+///
+/// Unlike [`NaiveFastSweepingMethod`](crate::NaiveFastSweepingMethod), which only relaxes
+/// along the grid axes and therefore produces Manhattan distances, this algorithm solves
+/// the Eikonal equation `|∇u| = 1` using the Godunov upwind discretization of Zhao's
+/// fast sweeping method. The result approximates Euclidean distances.
+#[derive(Debug, Clone, Copy)]
+pub struct EikonalFastSweepingMethod {
+    spacing: (f32, f32, f32),
+    max_iterations: usize,
+    tolerance: f32,
+    max_distance: f32,
+    cutoff: Cutoff,
+}
+
+impl EikonalFastSweepingMethod {
+    /// Sets the grid spacing used by the Eikonal update.
+    ///
+    /// A value of `1.0` means neighboring cells are one distance unit apart.
+    #[must_use]
+    pub const fn with_step_size(mut self, step_size: f32) -> Self {
+        self.spacing = (step_size, step_size, step_size);
+        self
+    }
+
+    /// Sets separate grid spacings along the x and y axis.
+    ///
+    /// Use this for grids with rectangular cells to obtain distances in physical units.
+    /// The spacing along the z axis of volumes is left unchanged.
+    #[must_use]
+    pub const fn with_spacing(mut self, dx: f32, dy: f32) -> Self {
+        self.spacing.0 = dx;
+        self.spacing.1 = dy;
+        self
+    }
+
+    /// Sets separate grid spacings along the x, y and z axis of volumes.
+    ///
+    /// Use this e.g. for medical volumes whose slice spacing differs from the in-plane spacing.
+    #[must_use]
+    pub const fn with_spacing3(mut self, dx: f32, dy: f32, dz: f32) -> Self {
+        self.spacing = (dx, dy, dz);
+        self
+    }
+
+    /// Sets the grid spacing along every axis of grids with `D` axes.
+    ///
+    /// The returned solver calculates [`DistanceFieldN`]s with `D` axes, e.g. 4D space-time
+    /// fields whose time axis is spaced differently from the spatial axes.
+    #[must_use]
+    pub const fn with_spacing_n<const D: usize>(
+        self,
+        spacing: [f32; D],
+    ) -> EikonalFastSweepingMethodN<D> {
+        EikonalFastSweepingMethodN {
+            method: self,
+            spacing,
+        }
+    }
+
+    /// Sets the maximum number of sweep iterations.
+    ///
+    /// If set to `0`, the algorithm runs until convergence (no changes detected).
+    #[must_use]
+    pub const fn with_max_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_iterations = max_iterations;
+        self
+    }
+
+    /// Sets the convergence tolerance.
+    ///
+    /// Sweeping stops once no cell changed by more than this value during an iteration.
+    /// The default of `0.0` sweeps until no cell changes at all.
+    #[must_use]
+    pub const fn with_tolerance(mut self, tolerance: f32) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Sets the largest distance or travel time of interest.
+    ///
+    /// Sweeps over 2D grids skip the cells that are farther than this from every obstacle
+    /// along both axes, which saves most of the work on large, sparse maps. Distances beyond
+    /// the maximum are replaced according to [`with_cutoff`](Self::with_cutoff).
+    #[must_use]
+    pub const fn with_max_distance(mut self, max_distance: f32) -> Self {
+        self.max_distance = max_distance;
+        self
+    }
+
+    /// Sets how distances beyond the [maximum distance](Self::with_max_distance) are
+    /// represented.
+    #[must_use]
+    pub const fn with_cutoff(mut self, cutoff: Cutoff) -> Self {
+        self.cutoff = cutoff;
+        self
+    }
+
+    /// Calculates the distance field like
+    /// [`calculate_distance_field`](DistanceFieldAlgorithm::calculate_distance_field)
+    /// and reports how the solve went.
+    pub fn calculate_distance_field_with_report(
+        &self,
+        distance_field: &mut DistanceField,
+        obstacles: &Obstacles,
+    ) -> SolveReport {
+        self.fast_sweeping(distance_field, None, obstacles, None)
+    }
+
+    /// Calculates the travel time like
+    /// [`calculate_travel_time`](TravelTimeAlgorithm::calculate_travel_time)
+    /// and reports how the solve went.
+    pub fn calculate_travel_time_with_report(
+        &self,
+        travel_time: &mut DistanceField,
+        obstacles: &Obstacles,
+        costs: &CostField,
+    ) -> SolveReport {
+        self.fast_sweeping(travel_time, None, obstacles, Some(costs))
+    }
+
+    /// Extends the finite values of a distance field to the remaining cells, without
+    /// reinitializing it from obstacles.
+    ///
+    /// Use this after seeding the field with exact distances, e.g. with
+    /// [`VectorGeometry::seed`](crate::VectorGeometry::seed). Cells only ever decrease, so
+    /// seeded values stay unchanged as long as they are consistent with each other.
+    pub fn propagate(&self, distance_field: &mut DistanceField) -> SolveReport {
+        let report = self.perform_sweeps(distance_field, None, None, None);
+        self.apply_cutoff(distance_field.iter_mut());
+        report
+    }
+
+    /// Returns the Godunov update of a single cell from its current neighbors.
+    pub(crate) fn update_cell(&self, distance_field: &DistanceField, x: usize, y: usize) -> f32 {
+        let (width, height) = (distance_field.width(), distance_field.height());
+        let neighbor = |x: Option<usize>, y: Option<usize>| match (x, y) {
+            (Some(x), Some(y)) if x < width && y < height => *distance_field.get_at(x, y),
+            _ => DistanceField::MAX_DISTANCE,
+        };
+        let left = neighbor(x.checked_sub(1), Some(y));
+        let right = neighbor(Some(x + 1), Some(y));
+        let up = neighbor(Some(x), y.checked_sub(1));
+        let down = neighbor(Some(x), Some(y + 1));
+        godunov_update(
+            left.min(right),
+            up.min(down),
+            self.spacing.0,
+            self.spacing.1,
+        )
+    }
+
+    fn fast_sweeping(
+        &self,
+        distance_field: &mut DistanceField,
+        mut nearest: Option<&mut NearestObstacles>,
+        obstacles: &Obstacles,
+        costs: Option<&CostField>,
+    ) -> SolveReport {
+        if let Some(costs) = costs {
+            costs.assert_dimensions((obstacles.width(), obstacles.height()));
+            costs.assert_dimensions((distance_field.width(), distance_field.height()));
+        }
+        self.initialize(distance_field, obstacles);
+        if let Some(nearest) = nearest.as_deref_mut() {
+            nearest.initialize(obstacles);
+        }
+
+        // A cell is at least h / √2 farther away than its closest neighbor, with the spacing
+        // h scaled by the cheapest traversal cost.
+        let (dx, dy, _) = self.spacing;
+        let min_cost = costs.map_or(1.0, |costs| {
+            costs.iter().copied().fold(f32::INFINITY, f32::min)
+        });
+        let min_step = dx.min(dy) * min_cost / std::f32::consts::SQRT_2;
+        // Without a positive lower bound on the step, no cell can be ruled out.
+        let spans = (self.max_distance.is_finite() && min_step > 0.0)
+            .then(|| row_spans(obstacles, self.max_distance / min_step));
+
+        let report = self.perform_sweeps(distance_field, nearest, costs, spans.as_deref());
+        self.apply_cutoff(distance_field.iter_mut());
+        report
+    }
+
+    /// Replaces the distances beyond the maximum distance.
+    fn apply_cutoff<'a>(&self, distances: impl IntoIterator<Item = &'a mut f32>) {
+        if self.max_distance < DistanceField::MAX_DISTANCE {
+            self.cutoff.apply(distances, self.max_distance);
+        }
+    }
+
+    fn initialize(&self, distance_field: &mut DistanceField, obstacles: &Obstacles) {
+        for (dist, &is_obstacle) in distance_field.iter_mut().zip(obstacles.iter()) {
+            *dist = if is_obstacle {
+                0_f32
+            } else {
+                DistanceField::MAX_DISTANCE
+            };
+        }
+    }
+
+    fn perform_sweeps(
+        &self,
+        distance_field: &mut DistanceField,
+        mut nearest: Option<&mut NearestObstacles>,
+        costs: Option<&CostField>,
+        spans: Option<&[Range<usize>]>,
+    ) -> SolveReport {
+        iterate_sweeps(self.max_iterations, self.tolerance, || {
+            // Same ordering as the naive method: top-left to bottom-right,
+            // bottom-right to top-left, top-right to bottom-left, bottom-left to top-right.
+            [(false, false), (true, true), (true, false), (false, true)].map(
+                |(reverse_x, reverse_y)| {
+                    self.sweep(
+                        distance_field,
+                        nearest.as_deref_mut(),
+                        costs,
+                        spans,
+                        reverse_x,
+                        reverse_y,
+                    )
+                },
+            )
+        })
+    }
+
+    fn sweep(
+        &self,
+        distance_field: &mut DistanceField,
+        mut nearest: Option<&mut NearestObstacles>,
+        costs: Option<&CostField>,
+        spans: Option<&[Range<usize>]>,
+        reverse_x: bool,
+        reverse_y: bool,
+    ) -> SweepStats {
+        let (dx, dy, _) = self.spacing;
+        let costs = costs.map(CostField::as_slice);
+        let height = distance_field.height();
+        let width = distance_field.width();
+        let distances = distance_field.as_mut_slice();
+        let mut stats = SweepStats::default();
+
+        for j in 0..height {
+            let y = if reverse_y { height - 1 - j } else { j };
+            let row = y * width;
+            let span = spans.map_or(0..width, |spans| spans[y].clone());
+
+            for i in 0..span.len() {
+                let x = if reverse_x {
+                    span.end - 1 - i
+                } else {
+                    span.start + i
+                };
+                let center = distances[row + x];
+
+                // The local grid spacing is scaled by the traversal cost of the cell;
+                // impassable cells are never updated.
+                let cost = costs.map_or(1.0, |costs| costs[row + x]);
+                let (hx, hy) = (dx * cost, dy * cost);
+                if hx == f32::INFINITY || hy == f32::INFINITY {
+                    continue;
+                }
+
+                let left = if x > 0 {
+                    distances[row + x - 1]
+                } else {
+                    DistanceField::MAX_DISTANCE
+                };
+                let right = if x + 1 < width {
+                    distances[row + x + 1]
+                } else {
+                    DistanceField::MAX_DISTANCE
+                };
+                let up = if y > 0 {
+                    distances[row - width + x]
+                } else {
+                    DistanceField::MAX_DISTANCE
+                };
+                let down = if y + 1 < height {
+                    distances[row + width + x]
+                } else {
+                    DistanceField::MAX_DISTANCE
+                };
+
+                let new_value = godunov_update(left.min(right), up.min(down), hx, hy);
+                if new_value < center {
+                    distances[row + x] = new_value;
+                    stats.record(center, new_value);
+
+                    // The nearest obstacle is inherited from the smallest upwind neighbor.
+                    if let Some(nearest) = nearest.as_deref_mut() {
+                        let source = if left.min(right) + hx <= up.min(down) + hy {
+                            if left <= right {
+                                (x - 1, y)
+                            } else {
+                                (x + 1, y)
+                            }
+                        } else if up <= down {
+                            (x, y - 1)
+                        } else {
+                            (x, y + 1)
+                        };
+                        nearest.propagate(source, (x, y));
+                    }
+                }
+            }
+        }
+        stats
+    }
+
+    /// Solves a grid with `D` axes, sweeping in all `2^D` directions.
+    fn fast_sweeping_n<const D: usize>(
+        &self,
+        distance_field: &mut DistanceFieldN<D>,
+        obstacles: &ObstaclesN<D>,
+        spacing: [f32; D],
+    ) -> SolveReport {
+        assert_eq!(
+            obstacles.shape(),
+            distance_field.shape(),
+            "the obstacles do not match the shape of the distance field"
+        );
+        for (dist, &is_obstacle) in distance_field.iter_mut().zip(obstacles.iter()) {
+            *dist = if is_obstacle {
+                0_f32
+            } else {
+                DistanceField::MAX_DISTANCE
+            };
+        }
+
+        let report = iterate_sweeps(self.max_iterations, self.tolerance, || {
+            sweep_directions::<D>()
+                .map(|reversed| Self::sweep_n(distance_field, &spacing, reversed))
+                .collect::<Vec<_>>()
+        });
+        self.apply_cutoff(distance_field.iter_mut());
+        report
+    }
+
+    /// Sweeps once over all cells, traversing axis `i` in reverse if bit `i` of `reversed` is set.
+    fn sweep_n<const D: usize>(
+        distance_field: &mut DistanceFieldN<D>,
+        spacing: &[f32; D],
+        reversed: usize,
+    ) -> SweepStats {
+        let shape = distance_field.shape();
+        let strides = distance_field.strides();
+        let mut stats = SweepStats::default();
+        if distance_field.is_empty() {
+            return stats;
+        }
+        let distances = distance_field.as_mut_slice();
+
+        let mut counter = [0; D];
+        let mut neighbors = [(0_f32, 0_f32); D];
+        loop {
+            let position: [usize; D] = std::array::from_fn(|axis| {
+                if reversed >> axis & 1 == 1 {
+                    shape[axis] - 1 - counter[axis]
+                } else {
+                    counter[axis]
+                }
+            });
+            let index: usize = position.iter().zip(&strides).map(|(p, s)| p * s).sum();
+
+            for (axis, neighbor) in neighbors.iter_mut().enumerate() {
+                let stride = strides[axis];
+                let mut value = DistanceField::MAX_DISTANCE;
+                if position[axis] > 0 {
+                    value = value.min(distances[index - stride]);
+                }
+                if position[axis] + 1 < shape[axis] {
+                    value = value.min(distances[index + stride]);
+                }
+                *neighbor = (value, spacing[axis]);
+            }
+
+            let center = distances[index];
+            let new_value = godunov_update_n(&mut neighbors);
+            if new_value < center {
+                distances[index] = new_value;
+                stats.record(center, new_value);
+            }
+
+            // Advance the counter like an odometer, with the first axis varying fastest.
+            let mut axis = 0;
+            loop {
+                if axis == D {
+                    return stats;
+                }
+                counter[axis] += 1;
+                if counter[axis] < shape[axis] {
+                    break;
+                }
+                counter[axis] = 0;
+                axis += 1;
+            }
+        }
+    }
+}
+
+/// Solves the discretized Eikonal equation `|∇u| = 1` for a single cell.
+///
+/// `a` and `b` are the smallest neighbor values along the x and y axis, respectively,
+/// and `hx` and `hy` are the grid spacings along these axes. Returns
+/// [`DistanceField::MAX_DISTANCE`] if neither neighbor has been reached yet.
+pub(crate) fn godunov_update(a: f32, b: f32, hx: f32, hy: f32) -> f32 {
+    if a.min(b) == DistanceField::MAX_DISTANCE {
+        return DistanceField::MAX_DISTANCE;
+    }
+
+    // The one-sided solution is valid if it does not exceed the other neighbor.
+    let one_sided = (a + hx).min(b + hy);
+    if one_sided <= a.max(b) {
+        return one_sided;
+    }
+
+    // Solve (u - a)² / hx² + (u - b)² / hy² = 1 for the larger root.
+    let (hx2, hy2) = (hx * hx, hy * hy);
+    let diff = a - b;
+    (a * hy2 + b * hx2 + hx * hy * (hx2 + hy2 - diff * diff).sqrt()) / (hx2 + hy2)
+}
+
+/// Returns the sweep directions of a grid with `D` axes, as bit masks of the reversed axes.
+///
+/// Every direction is followed by its opposite, like the four directions of 2D grids.
+fn sweep_directions<const D: usize>() -> impl Iterator<Item = usize> {
+    let count = 1_usize << D;
+    (0..count).map(move |i| {
+        if i % 2 == 0 {
+            i / 2
+        } else {
+            (count - 1) ^ (i / 2)
+        }
+    })
+}
+
+/// Solves the discretized Eikonal equation `|∇u| = 1` for a single cell of a grid with any
+/// number of dimensions.
+///
+/// `neighbors` holds the smallest neighbor value and the grid spacing along every axis and is
+/// reordered by this function. Returns [`DistanceField::MAX_DISTANCE`] if no neighbor has been
+/// reached yet.
+pub(crate) fn godunov_update_n(neighbors: &mut [(f32, f32)]) -> f32 {
+    neighbors.sort_unstable_by(|l, r| l.0.total_cmp(&r.0));
+    let Some(&(a, h)) = neighbors.first() else {
+        return DistanceField::MAX_DISTANCE;
+    };
+    if a == DistanceField::MAX_DISTANCE {
+        return DistanceField::MAX_DISTANCE;
+    }
+
+    // Include neighbors in increasing order while they are smaller than the solution, solving
+    // Σ (u - aᵢ)² / hᵢ² = 1 relative to the smallest neighbor to limit cancellation.
+    let mut u = a + h;
+    let (mut weights, mut weighted, mut squares) = (0_f32, 0_f32, 0_f32);
+    for &(value, h) in neighbors.iter() {
+        if value >= u {
+            break;
+        }
+        let (w, v) = (1.0 / (h * h), value - a);
+        weights += w;
+        weighted += v * w;
+        squares += v * v * w;
+        let discriminant = weighted * weighted - weights * (squares - 1.0);
+        u = a + (weighted + discriminant.sqrt()) / weights;
+    }
+    u
+}
+
+impl Default for EikonalFastSweepingMethod {
+    fn default() -> Self {
+        Self {
+            spacing: (1.0, 1.0, 1.0),
+            max_iterations: 0,
+            tolerance: 0.0,
+            max_distance: DistanceField::MAX_DISTANCE,
+            cutoff: Cutoff::Clamp,
+        }
+    }
+}
+
+impl DistanceFieldAlgorithm for EikonalFastSweepingMethod {
+    fn calculate_distance_field(&self, distance_field: &mut DistanceField, obstacles: &Obstacles) {
+        self.fast_sweeping(distance_field, None, obstacles, None);
+    }
+}
+
+impl FeatureTransformAlgorithm for EikonalFastSweepingMethod {
+    fn calculate_feature_transform(
+        &self,
+        distance_field: &mut DistanceField,
+        nearest: &mut NearestObstacles,
+        obstacles: &Obstacles,
+    ) {
+        if let Err(error) = nearest_obstacles::check_dimensions(distance_field, nearest, obstacles)
+        {
+            panic!("{error}");
+        }
+        self.fast_sweeping(distance_field, Some(nearest), obstacles, None);
+    }
+}
+
+impl DistanceFieldAlgorithm3 for EikonalFastSweepingMethod {
+    fn calculate_distance_field3(
+        &self,
+        distance_field: &mut DistanceField3,
+        obstacles: &Obstacles3,
+    ) {
+        let (dx, dy, dz) = self.spacing;
+        self.fast_sweeping_n(
+            distance_field.as_grid_mut(),
+            obstacles.as_grid(),
+            [dx, dy, dz],
+        );
+    }
+}
+
+/// The Eikonal fast sweeping method on grids with `D` axes and a separate spacing per axis.
+///
+/// Created by [`EikonalFastSweepingMethod::with_spacing_n`], whose remaining configuration
+/// applies as for 2D grids. Like for volumes, the maximum distance is applied to the result
+/// but does not restrict the sweeps.
+#[derive(Debug, Clone, Copy)]
+pub struct EikonalFastSweepingMethodN<const D: usize> {
+    method: EikonalFastSweepingMethod,
+    spacing: [f32; D],
+}
+
+impl<const D: usize> EikonalFastSweepingMethodN<D> {
+    /// Calculates the distance field like
+    /// [`calculate_distance_field_n`](DistanceFieldAlgorithmN::calculate_distance_field_n)
+    /// and reports how the solve went.
+    pub fn calculate_distance_field_n_with_report(
+        &self,
+        distance_field: &mut DistanceFieldN<D>,
+        obstacles: &ObstaclesN<D>,
+    ) -> SolveReport {
+        self.method
+            .fast_sweeping_n(distance_field, obstacles, self.spacing)
+    }
+}
+
+impl<const D: usize> DistanceFieldAlgorithmN<D> for EikonalFastSweepingMethodN<D> {
+    fn calculate_distance_field_n(
+        &self,
+        distance_field: &mut DistanceFieldN<D>,
+        obstacles: &ObstaclesN<D>,
+    ) {
+        self.calculate_distance_field_n_with_report(distance_field, obstacles);
+    }
+}
+
+impl TravelTimeAlgorithm for EikonalFastSweepingMethod {
+    fn calculate_travel_time(
+        &self,
+        travel_time: &mut DistanceField,
+        obstacles: &Obstacles,
+        costs: &CostField,
+    ) {
+        self.fast_sweeping(travel_time, None, obstacles, Some(costs));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Grid, Grid3};
+
+    /// Maximum absolute error, in cells, of the first-order scheme with respect to the
+    /// analytic Euclidean distance from a point source. The scheme never underestimates;
+    /// its error grows logarithmically with distance and stays below this bound for the
+    /// grid sizes (up to 41×41) used in these tests.
+    const POINT_SOURCE_TOLERANCE: f32 = 1.25;
+
+    /// Like [`POINT_SOURCE_TOLERANCE`], but for volumes up to 21×21×21, where the error
+    /// along the space diagonals is larger.
+    const VOLUME_POINT_SOURCE_TOLERANCE: f32 = 1.5;
+
+    fn point_source_field(size: usize, cx: usize, cy: usize, step_size: f32) -> DistanceField {
+        let mut obstacles = Obstacles::new(size, size);
+        obstacles.set_at(cx, cy, true);
+        let mut df = DistanceField::from(&obstacles);
+        let algo = EikonalFastSweepingMethod::default().with_step_size(step_size);
+        algo.calculate_distance_field(&mut df, &obstacles);
+        df
+    }
+
+    fn euclidean(x: usize, y: usize, cx: usize, cy: usize) -> f32 {
+        let dx = x as f32 - cx as f32;
+        let dy = y as f32 - cy as f32;
+        (dx * dx + dy * dy).sqrt()
+    }
+
+    #[test]
+    fn test_default_values() {
+        let algo = EikonalFastSweepingMethod::default();
+        assert_eq!(algo.spacing, (1.0, 1.0, 1.0));
+        assert_eq!(algo.max_iterations, 0);
+        assert_eq!(algo.tolerance, 0.0);
+    }
+
+    #[test]
+    fn test_builder_step_size() {
+        let algo = EikonalFastSweepingMethod::default().with_step_size(0.5);
+        assert_eq!(algo.spacing, (0.5, 0.5, 0.5));
+    }
+
+    #[test]
+    fn test_builder_max_iterations() {
+        let algo = EikonalFastSweepingMethod::default().with_max_iterations(10);
+        assert_eq!(algo.max_iterations, 10);
+    }
+
+    #[test]
+    fn test_godunov_update_one_sided() {
+        assert_eq!(godunov_update(2.0, f32::INFINITY, 1.0, 1.0), 3.0);
+        assert_eq!(godunov_update(f32::INFINITY, 2.0, 1.0, 1.0), 3.0);
+        assert_eq!(godunov_update(0.0, 5.0, 1.0, 1.0), 1.0);
+    }
+
+    #[test]
+    fn test_godunov_update_two_sided() {
+        let value = godunov_update(1.0, 1.0, 1.0, 1.0);
+        assert!((value - (1.0 + 0.5_f32.sqrt())).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_godunov_update_anisotropic() {
+        // One-sided updates use the spacing of their axis.
+        assert_eq!(godunov_update(0.0, f32::INFINITY, 1.0, 2.0), 1.0);
+        assert_eq!(godunov_update(f32::INFINITY, 0.0, 1.0, 2.0), 2.0);
+
+        // A plane wave at 45° in physical space satisfies both difference quotients.
+        let (hx, hy) = (1.0_f32, 2.0_f32);
+        let (a, b) = (hx / 2.0_f32.sqrt(), hy / 2.0_f32.sqrt());
+        let expected = 2.0 * a;
+        let value = godunov_update(a, 2.0 * a - b, hx, hy);
+        assert!((value - expected).abs() < 1e-6, "{value} != {expected}");
+    }
+
+    #[test]
+    fn test_godunov_update_unreached() {
+        assert!(godunov_update(f32::INFINITY, f32::INFINITY, 1.0, 1.0).is_infinite());
+    }
+
+    #[test]
+    fn test_anisotropic_axis_distances_are_physical() {
+        let mut obstacles = Obstacles::new(21, 21);
+        obstacles.set_at(10, 10, true);
+        let mut df = DistanceField::from(&obstacles);
+        EikonalFastSweepingMethod::default()
+            .with_spacing(1.0, 2.0)
+            .calculate_distance_field(&mut df, &obstacles);
+
+        for k in 1..=10 {
+            assert_eq!(*df.get_at(10 + k, 10), k as f32);
+            assert_eq!(*df.get_at(10 - k, 10), k as f32);
+            assert_eq!(*df.get_at(10, 10 + k), 2.0 * k as f32);
+            assert_eq!(*df.get_at(10, 10 - k), 2.0 * k as f32);
+        }
+    }
+
+    #[test]
+    fn test_anisotropic_point_source_matches_physical_distance() {
+        let (cx, cy) = (40, 20);
+        let mut obstacles = Obstacles::new(81, 41);
+        obstacles.set_at(cx, cy, true);
+        let mut df = DistanceField::from(&obstacles);
+        EikonalFastSweepingMethod::default()
+            .with_spacing(0.5, 1.0)
+            .calculate_distance_field(&mut df, &obstacles);
+
+        for y in 0..41 {
+            for x in 0..81 {
+                let dx = (x as f32 - cx as f32) * 0.5;
+                let dy = y as f32 - cy as f32;
+                let expected = (dx * dx + dy * dy).sqrt();
+                let actual = *df.get_at(x, y);
+                assert!(
+                    (actual - expected).abs() < POINT_SOURCE_TOLERANCE,
+                    "Mismatch at ({x}, {y}): {actual} != {expected}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_anisotropic_spacing_is_transpose_symmetric() {
+        let mut obstacles = Obstacles::new(30, 20);
+        let mut transposed = Obstacles::new(20, 30);
+        for (x, y) in [(3, 4), (25, 15), (12, 9)] {
+            obstacles.set_at(x, y, true);
+            transposed.set_at(y, x, true);
+        }
+
+        let mut df = DistanceField::from(&obstacles);
+        EikonalFastSweepingMethod::default()
+            .with_spacing(1.0, 2.0)
+            .calculate_distance_field(&mut df, &obstacles);
+        let mut dft = DistanceField::from(&transposed);
+        EikonalFastSweepingMethod::default()
+            .with_spacing(2.0, 1.0)
+            .calculate_distance_field(&mut dft, &transposed);
+
+        for y in 0..20 {
+            for x in 0..30 {
+                let (a, b) = (*df.get_at(x, y), *dft.get_at(y, x));
+                assert!((a - b).abs() < 1e-4, "Mismatch at ({x}, {y}): {a} != {b}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_obstacle_cells_have_zero_distance() {
+        let df = point_source_field(10, 5, 5, 1.0);
+        assert_eq!(*df.get_at(5, 5), 0.0);
+    }
+
+    #[test]
+    fn test_empty_grid_converges() {
+        let obstacles = Obstacles::new(10, 10);
+        let mut df = DistanceField::from(&obstacles);
+        EikonalFastSweepingMethod::default().calculate_distance_field(&mut df, &obstacles);
+        for &dist in df.iter() {
+            assert!(dist.is_infinite(), "Empty grid should retain MAX_DISTANCE");
+        }
+    }
+
+    #[test]
+    fn test_axis_distances_are_exact() {
+        let df = point_source_field(21, 10, 10, 1.0);
+        for k in 1..=10 {
+            assert_eq!(*df.get_at(10 + k, 10), k as f32);
+            assert_eq!(*df.get_at(10 - k, 10), k as f32);
+            assert_eq!(*df.get_at(10, 10 + k), k as f32);
+            assert_eq!(*df.get_at(10, 10 - k), k as f32);
+        }
+    }
+
+    #[test]
+    fn test_point_source_matches_euclidean() {
+        let (size, cx, cy) = (41, 20, 20);
+        let df = point_source_field(size, cx, cy, 1.0);
+        for y in 0..size {
+            for x in 0..size {
+                let expected = euclidean(x, y, cx, cy);
+                let actual = *df.get_at(x, y);
+                assert!(
+                    actual >= expected - 1e-4,
+                    "({x}, {y}): {actual} underestimates {expected}"
+                );
+                assert!(
+                    actual - expected <= POINT_SOURCE_TOLERANCE,
+                    "({x}, {y}): {actual} deviates from {expected}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_point_source_off_center() {
+        let (size, cx, cy) = (32, 3, 27);
+        let df = point_source_field(size, cx, cy, 1.0);
+        for y in 0..size {
+            for x in 0..size {
+                let expected = euclidean(x, y, cx, cy);
+                let actual = *df.get_at(x, y);
+                assert!(
+                    (actual - expected).abs() <= POINT_SOURCE_TOLERANCE,
+                    "({x}, {y}): {actual} deviates from {expected}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_beats_manhattan_on_diagonal() {
+        let df = point_source_field(21, 10, 10, 1.0);
+        let diagonal = *df.get_at(20, 20);
+        assert!(
+            diagonal < 20.0,
+            "Diagonal should be shorter than Manhattan distance"
+        );
+        let expected = euclidean(20, 20, 10, 10);
+        assert!((diagonal - expected).abs() <= POINT_SOURCE_TOLERANCE);
+    }
+
+    #[test]
+    fn test_step_size_scales_distances() {
+        let df1 = point_source_field(15, 7, 7, 1.0);
+        let df2 = point_source_field(15, 7, 7, 0.5);
+        for (a, b) in df1.iter().zip(df2.iter()) {
+            assert!((a * 0.5 - b).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_line_obstacle_distances() {
+        let mut obstacles = Obstacles::new(7, 7);
+        for y in 0..7 {
+            obstacles.set_at(3, y, true);
+        }
+        let mut df = DistanceField::from(&obstacles);
+        EikonalFastSweepingMethod::default().calculate_distance_field(&mut df, &obstacles);
+
+        for y in 0..7 {
+            assert_eq!(*df.get_at(3, y), 0.0);
+            assert_eq!(*df.get_at(2, y), 1.0);
+            assert_eq!(*df.get_at(0, y), 3.0);
+            assert_eq!(*df.get_at(6, y), 3.0);
+        }
+    }
+
+    #[test]
+    fn test_convergence_detection() {
+        let mut obstacles = Obstacles::new(64, 48);
+        obstacles.set_at(10, 10, true);
+        obstacles.set_at(50, 40, true);
+
+        let mut df1 = DistanceField::from(&obstacles);
+        EikonalFastSweepingMethod::default().calculate_distance_field(&mut df1, &obstacles);
+
+        let mut df2 = DistanceField::from(&obstacles);
+        EikonalFastSweepingMethod::default()
+            .with_max_iterations(100)
+            .calculate_distance_field(&mut df2, &obstacles);
+
+        for (a, b) in df1.iter().zip(df2.iter()) {
+            assert_eq!(*a, *b, "Convergence should match limited run");
+        }
+    }
+
+    #[test]
+    fn test_report_converged() {
+        let mut obstacles = Obstacles::new(64, 48);
+        obstacles.set_at(10, 10, true);
+        obstacles.set_at(50, 40, true);
+
+        let mut df = DistanceField::from(&obstacles);
+        let report = EikonalFastSweepingMethod::default()
+            .calculate_distance_field_with_report(&mut df, &obstacles);
+        assert!(report.converged);
+        assert_eq!(report.max_delta, 0.0);
+        assert_eq!(report.cells_changed.len(), 4 * report.iterations);
+        assert!(report.cells_changed.iter().rev().take(4).all(|&n| n == 0));
+
+        let mut capped = DistanceField::from(&obstacles);
+        let report = EikonalFastSweepingMethod::default()
+            .with_max_iterations(1)
+            .calculate_distance_field_with_report(&mut capped, &obstacles);
+        assert_eq!(report.iterations, 1);
+        assert!(!report.converged);
+    }
+
+    #[test]
+    fn test_tolerance_bounds_last_change() {
+        let mut obstacles = Obstacles::new(64, 48);
+        obstacles.set_at(10, 10, true);
+        let mut costs = CostField::from(&obstacles);
+        for y in 0..40 {
+            costs.set_at(30, y, CostField::IMPASSABLE);
+        }
+
+        let mut exact = DistanceField::from(&obstacles);
+        let exact_report = EikonalFastSweepingMethod::default()
+            .calculate_travel_time_with_report(&mut exact, &obstacles, &costs);
+
+        let mut loose = DistanceField::from(&obstacles);
+        let loose_report = EikonalFastSweepingMethod::default()
+            .with_tolerance(0.5)
+            .calculate_travel_time_with_report(&mut loose, &obstacles, &costs);
+
+        assert!(loose_report.converged);
+        assert!(loose_report.max_delta <= 0.5);
+        assert!(loose_report.iterations <= exact_report.iterations);
+    }
+
+    #[test]
+    fn test_uniform_cost_matches_distance_field() {
+        let mut obstacles = Obstacles::new(16, 12);
+        obstacles.set_at(3, 4, true);
+        obstacles.set_at(12, 9, true);
+        let algo = EikonalFastSweepingMethod::default().with_step_size(0.5);
+
+        let mut distances = DistanceField::from(&obstacles);
+        algo.calculate_distance_field(&mut distances, &obstacles);
+
+        let mut costs = CostField::from(&obstacles);
+        let mut travel_time = DistanceField::from(&obstacles);
+        algo.calculate_travel_time(&mut travel_time, &obstacles, &costs);
+        for (a, b) in distances.iter().zip(travel_time.iter()) {
+            assert_eq!(*a, *b);
+        }
+
+        for cost in costs.iter_mut() {
+            *cost = 2.0;
+        }
+        algo.calculate_travel_time(&mut travel_time, &obstacles, &costs);
+        for (a, b) in distances.iter().zip(travel_time.iter()) {
+            assert!((a * 2.0 - b).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_slow_region_increases_travel_time() {
+        let mut obstacles = Obstacles::new(11, 2);
+        let mut costs = CostField::from(&obstacles);
+        for y in 0..2 {
+            obstacles.set_at(0, y, true);
+            for x in 5..8 {
+                costs.set_at(x, y, 3.0);
+            }
+        }
+
+        let mut travel_time = DistanceField::from(&obstacles);
+        EikonalFastSweepingMethod::default().calculate_travel_time(
+            &mut travel_time,
+            &obstacles,
+            &costs,
+        );
+        assert_eq!(*travel_time.get_at(4, 0), 4.0);
+        assert_eq!(*travel_time.get_at(7, 0), 13.0);
+        assert_eq!(*travel_time.get_at(10, 1), 16.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "do not match")]
+    fn test_travel_time_rejects_wider_costs() {
+        let obstacles = Obstacles::new(8, 6);
+        let mut travel_time = DistanceField::from(&obstacles);
+        EikonalFastSweepingMethod::default().calculate_travel_time_with_report(
+            &mut travel_time,
+            &obstacles,
+            &CostField::new(9, 6),
+        );
+    }
+
+    #[test]
+    fn test_non_positive_costs_do_not_collapse_the_sweeps() {
+        let mut obstacles = Obstacles::new(5, 1);
+        obstacles.set_at(0, 0, true);
+        for cost in [-1.0, 0.0] {
+            let mut costs = CostField::from(&obstacles);
+            costs.set_at(2, 0, cost);
+            costs.set_at(3, 0, cost);
+            let mut travel_time = DistanceField::from(&obstacles);
+            // Negative costs never converge, so a single pass has to reach every cell.
+            EikonalFastSweepingMethod::default()
+                .with_max_iterations(1)
+                .calculate_travel_time(&mut travel_time, &obstacles, &costs);
+            assert!(travel_time.iter().all(|t| t.is_finite()), "cost {cost}");
+        }
+    }
+
+    #[test]
+    fn test_impassable_cells_are_honoured() {
+        let mut obstacles = Obstacles::new(9, 9);
+        obstacles.set_at(1, 4, true);
+        let mut costs = CostField::from(&obstacles);
+        // A wall with a single gap at the bottom.
+        for y in 0..8 {
+            costs.set_at(4, y, CostField::IMPASSABLE);
+        }
+
+        let mut travel_time = DistanceField::from(&obstacles);
+        EikonalFastSweepingMethod::default().calculate_travel_time(
+            &mut travel_time,
+            &obstacles,
+            &costs,
+        );
+
+        for y in 0..8 {
+            assert!(travel_time.get_at(4, y).is_infinite());
+        }
+        // The cell right behind the wall must be reached through the gap.
+        let behind = *travel_time.get_at(5, 4);
+        assert!(behind.is_finite());
+        assert!(behind > 8.0, "Travel time {behind} ignores the wall");
+    }
+
+    #[test]
+    fn test_enclosed_region_is_unreachable() {
+        let mut obstacles = Obstacles::new(7, 7);
+        obstacles.set_at(0, 0, true);
+        let mut costs = CostField::from(&obstacles);
+        for i in 2..5 {
+            costs.set_at(i, 2, CostField::IMPASSABLE);
+            costs.set_at(i, 4, CostField::IMPASSABLE);
+            costs.set_at(2, i, CostField::IMPASSABLE);
+            costs.set_at(4, i, CostField::IMPASSABLE);
+        }
+
+        let mut travel_time = DistanceField::from(&obstacles);
+        EikonalFastSweepingMethod::default().calculate_travel_time(
+            &mut travel_time,
+            &obstacles,
+            &costs,
+        );
+        assert!(travel_time.get_at(3, 3).is_infinite());
+        assert!(travel_time.get_at(6, 6).is_finite());
+    }
+
+    #[test]
+    fn test_feature_transform_consistent_with_distances() {
+        let mut obstacles = Obstacles::new(40, 30);
+        for (x, y) in [(3, 3), (20, 5), (35, 25), (10, 22), (30, 12)] {
+            obstacles.set_at(x, y, true);
+        }
+        let mut df = DistanceField::from(&obstacles);
+        let mut nearest = NearestObstacles::from(&obstacles);
+        let algo = EikonalFastSweepingMethod::default();
+        algo.calculate_feature_transform(&mut df, &mut nearest, &obstacles);
+
+        let mut reference = DistanceField::from(&obstacles);
+        algo.calculate_distance_field(&mut reference, &obstacles);
+
+        for y in 0..obstacles.height() {
+            for x in 0..obstacles.width() {
+                let (nx, ny) = nearest.get_at(x, y).expect("every cell has a source");
+                assert!(*obstacles.get_at(nx, ny));
+                assert_eq!(*df.get_at(x, y), *reference.get_at(x, y));
+
+                let to_source = euclidean(x, y, nx, ny);
+                assert!(
+                    (*df.get_at(x, y) - to_source).abs() <= POINT_SOURCE_TOLERANCE,
+                    "({x}, {y}): {} vs. {to_source} to {:?}",
+                    df.get_at(x, y),
+                    (nx, ny)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_feature_transform_obstacles_refer_to_themselves() {
+        let mut obstacles = Obstacles::new(8, 8);
+        obstacles.set_at(2, 5, true);
+        obstacles.set_at(6, 1, true);
+        let mut df = DistanceField::from(&obstacles);
+        let mut nearest = NearestObstacles::from(&obstacles);
+        EikonalFastSweepingMethod::default().calculate_feature_transform(
+            &mut df,
+            &mut nearest,
+            &obstacles,
+        );
+        assert_eq!(*nearest.get_at(2, 5), Some((2, 5)));
+        assert_eq!(*nearest.get_at(6, 1), Some((6, 1)));
+        assert_eq!(*nearest.get_at(0, 7), Some((2, 5)));
+        assert_eq!(*nearest.get_at(7, 0), Some((6, 1)));
+    }
+
+    #[test]
+    fn test_multiple_obstacles_all_finite() {
+        let mut obstacles = Obstacles::new(9, 9);
+        obstacles.set_at(0, 0, true);
+        obstacles.set_at(8, 8, true);
+        let mut df = DistanceField::from(&obstacles);
+        EikonalFastSweepingMethod::default().calculate_distance_field(&mut df, &obstacles);
+        for &dist in df.iter() {
+            assert!(dist.is_finite(), "All cells should have finite distance");
+        }
+    }
+
+    fn point_source_volume(size: usize, c: (usize, usize, usize), step: f32) -> DistanceField3 {
+        let mut obstacles = Obstacles3::new(size, size, size);
+        obstacles.set_at(c.0, c.1, c.2, true);
+        let mut df = DistanceField3::from(&obstacles);
+        let algo = EikonalFastSweepingMethod::default().with_step_size(step);
+        algo.calculate_distance_field3(&mut df, &obstacles);
+        df
+    }
+
+    #[test]
+    fn test_godunov_update_n_ignores_unreached_axes() {
+        let inf = f32::INFINITY;
+        assert_eq!(
+            godunov_update_n(&mut [(2.0, 1.0), (inf, 1.0), (inf, 1.0)]),
+            3.0
+        );
+        assert_eq!(
+            godunov_update_n(&mut [(inf, 1.0), (0.0, 1.0), (5.0, 1.0)]),
+            1.0
+        );
+        assert_eq!(
+            godunov_update_n(&mut [(1.0, 1.0), (1.0, 1.0), (inf, 1.0)]),
+            godunov_update(1.0, 1.0, 1.0, 1.0)
+        );
+        assert!(godunov_update_n(&mut [(inf, 1.0); 3]).is_infinite());
+    }
+
+    #[test]
+    fn test_godunov_update_n_three_sided() {
+        let value = godunov_update_n(&mut [(0.0, 1.0); 3]);
+        assert!((value - 1.0 / 3.0_f32.sqrt()).abs() < 1e-6);
+
+        // Anisotropic spacings weight the axes by their inverse squared spacing.
+        let value = godunov_update_n(&mut [(0.0, 1.0), (0.0, 2.0), (0.0, 2.0)]);
+        assert!((value - 1.0 / 1.5_f32.sqrt()).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_volume_anisotropic_slice_spacing() {
+        let mut obstacles = Obstacles3::new(9, 9, 9);
+        obstacles.set_at(4, 4, 4, true);
+        let mut df = DistanceField3::from(&obstacles);
+        EikonalFastSweepingMethod::default()
+            .with_spacing3(0.5, 0.5, 2.0)
+            .calculate_distance_field3(&mut df, &obstacles);
+
+        for k in 1..=4 {
+            assert_eq!(*df.get_at(4 + k, 4, 4), 0.5 * k as f32);
+            assert_eq!(*df.get_at(4, 4 - k, 4), 0.5 * k as f32);
+            assert_eq!(*df.get_at(4, 4, 4 + k), 2.0 * k as f32);
+        }
+
+        let dz = 2.0_f32;
+        let dx = 4.0 * 0.5_f32;
+        let expected = (dx * dx + dz * dz).sqrt();
+        let actual = *df.get_at(8, 4, 5);
+        assert!((actual - expected).abs() < VOLUME_POINT_SOURCE_TOLERANCE);
+    }
+
+    #[test]
+    fn test_volume_obstacle_cells_have_zero_distance() {
+        let df = point_source_volume(7, (3, 3, 3), 1.0);
+        assert_eq!(*df.get_at(3, 3, 3), 0.0);
+        assert!(*df.get_at(3, 3, 4) > 0.0);
+    }
+
+    #[test]
+    fn test_volume_empty_grid_converges() {
+        let obstacles = Obstacles3::new(6, 5, 4);
+        let mut df = DistanceField3::from(&obstacles);
+        EikonalFastSweepingMethod::default().calculate_distance_field3(&mut df, &obstacles);
+        for &dist in df.iter() {
+            assert!(dist.is_infinite(), "Empty grid should retain MAX_DISTANCE");
+        }
+    }
+
+    #[test]
+    fn test_volume_axis_distances_are_exact() {
+        let df = point_source_volume(13, (6, 6, 6), 1.0);
+        for k in 1..=6 {
+            assert_eq!(*df.get_at(6 + k, 6, 6), k as f32);
+            assert_eq!(*df.get_at(6, 6 - k, 6), k as f32);
+            assert_eq!(*df.get_at(6, 6, 6 + k), k as f32);
+        }
+    }
+
+    #[test]
+    fn test_volume_point_source_matches_euclidean() {
+        let size = 21;
+        let df = point_source_volume(size, (10, 10, 10), 1.0);
+        for z in 0..size {
+            for y in 0..size {
+                for x in 0..size {
+                    let dx = x as f32 - 10.0;
+                    let dy = y as f32 - 10.0;
+                    let dz = z as f32 - 10.0;
+                    let expected = (dx * dx + dy * dy + dz * dz).sqrt();
+                    let actual = *df.get_at(x, y, z);
+                    assert!(actual >= expected - 1e-4);
+                    assert!(
+                        actual - expected <= VOLUME_POINT_SOURCE_TOLERANCE,
+                        "({x}, {y}, {z}): {actual} deviates from {expected}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_volume_step_size_scales_distances() {
+        let df1 = point_source_volume(9, (2, 4, 6), 1.0);
+        let df2 = point_source_volume(9, (2, 4, 6), 0.5);
+        for (a, b) in df1.iter().zip(df2.iter()) {
+            assert!((a * 0.5 - b).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_volume_plane_obstacle_distances() {
+        let mut obstacles = Obstacles3::new(7, 5, 5);
+        for z in 0..5 {
+            for y in 0..5 {
+                obstacles.set_at(3, y, z, true);
+            }
+        }
+        let mut df = DistanceField3::from(&obstacles);
+        EikonalFastSweepingMethod::default().calculate_distance_field3(&mut df, &obstacles);
+
+        for z in 0..5 {
+            for y in 0..5 {
+                assert_eq!(*df.get_at(3, y, z), 0.0);
+                assert_eq!(*df.get_at(2, y, z), 1.0);
+                assert_eq!(*df.get_at(0, y, z), 3.0);
+                assert_eq!(*df.get_at(6, y, z), 3.0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_volume_convergence_detection() {
+        let mut obstacles = Obstacles3::new(16, 12, 10);
+        obstacles.set_at(2, 3, 4, true);
+        obstacles.set_at(13, 9, 1, true);
+
+        let mut df1 = DistanceField3::from(&obstacles);
+        EikonalFastSweepingMethod::default().calculate_distance_field3(&mut df1, &obstacles);
+
+        let mut df2 = DistanceField3::from(&obstacles);
+        EikonalFastSweepingMethod::default()
+            .with_max_iterations(100)
+            .calculate_distance_field3(&mut df2, &obstacles);
+
+        for (a, b) in df1.iter().zip(df2.iter()) {
+            assert_eq!(*a, *b, "Convergence should match limited run");
+        }
+    }
+
+    #[test]
+    fn test_volume_edge_case_1x1x1_grid() {
+        let mut obstacles = Obstacles3::new(1, 1, 1);
+        obstacles.set_at(0, 0, 0, true);
+        let mut df = DistanceField3::from(&obstacles);
+        EikonalFastSweepingMethod::default().calculate_distance_field3(&mut df, &obstacles);
+        assert_eq!(*df.get_at(0, 0, 0), 0.0);
+    }
+
+    #[test]
+    fn test_volume_single_column_propagates() {
+        let mut obstacles = Obstacles3::new(1, 1, 5);
+        obstacles.set_at(0, 0, 0, true);
+        let mut df = DistanceField3::from(&obstacles);
+        EikonalFastSweepingMethod::default().calculate_distance_field3(&mut df, &obstacles);
+        assert_eq!(*df.get_at(0, 0, 4), 4.0);
+    }
+
+    #[test]
+    fn test_max_distance_clamps() {
+        let mut obstacles = Obstacles::new(200, 150);
+        obstacles.set_at(20, 30, true);
+        obstacles.set_at(150, 100, true);
+        for cutoff in [Cutoff::Clamp, Cutoff::Unreached] {
+            let algo = EikonalFastSweepingMethod::default().with_spacing(0.5, 0.75);
+            let mut full = DistanceField::from(&obstacles);
+            let full_report = algo.calculate_distance_field_with_report(&mut full, &obstacles);
+
+            let mut cut = DistanceField::from(&obstacles);
+            let report = algo
+                .with_max_distance(12.0)
+                .with_cutoff(cutoff)
+                .calculate_distance_field_with_report(&mut cut, &obstacles);
+            for (full, cut) in full.iter().zip(cut.iter()) {
+                match cutoff {
+                    _ if *full <= 12.0 => assert_eq!(cut, full),
+                    Cutoff::Clamp => assert_eq!(*cut, 12.0),
+                    Cutoff::Unreached => assert_eq!(*cut, DistanceField::MAX_DISTANCE),
+                }
+            }
+
+            let cells = |report: &SolveReport| report.cells_changed.iter().sum::<usize>();
+            assert!(cells(&report) * 4 < cells(&full_report));
+        }
+    }
+
+    #[test]
+    fn test_max_distance_with_cheap_costs() {
+        // Cheap cells travel faster than one cell per step, so the skipped region shrinks.
+        let mut obstacles = Obstacles::new(120, 80);
+        obstacles.set_at(10, 40, true);
+        let mut costs = CostField::from(&obstacles);
+        for cost in costs.iter_mut() {
+            *cost = 0.25;
+        }
+        let algo = EikonalFastSweepingMethod::default();
+        let mut full = DistanceField::from(&obstacles);
+        algo.calculate_travel_time(&mut full, &obstacles, &costs);
+        let mut cut = DistanceField::from(&obstacles);
+        algo.with_max_distance(20.0)
+            .calculate_travel_time(&mut cut, &obstacles, &costs);
+
+        for (full, cut) in full.iter().zip(cut.iter()) {
+            assert_eq!(*cut, full.min(20.0));
+        }
+    }
+
+    #[test]
+    fn test_volume_max_distance_clamps() {
+        let mut obstacles = Obstacles3::new(12, 12, 12);
+        obstacles.set_at(0, 0, 0, true);
+        let mut df = DistanceField3::from(&obstacles);
+        EikonalFastSweepingMethod::default()
+            .with_max_distance(5.0)
+            .calculate_distance_field3(&mut df, &obstacles);
+        assert_eq!(*df.get_at(0, 0, 4), 4.0);
+        assert_eq!(*df.get_at(11, 11, 11), 5.0);
+    }
+
+    #[test]
+    fn test_godunov_update_n_matches_lower_dimensions() {
+        let cases = [
+            (0.0, 0.5, 1.0),
+            (3.0, 3.2, 3.1),
+            (1.0, 7.0, 2.0),
+            (2.5, 2.5, 2.5),
+        ];
+        for (a, b, c) in cases {
+            let two = godunov_update_n(&mut [(a, 1.0), (b, 2.0)]);
+            assert!((two - godunov_update(a, b, 1.0, 2.0)).abs() < 1e-5);
+
+            // The order of the axes does not matter.
+            let three = godunov_update_n(&mut [(a, 1.0), (b, 0.5), (c, 1.5)]);
+            assert_eq!(three, godunov_update_n(&mut [(c, 1.5), (a, 1.0), (b, 0.5)]));
+        }
+        let max = DistanceField::MAX_DISTANCE;
+        assert_eq!(godunov_update_n(&mut [(max, 1.0), (max, 1.0)]), max);
+        assert_eq!(godunov_update_n(&mut []), max);
+    }
+
+    #[test]
+    fn test_n_dimensional_matches_2d_and_3d() {
+        let algo = EikonalFastSweepingMethod::default().with_spacing3(1.0, 0.5, 2.0);
+
+        let mut obstacles = Obstacles::new(17, 13);
+        obstacles.set_at(3, 4, true);
+        obstacles.set_at(12, 10, true);
+        let mut df = DistanceField::from(&obstacles);
+        algo.calculate_distance_field(&mut df, &obstacles);
+        let mut df_n = DistanceFieldN::new([17, 13], 0.0);
+        algo.with_spacing_n([1.0, 0.5])
+            .calculate_distance_field_n(&mut df_n, obstacles.as_grid());
+        for (expected, actual) in df.iter().zip(df_n.iter()) {
+            assert!((expected - actual).abs() < 1e-4, "{expected} vs {actual}");
+        }
+
+        let mut obstacles = Obstacles3::new(9, 7, 8);
+        obstacles.set_at(2, 5, 1, true);
+        obstacles.set_at(7, 1, 6, true);
+        let mut df = DistanceField3::from(&obstacles);
+        algo.calculate_distance_field3(&mut df, &obstacles);
+        let mut df_n = DistanceFieldN::new([9, 7, 8], 0.0);
+        algo.with_spacing_n([1.0, 0.5, 2.0])
+            .calculate_distance_field_n(&mut df_n, obstacles.as_grid());
+        assert_eq!(df.as_grid(), &df_n);
+    }
+
+    #[test]
+    #[should_panic(expected = "do not match the shape")]
+    fn test_n_dimensional_rejects_mismatched_shapes() {
+        let obstacles = ObstaclesN::new([4, 4, 4], false);
+        let mut df = DistanceFieldN::new([4, 4, 5], 0.0);
+        EikonalFastSweepingMethod::default()
+            .with_spacing_n([1.0; 3])
+            .calculate_distance_field_n(&mut df, &obstacles);
+    }
+
+    #[test]
+    fn test_four_dimensional_point_source() {
+        let mut obstacles = ObstaclesN::new([9, 9, 9, 9], false);
+        obstacles[[4, 4, 4, 4]] = true;
+        let mut df = DistanceFieldN::new([9, 9, 9, 9], 0.0);
+        EikonalFastSweepingMethod::default()
+            .with_spacing_n([1.0; 4])
+            .calculate_distance_field_n(&mut df, &obstacles);
+
+        for k in 1..=4 {
+            assert_eq!(df[[4 + k, 4, 4, 4]], k as f32);
+            assert_eq!(df[[4, 4, 4, 4 - k]], k as f32);
+        }
+        assert_eq!(df[[1, 2, 6, 7]], df[[7, 6, 2, 1]]);
+        let diagonal = df[[8, 8, 8, 8]];
+        assert!((8.0..10.0).contains(&diagonal), "{diagonal}");
+    }
+
+    #[test]
+    fn test_n_dimensional_max_distance_clamps() {
+        let mut obstacles = ObstaclesN::new([6, 6, 6, 6], false);
+        obstacles[[0, 0, 0, 0]] = true;
+        let mut df = DistanceFieldN::new([6, 6, 6, 6], 0.0);
+        EikonalFastSweepingMethod::default()
+            .with_max_distance(3.0)
+            .with_spacing_n([1.0; 4])
+            .calculate_distance_field_n(&mut df, &obstacles);
+        assert_eq!(df[[0, 2, 0, 0]], 2.0);
+        assert_eq!(df[[5, 5, 5, 5]], 3.0);
+    }
+
+    #[test]
+    fn test_four_dimensional_time_spacing() {
+        let mut obstacles = ObstaclesN::new([5, 5, 5, 7], false);
+        obstacles[[2, 2, 2, 0]] = true;
+        let mut df = DistanceFieldN::new([5, 5, 5, 7], 0.0);
+        let report = EikonalFastSweepingMethod::default()
+            .with_spacing_n([1.0, 1.0, 1.0, 0.25])
+            .calculate_distance_field_n_with_report(&mut df, &obstacles);
+
+        assert!(report.converged);
+        assert_eq!(report.cells_changed.len(), 16 * report.iterations);
+        assert_eq!(df[[2, 2, 2, 6]], 1.5);
+        assert_eq!(df[[2, 2, 4, 0]], 2.0);
+    }
+}
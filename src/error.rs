@@ -0,0 +1,120 @@
+use std::fmt;
+
+/// Errors reported by the fallible API of this crate.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Error {
+    /// The dimensions of two grids passed together do not match.
+    DimensionMismatch {
+        /// The `(width, height)` of the grid that defines the expected dimensions.
+        expected: (usize, usize),
+        /// The `(width, height)` of the grid that was passed alongside.
+        actual: (usize, usize),
+    },
+    /// A cell coordinate lies outside of the grid.
+    OutOfBounds {
+        x: usize,
+        y: usize,
+        width: usize,
+        height: usize,
+    },
+    /// A continuous sampling position lies outside of the grid.
+    SampleOutOfBounds { x: f32, y: f32 },
+    /// No obstacle or seed can be reached from the given position.
+    Unreachable { x: f32, y: f32 },
+    /// The step size is not a finite, positive number.
+    InvalidStepSize(f32),
+    /// The convergence tolerance is negative or NaN.
+    InvalidTolerance(f32),
+    /// A traversal cost is not a positive number or [`CostField::IMPASSABLE`].
+    ///
+    /// [`CostField::IMPASSABLE`]: crate::CostField::IMPASSABLE
+    InvalidCost { x: usize, y: usize, value: f32 },
+}
+
+impl Error {
+    /// Returns an error if the two grids differ in size.
+    pub(crate) fn check_dimensions(
+        expected: (usize, usize),
+        actual: (usize, usize),
+    ) -> Result<(), Self> {
+        if expected == actual {
+            Ok(())
+        } else {
+            Err(Self::DimensionMismatch { expected, actual })
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DimensionMismatch { expected, actual } => write!(
+                f,
+                "grid dimensions {}x{} do not match the expected {}x{}",
+                actual.0, actual.1, expected.0, expected.1
+            ),
+            Self::OutOfBounds {
+                x,
+                y,
+                width,
+                height,
+            } => write!(f, "cell ({x}, {y}) is outside of the {width}x{height} grid"),
+            Self::SampleOutOfBounds { x, y } => {
+                write!(f, "sampling position ({x}, {y}) is outside of the grid")
+            }
+            Self::Unreachable { x, y } => {
+                write!(f, "no obstacle can be reached from position ({x}, {y})")
+            }
+            Self::InvalidStepSize(step_size) => {
+                write!(f, "step size {step_size} is not a finite, positive number")
+            }
+            Self::InvalidTolerance(tolerance) => {
+                write!(f, "tolerance {tolerance} is negative or NaN")
+            }
+            Self::InvalidCost { x, y, value } => {
+                write!(
+                    f,
+                    "cost {value} at cell ({x}, {y}) is not a positive number"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_dimensions() {
+        assert_eq!(Error::check_dimensions((3, 4), (3, 4)), Ok(()));
+        assert_eq!(
+            Error::check_dimensions((3, 4), (4, 3)),
+            Err(Error::DimensionMismatch {
+                expected: (3, 4),
+                actual: (4, 3)
+            })
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        let error = Error::OutOfBounds {
+            x: 5,
+            y: 1,
+            width: 4,
+            height: 2,
+        };
+        assert_eq!(error.to_string(), "cell (5, 1) is outside of the 4x2 grid");
+        assert_eq!(
+            Error::DimensionMismatch {
+                expected: (3, 4),
+                actual: (2, 2)
+            }
+            .to_string(),
+            "grid dimensions 2x2 do not match the expected 3x4"
+        );
+    }
+}
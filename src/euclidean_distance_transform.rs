@@ -0,0 +1,450 @@
+use crate::nearest_obstacles;
+use crate::{
+    DistanceField, DistanceFieldAlgorithm, FeatureTransformAlgorithm, NearestObstacles, Obstacles,
+};
+
+/// Configuration and implementation of the exact Euclidean distance transform.
+///
+/// This algorithm computes exact Euclidean distances in linear time using the separable
+/// lower-envelope-of-parabolas method of Felzenszwalb and Huttenlocher. A one-dimensional
+/// transform is applied to every row, followed by a one-dimensional transform of every column.
+#[derive(Debug, Clone, Copy)]
+pub struct EuclideanDistanceTransform {
+    spacing: (f32, f32),
+    squared: bool,
+}
+
+impl EuclideanDistanceTransform {
+    /// Sets the distance between two neighboring cells.
+    ///
+    /// This has the same meaning as the step size of the sweeping methods, i.e. a value of
+    /// `1.0` means neighboring cells are one distance unit apart.
+    #[must_use]
+    pub const fn with_step_size(mut self, step_size: f32) -> Self {
+        self.spacing = (step_size, step_size);
+        self
+    }
+
+    /// Sets separate distances between neighboring cells along the x and y axis.
+    ///
+    /// Use this for grids with rectangular cells to obtain distances in physical units.
+    #[must_use]
+    pub const fn with_spacing(mut self, dx: f32, dy: f32) -> Self {
+        self.spacing = (dx, dy);
+        self
+    }
+
+    /// Sets whether squared distances should be produced.
+    ///
+    /// Squared distances avoid the final square root and are useful for comparisons.
+    #[must_use]
+    pub const fn with_squared(mut self, squared: bool) -> Self {
+        self.squared = squared;
+        self
+    }
+
+    fn transform(
+        &self,
+        distance_field: &mut DistanceField,
+        nearest: Option<&mut NearestObstacles>,
+        obstacles: &Obstacles,
+    ) {
+        let width = distance_field.width();
+        let height = distance_field.height();
+
+        if width == 0 || height == 0 {
+            return;
+        }
+
+        // Squared distances in units of the y spacing; f64 keeps large grids exact.
+        let dx = f64::from(self.spacing.0);
+        let dy = f64::from(self.spacing.1);
+        let row_scale = (dx / dy) * (dx / dy);
+        let mut squared = vec![f64::INFINITY; width * height];
+        for (value, &is_obstacle) in squared.iter_mut().zip(obstacles.iter()) {
+            if is_obstacle {
+                *value = 0.0;
+            }
+        }
+
+        let mut envelope = LowerEnvelope::new(width.max(height));
+        let mut output = vec![0_f64; width.max(height)];
+        let mut sources = vec![0_usize; width.max(height)];
+
+        // The column of the nearest obstacle within each row.
+        let mut row_sources = vec![0_usize; width * height];
+        for (row, row_sources) in squared
+            .chunks_exact_mut(width)
+            .zip(row_sources.chunks_exact_mut(width))
+        {
+            envelope.transform(row, &mut output[..width], row_sources);
+            for (value, &output) in row.iter_mut().zip(output[..width].iter()) {
+                *value = output * row_scale;
+            }
+        }
+
+        let mut nearest = nearest.map(NearestObstacles::as_mut_slice);
+        let mut column = vec![0_f64; height];
+        for x in 0..width {
+            for (y, value) in column.iter_mut().enumerate() {
+                *value = squared[y * width + x];
+            }
+            envelope.transform(&column, &mut output[..height], &mut sources[..height]);
+            for (y, &value) in output[..height].iter().enumerate() {
+                squared[y * width + x] = value;
+            }
+
+            if let Some(nearest) = nearest.as_deref_mut() {
+                for (y, &source_y) in sources[..height].iter().enumerate() {
+                    nearest[y * width + x] = output[y]
+                        .is_finite()
+                        .then(|| (row_sources[source_y * width + x], source_y));
+                }
+            }
+        }
+
+        for (dist, &value) in distance_field.iter_mut().zip(squared.iter()) {
+            *dist = if self.squared {
+                (value * dy * dy) as f32
+            } else {
+                (value.sqrt() * dy) as f32
+            };
+        }
+    }
+}
+
+/// Scratch space for the one-dimensional squared distance transform.
+struct LowerEnvelope {
+    /// Locations of the parabolas forming the lower envelope.
+    vertices: Vec<usize>,
+    /// Boundaries between the parabolas of the lower envelope.
+    boundaries: Vec<f64>,
+}
+
+impl LowerEnvelope {
+    fn new(len: usize) -> Self {
+        Self {
+            vertices: vec![0; len],
+            boundaries: vec![0.0; len + 1],
+        }
+    }
+
+    /// Computes `output[q] = min_p (q - p)² + input[p]` and stores the minimizing `p`
+    /// in `sources[q]`.
+    ///
+    /// Infinite input values do not contribute a parabola. If all inputs are infinite,
+    /// the output is infinite as well and the sources are meaningless.
+    fn transform(&mut self, input: &[f64], output: &mut [f64], sources: &mut [usize]) {
+        let Some(first) = input.iter().position(|value| value.is_finite()) else {
+            output.fill(f64::INFINITY);
+            sources.fill(0);
+            return;
+        };
+
+        let v = &mut self.vertices;
+        let z = &mut self.boundaries;
+        let mut k = 0;
+        v[0] = first;
+        z[0] = f64::NEG_INFINITY;
+        z[1] = f64::INFINITY;
+
+        for q in first + 1..input.len() {
+            if !input[q].is_finite() {
+                continue;
+            }
+
+            let mut s = intersection(input, v[k], q);
+            while s <= z[k] {
+                k -= 1;
+                s = intersection(input, v[k], q);
+            }
+
+            k += 1;
+            v[k] = q;
+            z[k] = s;
+            z[k + 1] = f64::INFINITY;
+        }
+
+        k = 0;
+        for (q, (value, source)) in output.iter_mut().zip(sources.iter_mut()).enumerate() {
+            while z[k + 1] < q as f64 {
+                k += 1;
+            }
+            let offset = q as f64 - v[k] as f64;
+            *value = offset * offset + input[v[k]];
+            *source = v[k];
+        }
+    }
+}
+
+/// Returns the location where the parabolas rooted at `p` and `q` intersect.
+fn intersection(f: &[f64], p: usize, q: usize) -> f64 {
+    let (p, q, fp, fq) = (p as f64, q as f64, f[p], f[q]);
+    ((fq + q * q) - (fp + p * p)) / (2.0 * (q - p))
+}
+
+impl Default for EuclideanDistanceTransform {
+    fn default() -> Self {
+        Self {
+            spacing: (1.0, 1.0),
+            squared: false,
+        }
+    }
+}
+
+impl DistanceFieldAlgorithm for EuclideanDistanceTransform {
+    fn calculate_distance_field(&self, distance_field: &mut DistanceField, obstacles: &Obstacles) {
+        self.transform(distance_field, None, obstacles)
+    }
+}
+
+impl FeatureTransformAlgorithm for EuclideanDistanceTransform {
+    fn calculate_feature_transform(
+        &self,
+        distance_field: &mut DistanceField,
+        nearest: &mut NearestObstacles,
+        obstacles: &Obstacles,
+    ) {
+        if let Err(error) = nearest_obstacles::check_dimensions(distance_field, nearest, obstacles)
+        {
+            panic!("{error}");
+        }
+        self.transform(distance_field, Some(nearest), obstacles)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Grid;
+
+    fn brute_force(obstacles: &Obstacles, x: usize, y: usize) -> f32 {
+        brute_force_with_spacing(obstacles, x, y, (1.0, 1.0))
+    }
+
+    fn brute_force_with_spacing(
+        obstacles: &Obstacles,
+        x: usize,
+        y: usize,
+        (sx, sy): (f32, f32),
+    ) -> f32 {
+        let mut best = f32::INFINITY;
+        for oy in 0..obstacles.height() {
+            for ox in 0..obstacles.width() {
+                if *obstacles.get_at(ox, oy) {
+                    let dx = (ox as f32 - x as f32) * sx;
+                    let dy = (oy as f32 - y as f32) * sy;
+                    best = best.min((dx * dx + dy * dy).sqrt());
+                }
+            }
+        }
+        best
+    }
+
+    #[test]
+    fn test_default_values() {
+        let algo = EuclideanDistanceTransform::default();
+        assert_eq!(algo.spacing, (1.0, 1.0));
+        assert!(!algo.squared);
+    }
+
+    #[test]
+    fn test_builders() {
+        let algo = EuclideanDistanceTransform::default()
+            .with_step_size(0.25)
+            .with_squared(true);
+        assert_eq!(algo.spacing, (0.25, 0.25));
+        assert!(algo.squared);
+    }
+
+    #[test]
+    fn test_anisotropic_spacing_matches_brute_force() {
+        let mut obstacles = Obstacles::new(23, 17);
+        for (x, y) in [(3, 4), (18, 2), (11, 12), (20, 15)] {
+            obstacles.set_at(x, y, true);
+        }
+
+        for spacing in [(1.0, 2.0), (2.0, 1.0), (0.3, 0.6)] {
+            let mut df = DistanceField::from(&obstacles);
+            EuclideanDistanceTransform::default()
+                .with_spacing(spacing.0, spacing.1)
+                .calculate_distance_field(&mut df, &obstacles);
+
+            for y in 0..obstacles.height() {
+                for x in 0..obstacles.width() {
+                    let expected = brute_force_with_spacing(&obstacles, x, y, spacing);
+                    let actual = *df.get_at(x, y);
+                    assert!(
+                        (actual - expected).abs() < 1e-4,
+                        "Mismatch at ({x}, {y}) for {spacing:?}: {actual} != {expected}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_empty_grid_stays_infinite() {
+        let obstacles = Obstacles::new(8, 6);
+        let mut df = DistanceField::from(&obstacles);
+        EuclideanDistanceTransform::default().calculate_distance_field(&mut df, &obstacles);
+        for &dist in df.iter() {
+            assert!(dist.is_infinite());
+        }
+    }
+
+    #[test]
+    fn test_single_obstacle_exact() {
+        let mut obstacles = Obstacles::new(9, 7);
+        obstacles.set_at(2, 5, true);
+        let mut df = DistanceField::from(&obstacles);
+        EuclideanDistanceTransform::default().calculate_distance_field(&mut df, &obstacles);
+
+        assert_eq!(*df.get_at(2, 5), 0.0);
+        assert_eq!(*df.get_at(5, 1), 5.0);
+        assert_eq!(*df.get_at(8, 5), 6.0);
+        assert_eq!(*df.get_at(3, 4), 2.0_f32.sqrt());
+    }
+
+    #[test]
+    fn test_matches_brute_force() {
+        let mut obstacles = Obstacles::new(23, 17);
+        for (x, y) in [(0, 0), (5, 3), (22, 16), (11, 9), (12, 9), (3, 15), (20, 2)] {
+            obstacles.set_at(x, y, true);
+        }
+        let mut df = DistanceField::from(&obstacles);
+        EuclideanDistanceTransform::default().calculate_distance_field(&mut df, &obstacles);
+
+        for y in 0..obstacles.height() {
+            for x in 0..obstacles.width() {
+                let expected = brute_force(&obstacles, x, y);
+                assert!(
+                    (*df.get_at(x, y) - expected).abs() < 1e-5,
+                    "({x}, {y}): {} != {expected}",
+                    df.get_at(x, y)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_squared_distances() {
+        let mut obstacles = Obstacles::new(6, 6);
+        obstacles.set_at(0, 0, true);
+        let mut df = DistanceField::from(&obstacles);
+        EuclideanDistanceTransform::default()
+            .with_squared(true)
+            .calculate_distance_field(&mut df, &obstacles);
+
+        assert_eq!(*df.get_at(3, 4), 25.0);
+        assert_eq!(*df.get_at(5, 5), 50.0);
+    }
+
+    #[test]
+    fn test_step_size_scales_distances() {
+        let mut obstacles = Obstacles::new(6, 6);
+        obstacles.set_at(0, 0, true);
+
+        let mut df = DistanceField::from(&obstacles);
+        EuclideanDistanceTransform::default()
+            .with_step_size(0.5)
+            .calculate_distance_field(&mut df, &obstacles);
+        assert_eq!(*df.get_at(3, 4), 2.5);
+
+        let mut df = DistanceField::from(&obstacles);
+        EuclideanDistanceTransform::default()
+            .with_step_size(0.5)
+            .with_squared(true)
+            .calculate_distance_field(&mut df, &obstacles);
+        assert_eq!(*df.get_at(3, 4), 6.25);
+    }
+
+    #[test]
+    fn test_agrees_with_sweeps_along_axes() {
+        let mut obstacles = Obstacles::new(7, 7);
+        for y in 0..7 {
+            obstacles.set_at(3, y, true);
+        }
+        let mut exact = DistanceField::from(&obstacles);
+        let mut swept = DistanceField::from(&obstacles);
+        EuclideanDistanceTransform::default()
+            .with_step_size(0.1)
+            .calculate_distance_field(&mut exact, &obstacles);
+        crate::NaiveFastSweepingMethod::default()
+            .with_step_size(0.1)
+            .calculate_distance_field(&mut swept, &obstacles);
+
+        for (a, b) in exact.iter().zip(swept.iter()) {
+            assert!((a - b).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_feature_transform_is_exact() {
+        let mut obstacles = Obstacles::new(23, 17);
+        for (x, y) in [(0, 0), (5, 3), (22, 16), (11, 9), (3, 15), (20, 2)] {
+            obstacles.set_at(x, y, true);
+        }
+        let mut df = DistanceField::from(&obstacles);
+        let mut nearest = NearestObstacles::from(&obstacles);
+        EuclideanDistanceTransform::default()
+            .with_step_size(0.5)
+            .calculate_feature_transform(&mut df, &mut nearest, &obstacles);
+
+        for y in 0..obstacles.height() {
+            for x in 0..obstacles.width() {
+                let (nx, ny) = nearest
+                    .get_at(x, y)
+                    .expect("every cell has a nearest obstacle");
+                assert!(*obstacles.get_at(nx, ny));
+                let dx = nx as f32 - x as f32;
+                let dy = ny as f32 - y as f32;
+                let expected = (dx * dx + dy * dy).sqrt() * 0.5;
+                assert!((*df.get_at(x, y) - expected).abs() < 1e-5);
+                assert!((brute_force(&obstacles, x, y) * 0.5 - expected).abs() < 1e-5);
+            }
+        }
+    }
+
+    #[test]
+    fn test_feature_transform_empty_grid() {
+        let obstacles = Obstacles::new(4, 4);
+        let mut df = DistanceField::from(&obstacles);
+        let mut nearest = NearestObstacles::from(&obstacles);
+        nearest.set_at(1, 1, Some((0, 0)));
+        EuclideanDistanceTransform::default().calculate_feature_transform(
+            &mut df,
+            &mut nearest,
+            &obstacles,
+        );
+        assert!(nearest.iter().all(Option::is_none));
+    }
+
+    #[test]
+    fn test_single_row_and_column() {
+        let mut obstacles = Obstacles::new(5, 1);
+        obstacles.set_at(0, 0, true);
+        let mut df = DistanceField::from(&obstacles);
+        EuclideanDistanceTransform::default().calculate_distance_field(&mut df, &obstacles);
+        assert_eq!(*df.get_at(4, 0), 4.0);
+
+        let mut obstacles = Obstacles::new(1, 5);
+        obstacles.set_at(0, 4, true);
+        let mut df = DistanceField::from(&obstacles);
+        EuclideanDistanceTransform::default().calculate_distance_field(&mut df, &obstacles);
+        assert_eq!(*df.get_at(0, 0), 4.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "do not match")]
+    fn test_feature_transform_rejects_mismatched_nearest() {
+        let obstacles = Obstacles::new(6, 4);
+        let mut df = DistanceField::from(&obstacles);
+        let mut nearest = NearestObstacles::new(4, 6);
+        EuclideanDistanceTransform::default().calculate_feature_transform(
+            &mut df,
+            &mut nearest,
+            &obstacles,
+        );
+    }
+}
@@ -0,0 +1,460 @@
+use crate::eikonal_sweeping::godunov_update;
+use crate::{
+    CostField, Cutoff, DistanceField, DistanceFieldAlgorithm, Obstacles, TravelTimeAlgorithm,
+};
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+/// Configuration and implementation of the Fast Marching Method.
+///
+/// This algorithm solves the Eikonal equation `|∇u| = 1` with the same Godunov upwind
+/// discretization as [`EikonalFastSweepingMethod`](crate::EikonalFastSweepingMethod),
+/// but visits every cell exactly once in order of increasing distance, using a binary heap
+/// as the narrow band of trial cells (Sethian, 1996).
+#[derive(Debug, Clone, Copy)]
+pub struct FastMarchingMethod {
+    spacing: (f32, f32),
+    max_distance: f32,
+    cutoff: Cutoff,
+}
+
+/// The state of a cell during marching.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CellState {
+    /// The cell has not been reached yet.
+    Far,
+    /// The cell has a tentative distance and is part of the narrow band.
+    Trial,
+    /// The distance of the cell is final.
+    Known,
+}
+
+/// A narrow band entry, ordered such that the smallest distance is popped first.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Trial {
+    pub(crate) distance: f32,
+    pub(crate) index: usize,
+}
+
+impl PartialEq for Trial {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Trial {}
+
+impl PartialOrd for Trial {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Trial {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .distance
+            .total_cmp(&self.distance)
+            .then_with(|| other.index.cmp(&self.index))
+    }
+}
+
+impl FastMarchingMethod {
+    /// Sets the grid spacing used by the Eikonal update.
+    ///
+    /// A value of `1.0` means neighboring cells are one distance unit apart.
+    #[must_use]
+    pub const fn with_step_size(mut self, step_size: f32) -> Self {
+        self.spacing = (step_size, step_size);
+        self
+    }
+
+    /// Sets separate grid spacings along the x and y axis.
+    ///
+    /// Use this for grids with rectangular cells to obtain distances in physical units.
+    #[must_use]
+    pub const fn with_spacing(mut self, dx: f32, dy: f32) -> Self {
+        self.spacing = (dx, dy);
+        self
+    }
+
+    /// Sets the largest distance or travel time of interest.
+    ///
+    /// Marching stops once all cells up to this distance are known. Distances beyond the
+    /// maximum are replaced according to [`with_cutoff`](Self::with_cutoff).
+    #[must_use]
+    pub const fn with_max_distance(mut self, max_distance: f32) -> Self {
+        self.max_distance = max_distance;
+        self
+    }
+
+    /// Sets how distances beyond the [maximum distance](Self::with_max_distance) are
+    /// represented.
+    #[must_use]
+    pub const fn with_cutoff(mut self, cutoff: Cutoff) -> Self {
+        self.cutoff = cutoff;
+        self
+    }
+
+    fn fast_marching(
+        &self,
+        distance_field: &mut DistanceField,
+        obstacles: &Obstacles,
+        costs: Option<&CostField>,
+    ) {
+        let width = distance_field.width();
+        let height = distance_field.height();
+        if let Some(costs) = costs {
+            costs.assert_dimensions((obstacles.width(), obstacles.height()));
+            costs.assert_dimensions((width, height));
+        }
+        let mut states = vec![CellState::Far; width * height];
+        let mut band = BinaryHeap::new();
+        let costs = costs.map(CostField::as_slice);
+
+        let distances = distance_field.as_mut_slice();
+        distances.fill(DistanceField::MAX_DISTANCE);
+        for (index, &is_obstacle) in obstacles.iter().enumerate().take(width * height) {
+            if is_obstacle {
+                distances[index] = 0.0;
+                states[index] = CellState::Trial;
+                band.push(Trial {
+                    distance: 0.0,
+                    index,
+                });
+            }
+        }
+
+        while let Some(Trial { distance, index }) = band.pop() {
+            // Skip stale entries superseded by a smaller tentative distance.
+            if states[index] == CellState::Known || distance > distances[index] {
+                continue;
+            }
+            if distance > self.max_distance {
+                break;
+            }
+            states[index] = CellState::Known;
+
+            let (x, y) = (index % width, index / width);
+            let neighbors = [
+                (x > 0).then(|| index - 1),
+                (x + 1 < width).then(|| index + 1),
+                (y > 0).then(|| index - width),
+                (y + 1 < height).then(|| index + width),
+            ];
+
+            for neighbor in neighbors.into_iter().flatten() {
+                if states[neighbor] == CellState::Known {
+                    continue;
+                }
+
+                // Impassable cells are never reached.
+                let cost = costs.map_or(1.0, |costs| costs[neighbor]);
+                let h = (self.spacing.0 * cost, self.spacing.1 * cost);
+                if h.0 == f32::INFINITY || h.1 == f32::INFINITY {
+                    continue;
+                }
+
+                let new_value = update(distances, &states, neighbor, width, h);
+                if new_value < distances[neighbor] {
+                    distances[neighbor] = new_value;
+                    states[neighbor] = CellState::Trial;
+                    band.push(Trial {
+                        distance: new_value,
+                        index: neighbor,
+                    });
+                }
+            }
+        }
+
+        if self.max_distance < DistanceField::MAX_DISTANCE {
+            self.cutoff.apply(distances.iter_mut(), self.max_distance);
+        }
+    }
+}
+
+/// Computes the tentative distance of a cell from its known neighbors.
+///
+/// `h` holds the grid spacings along the x and y axis.
+fn update(
+    distances: &[f32],
+    states: &[CellState],
+    index: usize,
+    width: usize,
+    (hx, hy): (f32, f32),
+) -> f32 {
+    let known = |neighbor: usize| {
+        if states[neighbor] == CellState::Known {
+            distances[neighbor]
+        } else {
+            DistanceField::MAX_DISTANCE
+        }
+    };
+
+    let (x, y) = (index % width, index / width);
+    let height = distances.len() / width;
+    let mut a = DistanceField::MAX_DISTANCE;
+    let mut b = DistanceField::MAX_DISTANCE;
+    if x > 0 {
+        a = a.min(known(index - 1));
+    }
+    if x + 1 < width {
+        a = a.min(known(index + 1));
+    }
+    if y > 0 {
+        b = b.min(known(index - width));
+    }
+    if y + 1 < height {
+        b = b.min(known(index + width));
+    }
+
+    godunov_update(a, b, hx, hy)
+}
+
+impl Default for FastMarchingMethod {
+    fn default() -> Self {
+        Self {
+            spacing: (1.0, 1.0),
+            max_distance: DistanceField::MAX_DISTANCE,
+            cutoff: Cutoff::Clamp,
+        }
+    }
+}
+
+impl DistanceFieldAlgorithm for FastMarchingMethod {
+    fn calculate_distance_field(&self, distance_field: &mut DistanceField, obstacles: &Obstacles) {
+        self.fast_marching(distance_field, obstacles, None)
+    }
+}
+
+impl TravelTimeAlgorithm for FastMarchingMethod {
+    fn calculate_travel_time(
+        &self,
+        travel_time: &mut DistanceField,
+        obstacles: &Obstacles,
+        costs: &CostField,
+    ) {
+        self.fast_marching(travel_time, obstacles, Some(costs))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EikonalFastSweepingMethod, Error, Grid};
+
+    fn create_test_obstacles() -> Obstacles {
+        let mut obstacles = Obstacles::new(64, 48);
+        for y in 10..20 {
+            obstacles.set_at(10, y, true);
+        }
+        for x in 10..40 {
+            obstacles.set_at(x, 20, true);
+        }
+        for x in 10..20 {
+            obstacles.set_at(40 + x, 20 + x, true);
+        }
+        obstacles
+    }
+
+    #[test]
+    fn test_default_values() {
+        let algo = FastMarchingMethod::default();
+        assert_eq!(algo.spacing, (1.0, 1.0));
+    }
+
+    #[test]
+    fn test_builder_step_size() {
+        let algo = FastMarchingMethod::default().with_step_size(0.5);
+        assert_eq!(algo.spacing, (0.5, 0.5));
+    }
+
+    #[test]
+    fn test_trial_ordering_pops_smallest_first() {
+        let mut heap = BinaryHeap::new();
+        for (distance, index) in [(3.0, 0), (1.0, 1), (2.0, 2)] {
+            heap.push(Trial { distance, index });
+        }
+        assert_eq!(heap.pop().unwrap().index, 1);
+        assert_eq!(heap.pop().unwrap().index, 2);
+        assert_eq!(heap.pop().unwrap().index, 0);
+    }
+
+    #[test]
+    fn test_empty_grid_stays_infinite() {
+        let obstacles = Obstacles::new(10, 10);
+        let mut df = DistanceField::from(&obstacles);
+        FastMarchingMethod::default().calculate_distance_field(&mut df, &obstacles);
+        for &dist in df.iter() {
+            assert!(dist.is_infinite(), "Empty grid should retain MAX_DISTANCE");
+        }
+    }
+
+    #[test]
+    fn test_obstacle_cells_have_zero_distance() {
+        let mut obstacles = Obstacles::new(10, 10);
+        obstacles.set_at(5, 5, true);
+        let mut df = DistanceField::from(&obstacles);
+        FastMarchingMethod::default().calculate_distance_field(&mut df, &obstacles);
+        assert_eq!(*df.get_at(5, 5), 0.0);
+    }
+
+    #[test]
+    fn test_axis_distances_are_exact() {
+        let mut obstacles = Obstacles::new(21, 21);
+        obstacles.set_at(10, 10, true);
+        let mut df = DistanceField::from(&obstacles);
+        FastMarchingMethod::default().calculate_distance_field(&mut df, &obstacles);
+        for k in 1..=10 {
+            assert_eq!(*df.get_at(10 + k, 10), k as f32);
+            assert_eq!(*df.get_at(10, 10 - k), k as f32);
+        }
+    }
+
+    #[test]
+    fn test_matches_eikonal_sweeping() {
+        let obstacles = create_test_obstacles();
+        let mut marched = DistanceField::from(&obstacles);
+        let mut swept = DistanceField::from(&obstacles);
+        FastMarchingMethod::default()
+            .with_step_size(0.1)
+            .calculate_distance_field(&mut marched, &obstacles);
+        EikonalFastSweepingMethod::default()
+            .with_step_size(0.1)
+            .calculate_distance_field(&mut swept, &obstacles);
+
+        for (a, b) in marched.iter().zip(swept.iter()) {
+            assert!((a - b).abs() < 1e-4, "{a} != {b}");
+        }
+    }
+
+    #[test]
+    fn test_travel_time_matches_eikonal_sweeping() {
+        let obstacles = create_test_obstacles();
+        let mut costs = CostField::from(&obstacles);
+        for y in 0..obstacles.height() {
+            for x in 30..34 {
+                costs.set_at(x, y, if y < 40 { 4.0 } else { CostField::IMPASSABLE });
+            }
+        }
+
+        let mut marched = DistanceField::from(&obstacles);
+        let mut swept = DistanceField::from(&obstacles);
+        FastMarchingMethod::default().calculate_travel_time(&mut marched, &obstacles, &costs);
+        EikonalFastSweepingMethod::default().calculate_travel_time(&mut swept, &obstacles, &costs);
+
+        for (a, b) in marched.iter().zip(swept.iter()) {
+            assert!(a == b || (a - b).abs() < 1e-3, "{a} != {b}");
+        }
+        assert!(marched.get_at(31, 45).is_infinite());
+    }
+
+    #[test]
+    #[should_panic(expected = "do not match")]
+    fn test_travel_time_rejects_smaller_costs() {
+        let obstacles = create_test_obstacles();
+        let mut travel_time = DistanceField::from(&obstacles);
+        let costs = CostField::new(10, 10);
+        FastMarchingMethod::default().calculate_travel_time(&mut travel_time, &obstacles, &costs);
+    }
+
+    #[test]
+    fn test_try_travel_time_checks_dimensions() {
+        let obstacles = Obstacles::new(8, 6);
+        let mut travel_time = DistanceField::from(&obstacles);
+        let algo = FastMarchingMethod::default();
+        assert_eq!(
+            algo.try_calculate_travel_time(&mut travel_time, &obstacles, &CostField::new(9, 6)),
+            Err(Error::DimensionMismatch {
+                expected: (8, 6),
+                actual: (9, 6)
+            })
+        );
+        let mut small = DistanceField::new(8, 5);
+        assert!(algo
+            .try_calculate_travel_time(&mut small, &obstacles, &CostField::from(&obstacles))
+            .is_err());
+        assert_eq!(
+            algo.try_calculate_travel_time(
+                &mut travel_time,
+                &obstacles,
+                &CostField::from(&obstacles)
+            ),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_try_travel_time_rejects_invalid_costs() {
+        let obstacles = Obstacles::new(8, 6);
+        let mut travel_time = DistanceField::from(&obstacles);
+        let mut costs = CostField::from(&obstacles);
+        costs.set_at(3, 4, -1.0);
+        assert_eq!(
+            FastMarchingMethod::default().try_calculate_travel_time(
+                &mut travel_time,
+                &obstacles,
+                &costs
+            ),
+            Err(Error::InvalidCost {
+                x: 3,
+                y: 4,
+                value: -1.0
+            })
+        );
+    }
+
+    #[test]
+    fn test_anisotropic_spacing() {
+        let mut obstacles = Obstacles::new(21, 21);
+        obstacles.set_at(10, 10, true);
+        let mut marched = DistanceField::from(&obstacles);
+        FastMarchingMethod::default()
+            .with_spacing(1.0, 2.0)
+            .calculate_distance_field(&mut marched, &obstacles);
+
+        for k in 1..=10 {
+            assert_eq!(*marched.get_at(10 + k, 10), k as f32);
+            assert_eq!(*marched.get_at(10, 10 - k), 2.0 * k as f32);
+        }
+
+        let mut swept = DistanceField::from(&obstacles);
+        EikonalFastSweepingMethod::default()
+            .with_spacing(1.0, 2.0)
+            .calculate_distance_field(&mut swept, &obstacles);
+        for (a, b) in marched.iter().zip(swept.iter()) {
+            assert!((a - b).abs() < 1e-4, "{a} != {b}");
+        }
+    }
+
+    #[test]
+    fn test_single_row_propagates() {
+        let mut obstacles = Obstacles::new(5, 1);
+        obstacles.set_at(0, 0, true);
+        let mut df = DistanceField::from(&obstacles);
+        FastMarchingMethod::default().calculate_distance_field(&mut df, &obstacles);
+        assert_eq!(*df.get_at(4, 0), 4.0);
+    }
+
+    #[test]
+    fn test_max_distance_clamps() {
+        let obstacles = create_test_obstacles();
+        let mut full = DistanceField::from(&obstacles);
+        FastMarchingMethod::default().calculate_distance_field(&mut full, &obstacles);
+
+        for cutoff in [Cutoff::Clamp, Cutoff::Unreached] {
+            let mut cut = DistanceField::from(&obstacles);
+            FastMarchingMethod::default()
+                .with_max_distance(6.5)
+                .with_cutoff(cutoff)
+                .calculate_distance_field(&mut cut, &obstacles);
+            for (full, cut) in full.iter().zip(cut.iter()) {
+                match cutoff {
+                    _ if *full <= 6.5 => assert_eq!(cut, full),
+                    Cutoff::Clamp => assert_eq!(*cut, 6.5),
+                    Cutoff::Unreached => assert_eq!(*cut, DistanceField::MAX_DISTANCE),
+                }
+            }
+        }
+    }
+}
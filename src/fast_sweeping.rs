@@ -1,4 +1,11 @@
-use crate::{min3, DistanceField, DistanceFieldAlgorithm, Obstacles};
+use crate::cutoff::row_spans;
+use crate::nearest_obstacles;
+use crate::solve_report::{iterate_sweeps, SweepStats};
+use crate::{
+    min3, Cutoff, DistanceField, DistanceFieldAlgorithm, Error, FeatureTransformAlgorithm,
+    NearestObstacles, Obstacles, SolveReport,
+};
+use std::ops::Range;
 
 /// Configuration and implementation of the naive fast sweeping method.
 ///
@@ -6,8 +13,11 @@ use crate::{min3, DistanceField, DistanceFieldAlgorithm, Obstacles};
 /// across the grid, propagating distance values from obstacles outward.
 #[derive(Debug, Clone, Copy)]
 pub struct NaiveFastSweepingMethod {
-    step_size: f32,
+    spacing: (f32, f32),
     max_iterations: usize,
+    tolerance: f32,
+    max_distance: f32,
+    cutoff: Cutoff,
 }
 
 impl NaiveFastSweepingMethod {
@@ -15,8 +25,11 @@ impl NaiveFastSweepingMethod {
     #[deprecated(since = "0.2.0", note = "use `default()` with builder methods instead")]
     pub fn new(step_size: f32, num_iter: usize) -> Self {
         Self {
-            step_size,
+            spacing: (step_size, step_size),
             max_iterations: num_iter,
+            tolerance: 0.0,
+            max_distance: DistanceField::MAX_DISTANCE,
+            cutoff: Cutoff::Clamp,
         }
     }
 
@@ -26,7 +39,16 @@ impl NaiveFastSweepingMethod {
     /// Smaller values produce finer distance gradients.
     #[must_use]
     pub const fn with_step_size(mut self, step_size: f32) -> Self {
-        self.step_size = step_size;
+        self.spacing = (step_size, step_size);
+        self
+    }
+
+    /// Sets separate step sizes along the x and y axis.
+    ///
+    /// Use this for grids with rectangular cells, e.g. to obtain distances in physical units.
+    #[must_use]
+    pub const fn with_spacing(mut self, dx: f32, dy: f32) -> Self {
+        self.spacing = (dx, dy);
         self
     }
 
@@ -39,12 +61,113 @@ impl NaiveFastSweepingMethod {
         self
     }
 
-    fn fast_sweeping(&self, distance_field: &mut DistanceField, obstacles: &Obstacles) {
+    /// Sets the convergence tolerance.
+    ///
+    /// Sweeping stops once no cell changed by more than this value during an iteration.
+    /// The default of `0.0` sweeps until no cell changes at all.
+    #[must_use]
+    pub const fn with_tolerance(mut self, tolerance: f32) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Sets the largest distance of interest.
+    ///
+    /// Sweeps skip the cells that are farther than this from every obstacle along both axes,
+    /// which saves most of the work on large, sparse maps. Distances beyond the maximum are
+    /// replaced according to [`with_cutoff`](Self::with_cutoff).
+    #[must_use]
+    pub const fn with_max_distance(mut self, max_distance: f32) -> Self {
+        self.max_distance = max_distance;
+        self
+    }
+
+    /// Sets how distances beyond the [maximum distance](Self::with_max_distance) are
+    /// represented.
+    #[must_use]
+    pub const fn with_cutoff(mut self, cutoff: Cutoff) -> Self {
+        self.cutoff = cutoff;
+        self
+    }
+
+    /// Sets the step size, rejecting values that are not finite and positive.
+    ///
+    /// See [`with_step_size`](Self::with_step_size).
+    pub fn try_with_step_size(self, step_size: f32) -> Result<Self, Error> {
+        self.try_with_spacing(step_size, step_size)
+    }
+
+    /// Sets separate step sizes, rejecting values that are not finite and positive.
+    ///
+    /// See [`with_spacing`](Self::with_spacing).
+    pub fn try_with_spacing(self, dx: f32, dy: f32) -> Result<Self, Error> {
+        for step_size in [dx, dy] {
+            if !(step_size.is_finite() && step_size > 0.0) {
+                return Err(Error::InvalidStepSize(step_size));
+            }
+        }
+        Ok(self.with_spacing(dx, dy))
+    }
+
+    /// Sets the convergence tolerance, rejecting negative values and NaN.
+    ///
+    /// See [`with_tolerance`](Self::with_tolerance).
+    pub fn try_with_tolerance(self, tolerance: f32) -> Result<Self, Error> {
+        if tolerance >= 0.0 {
+            Ok(self.with_tolerance(tolerance))
+        } else {
+            Err(Error::InvalidTolerance(tolerance))
+        }
+    }
+
+    /// Calculates the distance field like
+    /// [`calculate_distance_field`](DistanceFieldAlgorithm::calculate_distance_field)
+    /// and reports how the solve went.
+    pub fn calculate_distance_field_with_report(
+        &self,
+        distance_field: &mut DistanceField,
+        obstacles: &Obstacles,
+    ) -> SolveReport {
+        self.fast_sweeping(distance_field, None, obstacles)
+    }
+
+    fn fast_sweeping(
+        &self,
+        distance_field: &mut DistanceField,
+        mut nearest: Option<&mut NearestObstacles>,
+        obstacles: &Obstacles,
+    ) -> SolveReport {
         self.initialize(distance_field, obstacles);
-        self.perform_sweeps(distance_field);
+        if let Some(nearest) = nearest.as_deref_mut() {
+            nearest.initialize(obstacles);
+        }
+        let spans = self.row_spans(obstacles);
+        let report = self.perform_sweeps(distance_field, nearest, &spans);
+        self.apply_cutoff(distance_field);
+        report
+    }
+
+    /// Returns the columns of every row that can lie within the maximum distance.
+    pub(crate) fn row_spans(&self, obstacles: &Obstacles) -> Vec<Range<usize>> {
+        let (dx, dy) = self.spacing;
+        row_spans(obstacles, self.max_distance / dx.min(dy))
+    }
+
+    /// Replaces the distances beyond the maximum distance.
+    pub(crate) fn apply_cutoff(&self, distance_field: &mut DistanceField) {
+        if self.max_distance < DistanceField::MAX_DISTANCE {
+            self.cutoff
+                .apply(distance_field.iter_mut(), self.max_distance);
+        }
     }
 
-    fn initialize(&self, distance_field: &mut DistanceField, obstacles: &Obstacles) {
+    /// Returns the step sizes along the x and y axis.
+    #[cfg_attr(not(feature = "rayon"), allow(dead_code))]
+    pub(crate) const fn spacing(&self) -> (f32, f32) {
+        self.spacing
+    }
+
+    pub(crate) fn initialize(&self, distance_field: &mut DistanceField, obstacles: &Obstacles) {
         for (dist, &is_obstacle) in distance_field.iter_mut().zip(obstacles.iter()) {
             *dist = if is_obstacle {
                 0_f32
@@ -54,137 +177,320 @@ impl NaiveFastSweepingMethod {
         }
     }
 
-    fn perform_sweeps(&self, distance_field: &mut DistanceField) {
-        let max_iter = self.max_iterations;
-        let mut iteration = 0;
-
-        loop {
-            let mut changed = false;
-            changed |= self.sweep_topleft_bottomright(distance_field);
-            changed |= self.sweep_bottomright_topleft(distance_field);
-            changed |= self.sweep_topright_bottomleft(distance_field);
-            changed |= self.sweep_bottomleft_topright(distance_field);
+    fn perform_sweeps(
+        &self,
+        distance_field: &mut DistanceField,
+        mut nearest: Option<&mut NearestObstacles>,
+        spans: &[Range<usize>],
+    ) -> SolveReport {
+        self.perform_sweeps_with(distance_field, |distance_field, direction| {
+            let nearest = nearest.as_deref_mut();
+
+            // The directional sweeps skip the first row and column in sweep order, which are
+            // covered by the other directions. Grids with a single row or column have no
+            // other cells to cover them and are swept along their only axis instead.
+            let (dx, dy) = self.spacing;
+            if distance_field.height() <= 1 {
+                return Self::sweep_line(distance_field, nearest, direction.reverse_x(), dx);
+            }
+            if distance_field.width() <= 1 {
+                return Self::sweep_line(distance_field, nearest, direction.reverse_y(), dy);
+            }
 
-            iteration += 1;
-            if !changed || (max_iter > 0 && iteration >= max_iter) {
-                break;
+            match direction {
+                SweepDirection::TopLeftBottomRight => {
+                    self.sweep_topleft_bottomright(distance_field, nearest, spans)
+                }
+                SweepDirection::BottomRightTopLeft => {
+                    self.sweep_bottomright_topleft(distance_field, nearest, spans)
+                }
+                SweepDirection::TopRightBottomLeft => {
+                    self.sweep_topright_bottomleft(distance_field, nearest, spans)
+                }
+                SweepDirection::BottomLeftTopRight => {
+                    self.sweep_bottomleft_topright(distance_field, nearest, spans)
+                }
             }
-        }
+        })
     }
 
-    fn sweep_topleft_bottomright(&self, distance_field: &mut DistanceField) -> bool {
-        let step_size = self.step_size;
-        let height = distance_field.height();
+    /// Runs sweep iterations until convergence or until the iteration limit is reached,
+    /// using `sweep` to perform each directional sweep.
+    pub(crate) fn perform_sweeps_with<F>(
+        &self,
+        distance_field: &mut DistanceField,
+        mut sweep: F,
+    ) -> SolveReport
+    where
+        F: FnMut(&mut DistanceField, SweepDirection) -> SweepStats,
+    {
+        iterate_sweeps(self.max_iterations, self.tolerance, || {
+            SweepDirection::ALL.map(|direction| sweep(distance_field, direction))
+        })
+    }
+
+    /// Sweeps a grid consisting of a single row or column along its only axis.
+    fn sweep_line(
+        distance_field: &mut DistanceField,
+        mut nearest: Option<&mut NearestObstacles>,
+        reverse: bool,
+        step_size: f32,
+    ) -> SweepStats {
         let width = distance_field.width();
-        let mut changed = false;
+        let distances = distance_field.as_mut_slice();
+        let len = distances.len();
+        let mut stats = SweepStats::default();
+
+        for i in 1..len {
+            let (index, upwind) = if reverse {
+                (len - 1 - i, len - i)
+            } else {
+                (i, i - 1)
+            };
 
-        for y in 1..height {
+            let center = distances[index];
+            let new_value = center.min(distances[upwind] + step_size);
+            if new_value != center {
+                distances[index] = new_value;
+                stats.record(center, new_value);
+
+                if let Some(nearest) = nearest.as_deref_mut() {
+                    let source = (upwind % width, upwind / width);
+                    nearest.propagate(source, (index % width, index / width));
+                }
+            }
+        }
+        stats
+    }
+
+    fn sweep_topleft_bottomright(
+        &self,
+        distance_field: &mut DistanceField,
+        mut nearest: Option<&mut NearestObstacles>,
+        spans: &[Range<usize>],
+    ) -> SweepStats {
+        let (dx, dy) = self.spacing;
+        let mut stats = SweepStats::default();
+
+        for (y, span) in spans.iter().enumerate().skip(1) {
+            let start = span.start.max(1);
+            if start >= span.end {
+                continue;
+            }
             let (prev_row, current_row) = distance_field.get_rows_mut(y - 1);
-            let mut carry = current_row[0];
+            let mut carry = current_row[start - 1];
 
-            for x in 1..width {
+            for x in start..span.end {
                 let center = current_row[x];
                 let up_neighbor = prev_row[x];
-                let new_value = min3(center, up_neighbor + step_size, carry + step_size);
+                let new_value = min3(center, up_neighbor + dy, carry + dx);
 
                 if new_value != center {
                     current_row[x] = new_value;
-                    changed = true;
+                    stats.record(center, new_value);
+
+                    if let Some(nearest) = nearest.as_deref_mut() {
+                        let source = if up_neighbor + dy <= carry + dx {
+                            (x, y - 1)
+                        } else {
+                            (x - 1, y)
+                        };
+                        nearest.propagate(source, (x, y));
+                    }
                 }
                 carry = new_value;
             }
         }
-        changed
+        stats
     }
 
-    fn sweep_bottomright_topleft(&self, distance_field: &mut DistanceField) -> bool {
-        let step_size = self.step_size;
+    fn sweep_bottomright_topleft(
+        &self,
+        distance_field: &mut DistanceField,
+        mut nearest: Option<&mut NearestObstacles>,
+        spans: &[Range<usize>],
+    ) -> SweepStats {
+        let (dx, dy) = self.spacing;
         let height = distance_field.height();
         let width = distance_field.width();
-        let mut changed = false;
+        let mut stats = SweepStats::default();
 
         for y in (0..height - 1).rev() {
+            let end = spans[y].end.min(width - 1);
+            if spans[y].start >= end {
+                continue;
+            }
             let (current_row, next_row) = distance_field.get_rows_mut(y);
-            let mut carry = current_row[width - 1];
+            let mut carry = current_row[end];
 
-            for x in (0..width - 1).rev() {
+            for x in (spans[y].start..end).rev() {
                 let center = current_row[x];
                 let down_neighbor = next_row[x];
-                let new_value = min3(center, down_neighbor + step_size, carry + step_size);
+                let new_value = min3(center, down_neighbor + dy, carry + dx);
 
                 if new_value != center {
                     current_row[x] = new_value;
-                    changed = true;
+                    stats.record(center, new_value);
+
+                    if let Some(nearest) = nearest.as_deref_mut() {
+                        let source = if down_neighbor + dy <= carry + dx {
+                            (x, y + 1)
+                        } else {
+                            (x + 1, y)
+                        };
+                        nearest.propagate(source, (x, y));
+                    }
                 }
                 carry = new_value;
             }
         }
-        changed
+        stats
     }
 
-    fn sweep_topright_bottomleft(&self, distance_field: &mut DistanceField) -> bool {
-        let step_size = self.step_size;
-        let height = distance_field.height();
+    fn sweep_topright_bottomleft(
+        &self,
+        distance_field: &mut DistanceField,
+        mut nearest: Option<&mut NearestObstacles>,
+        spans: &[Range<usize>],
+    ) -> SweepStats {
+        let (dx, dy) = self.spacing;
         let width = distance_field.width();
-        let mut changed = false;
+        let mut stats = SweepStats::default();
 
-        for y in 1..height {
+        for (y, span) in spans.iter().enumerate().skip(1) {
+            let end = span.end.min(width - 1);
+            if span.start >= end {
+                continue;
+            }
             let (prev_row, current_row) = distance_field.get_rows_mut(y - 1);
-            let mut carry = current_row[width - 1];
+            let mut carry = current_row[end];
 
-            for x in (0..width - 1).rev() {
+            for x in (span.start..end).rev() {
                 let center = current_row[x];
                 let up_neighbor = prev_row[x];
-                let new_value = min3(center, up_neighbor + step_size, carry + step_size);
+                let new_value = min3(center, up_neighbor + dy, carry + dx);
 
                 if new_value != center {
                     current_row[x] = new_value;
-                    changed = true;
+                    stats.record(center, new_value);
+
+                    if let Some(nearest) = nearest.as_deref_mut() {
+                        let source = if up_neighbor + dy <= carry + dx {
+                            (x, y - 1)
+                        } else {
+                            (x + 1, y)
+                        };
+                        nearest.propagate(source, (x, y));
+                    }
                 }
                 carry = new_value;
             }
         }
-        changed
+        stats
     }
 
-    fn sweep_bottomleft_topright(&self, distance_field: &mut DistanceField) -> bool {
-        let step_size = self.step_size;
+    fn sweep_bottomleft_topright(
+        &self,
+        distance_field: &mut DistanceField,
+        mut nearest: Option<&mut NearestObstacles>,
+        spans: &[Range<usize>],
+    ) -> SweepStats {
+        let (dx, dy) = self.spacing;
         let height = distance_field.height();
-        let width = distance_field.width();
-        let mut changed = false;
+        let mut stats = SweepStats::default();
 
         for y in (0..height - 1).rev() {
+            let start = spans[y].start.max(1);
+            if start >= spans[y].end {
+                continue;
+            }
             let (current_row, next_row) = distance_field.get_rows_mut(y);
-            let mut carry = current_row[0];
+            let mut carry = current_row[start - 1];
 
-            for x in 1..width {
+            for x in start..spans[y].end {
                 let center = current_row[x];
                 let down_neighbor = next_row[x];
-                let new_value = min3(center, down_neighbor + step_size, carry + step_size);
+                let new_value = min3(center, down_neighbor + dy, carry + dx);
 
                 if new_value != center {
                     current_row[x] = new_value;
-                    changed = true;
+                    stats.record(center, new_value);
+
+                    if let Some(nearest) = nearest.as_deref_mut() {
+                        let source = if down_neighbor + dy <= carry + dx {
+                            (x, y + 1)
+                        } else {
+                            (x - 1, y)
+                        };
+                        nearest.propagate(source, (x, y));
+                    }
                 }
                 carry = new_value;
             }
         }
-        changed
+        stats
+    }
+}
+
+/// The direction of a single sweep over the grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SweepDirection {
+    TopLeftBottomRight,
+    BottomRightTopLeft,
+    TopRightBottomLeft,
+    BottomLeftTopRight,
+}
+
+impl SweepDirection {
+    /// All directions, in the order they are swept in each iteration.
+    pub(crate) const ALL: [Self; 4] = [
+        Self::TopLeftBottomRight,
+        Self::BottomRightTopLeft,
+        Self::TopRightBottomLeft,
+        Self::BottomLeftTopRight,
+    ];
+
+    /// Returns whether the sweep runs from right to left.
+    pub(crate) const fn reverse_x(self) -> bool {
+        matches!(self, Self::BottomRightTopLeft | Self::TopRightBottomLeft)
+    }
+
+    /// Returns whether the sweep runs from bottom to top.
+    pub(crate) const fn reverse_y(self) -> bool {
+        matches!(self, Self::BottomRightTopLeft | Self::BottomLeftTopRight)
     }
 }
 
 impl Default for NaiveFastSweepingMethod {
     fn default() -> Self {
         Self {
-            step_size: 1.0,
+            spacing: (1.0, 1.0),
             max_iterations: 0,
+            tolerance: 0.0,
+            max_distance: DistanceField::MAX_DISTANCE,
+            cutoff: Cutoff::Clamp,
         }
     }
 }
 
 impl DistanceFieldAlgorithm for NaiveFastSweepingMethod {
     fn calculate_distance_field(&self, distance_field: &mut DistanceField, obstacles: &Obstacles) {
-        self.fast_sweeping(distance_field, obstacles)
+        self.fast_sweeping(distance_field, None, obstacles);
+    }
+}
+
+impl FeatureTransformAlgorithm for NaiveFastSweepingMethod {
+    fn calculate_feature_transform(
+        &self,
+        distance_field: &mut DistanceField,
+        nearest: &mut NearestObstacles,
+        obstacles: &Obstacles,
+    ) {
+        if let Err(error) = nearest_obstacles::check_dimensions(distance_field, nearest, obstacles)
+        {
+            panic!("{error}");
+        }
+        self.fast_sweeping(distance_field, Some(nearest), obstacles);
     }
 }
 
@@ -289,14 +595,162 @@ mod tests {
     #[test]
     fn test_default_values() {
         let algo = NaiveFastSweepingMethod::default();
-        assert_eq!(algo.step_size, 1.0);
+        assert_eq!(algo.spacing, (1.0, 1.0));
         assert_eq!(algo.max_iterations, 0);
     }
 
     #[test]
     fn test_builder_step_size() {
         let algo = NaiveFastSweepingMethod::default().with_step_size(0.5);
-        assert_eq!(algo.step_size, 0.5);
+        assert_eq!(algo.spacing, (0.5, 0.5));
+    }
+
+    #[test]
+    fn test_try_with_step_size() {
+        let algo = NaiveFastSweepingMethod::default().try_with_step_size(0.5);
+        assert_eq!(algo.map(|algo| algo.spacing), Ok((0.5, 0.5)));
+
+        for step_size in [0.0, -1.0, f32::INFINITY] {
+            assert_eq!(
+                NaiveFastSweepingMethod::default()
+                    .try_with_step_size(step_size)
+                    .unwrap_err(),
+                Error::InvalidStepSize(step_size)
+            );
+        }
+        assert!(matches!(
+            NaiveFastSweepingMethod::default().try_with_step_size(f32::NAN),
+            Err(Error::InvalidStepSize(s)) if s.is_nan()
+        ));
+    }
+
+    #[test]
+    fn test_try_with_tolerance() {
+        assert!(NaiveFastSweepingMethod::default()
+            .try_with_tolerance(0.0)
+            .is_ok());
+        assert_eq!(
+            NaiveFastSweepingMethod::default()
+                .try_with_tolerance(-0.5)
+                .unwrap_err(),
+            Error::InvalidTolerance(-0.5)
+        );
+        assert!(NaiveFastSweepingMethod::default()
+            .try_with_tolerance(f32::NAN)
+            .is_err());
+    }
+
+    #[test]
+    fn test_try_with_spacing() {
+        let algo = NaiveFastSweepingMethod::default().try_with_spacing(1.0, 2.0);
+        assert_eq!(algo.map(|algo| algo.spacing), Ok((1.0, 2.0)));
+        assert_eq!(
+            NaiveFastSweepingMethod::default()
+                .try_with_spacing(1.0, -2.0)
+                .unwrap_err(),
+            Error::InvalidStepSize(-2.0)
+        );
+    }
+
+    #[test]
+    fn test_anisotropic_spacing() {
+        let mut obstacles = Obstacles::new(21, 21);
+        obstacles.set_at(10, 10, true);
+        let mut df = DistanceField::from(&obstacles);
+        NaiveFastSweepingMethod::default()
+            .with_spacing(1.0, 2.0)
+            .calculate_distance_field(&mut df, &obstacles);
+
+        for k in 1..=10 {
+            assert_eq!(*df.get_at(10 + k, 10), k as f32);
+            assert_eq!(*df.get_at(10 - k, 10), k as f32);
+            assert_eq!(*df.get_at(10, 10 + k), 2.0 * k as f32);
+            assert_eq!(*df.get_at(10, 10 - k), 2.0 * k as f32);
+            assert_eq!(*df.get_at(10 + k, 10 - k), 3.0 * k as f32);
+        }
+    }
+
+    #[test]
+    fn test_anisotropic_spacing_on_lines() {
+        for (width, height, expected) in [(5, 1, 4.0), (1, 5, 8.0)] {
+            let mut obstacles = Obstacles::new(width, height);
+            obstacles.set_at(0, 0, true);
+            let mut df = DistanceField::from(&obstacles);
+            NaiveFastSweepingMethod::default()
+                .with_spacing(1.0, 2.0)
+                .calculate_distance_field(&mut df, &obstacles);
+            assert_eq!(*df.get_at(width - 1, height - 1), expected);
+        }
+    }
+
+    #[test]
+    fn test_builder_tolerance() {
+        let algo = NaiveFastSweepingMethod::default().with_tolerance(0.25);
+        assert_eq!(algo.tolerance, 0.25);
+        assert_eq!(NaiveFastSweepingMethod::default().tolerance, 0.0);
+    }
+
+    #[test]
+    fn test_report_converged() {
+        let obstacles = create_test_obstacles();
+        let mut df = DistanceField::from(&obstacles);
+        let algo = NaiveFastSweepingMethod::default().with_step_size(0.1);
+        let report = algo.calculate_distance_field_with_report(&mut df, &obstacles);
+
+        assert!(report.converged);
+        assert_eq!(report.max_delta, 0.0);
+        assert_eq!(report.cells_changed.len(), 4 * report.iterations);
+        assert!(report.cells_changed.iter().rev().take(4).all(|&n| n == 0));
+        assert!(report.cells_changed[0] > 0);
+
+        let mut reference = DistanceField::from(&obstacles);
+        algo.calculate_distance_field(&mut reference, &obstacles);
+        for (a, b) in df.iter().zip(reference.iter()) {
+            assert_eq!(*a, *b);
+        }
+    }
+
+    #[test]
+    fn test_report_hits_iteration_cap() {
+        let obstacles = create_test_obstacles();
+        let mut df = DistanceField::from(&obstacles);
+        let report = NaiveFastSweepingMethod::default()
+            .with_max_iterations(1)
+            .calculate_distance_field_with_report(&mut df, &obstacles);
+
+        assert_eq!(report.iterations, 1);
+        assert!(!report.converged);
+        assert!(report.max_delta.is_infinite());
+        assert_eq!(report.cells_changed.len(), 4);
+    }
+
+    #[test]
+    fn test_report_empty_grid() {
+        let obstacles = Obstacles::new(10, 10);
+        let mut df = DistanceField::from(&obstacles);
+        let report = NaiveFastSweepingMethod::default()
+            .calculate_distance_field_with_report(&mut df, &obstacles);
+        assert_eq!(report.iterations, 1);
+        assert_eq!(report.cells_changed, vec![0; 4]);
+        assert!(report.converged);
+    }
+
+    #[test]
+    fn test_tolerance_stops_early() {
+        let obstacles = create_test_obstacles();
+        let algo = NaiveFastSweepingMethod::default().with_step_size(0.1);
+
+        let mut exact = DistanceField::from(&obstacles);
+        let exact_report = algo.calculate_distance_field_with_report(&mut exact, &obstacles);
+
+        let mut loose = DistanceField::from(&obstacles);
+        let loose_report = algo
+            .with_tolerance(f32::MAX)
+            .calculate_distance_field_with_report(&mut loose, &obstacles);
+
+        assert!(loose_report.converged);
+        assert!(loose_report.iterations <= exact_report.iterations);
+        assert!(loose_report.max_delta.is_finite());
     }
 
     #[test]
@@ -344,11 +798,9 @@ mod tests {
         let mut df = DistanceField::from(&obstacles);
         let algo = NaiveFastSweepingMethod::default();
         algo.calculate_distance_field(&mut df, &obstacles);
-        // Sweep algorithm requires width >= 2 for propagation (loops start at x=1)
-        // On 1-width grids, only the obstacle cell gets updated
-        assert_eq!(*df.get_at(0, 0), 0.0);
-        assert!(df.get_at(0, 1).is_infinite());
-        assert!(df.get_at(0, 2).is_infinite());
+        for y in 0..5 {
+            assert_eq!(*df.get_at(0, y), y as f32);
+        }
     }
 
     #[test]
@@ -358,11 +810,44 @@ mod tests {
         let mut df = DistanceField::from(&obstacles);
         let algo = NaiveFastSweepingMethod::default();
         algo.calculate_distance_field(&mut df, &obstacles);
-        // Sweep algorithm requires height >= 2 for propagation (loops start at y=1)
-        // On 1-height grids, only the obstacle cell gets updated
-        assert_eq!(*df.get_at(0, 0), 0.0);
-        assert!(df.get_at(1, 0).is_infinite());
-        assert!(df.get_at(2, 0).is_infinite());
+        for x in 0..5 {
+            assert_eq!(*df.get_at(x, 0), x as f32);
+        }
+    }
+
+    #[test]
+    fn test_edge_case_line_propagates_both_ways() {
+        for (width, height) in [(9, 1), (1, 9)] {
+            let mut obstacles = Obstacles::new(width, height);
+            obstacles.set_at(width / 2, height / 2, true);
+            obstacles.set_at(width - 1, height - 1, true);
+            let mut df = DistanceField::from(&obstacles);
+            let mut nearest = NearestObstacles::from(&obstacles);
+            NaiveFastSweepingMethod::default()
+                .with_step_size(0.5)
+                .calculate_feature_transform(&mut df, &mut nearest, &obstacles);
+
+            let expected = [2.0, 1.5, 1.0, 0.5, 0.0, 0.5, 1.0, 0.5, 0.0];
+            let nearest_index = [4, 4, 4, 4, 4, 4, 4, 8, 8];
+            for i in 0..9 {
+                let (x, y) = if height == 1 { (i, 0) } else { (0, i) };
+                let n = nearest_index[i];
+                let source = if height == 1 { (n, 0) } else { (0, n) };
+                assert_eq!(*df.get_at(x, y), expected[i]);
+                assert_eq!(*nearest.get_at(x, y), Some(source));
+            }
+        }
+    }
+
+    #[test]
+    fn test_edge_case_empty_grids() {
+        for (width, height) in [(0, 0), (0, 4), (4, 0)] {
+            let obstacles = Obstacles::new(width, height);
+            let mut df = DistanceField::from(&obstacles);
+            let report = NaiveFastSweepingMethod::default()
+                .calculate_distance_field_with_report(&mut df, &obstacles);
+            assert!(report.converged);
+        }
     }
 
     #[test]
@@ -581,6 +1066,80 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_feature_transform_consistent_with_distances() {
+        let mut obstacles = Obstacles::new(19, 13);
+        for (x, y) in [(0, 0), (4, 9), (10, 3), (11, 3), (18, 12), (15, 6)] {
+            obstacles.set_at(x, y, true);
+        }
+        let mut df = DistanceField::from(&obstacles);
+        let mut nearest = NearestObstacles::from(&obstacles);
+        let algo = NaiveFastSweepingMethod::default().with_step_size(0.5);
+        algo.calculate_feature_transform(&mut df, &mut nearest, &obstacles);
+
+        let mut reference = DistanceField::from(&obstacles);
+        algo.calculate_distance_field(&mut reference, &obstacles);
+
+        for y in 0..obstacles.height() {
+            for x in 0..obstacles.width() {
+                let (nx, ny) = nearest.get_at(x, y).expect("every cell has a source");
+                assert!(*obstacles.get_at(nx, ny));
+                let manhattan = (nx.abs_diff(x) + ny.abs_diff(y)) as f32 * 0.5;
+                assert_eq!(*df.get_at(x, y), manhattan, "({x}, {y})");
+                assert_eq!(*df.get_at(x, y), *reference.get_at(x, y));
+            }
+        }
+    }
+
+    #[test]
+    fn test_feature_transform_anisotropic_spacing() {
+        let mut obstacles = Obstacles::new(17, 15);
+        for (x, y) in [(0, 6), (6, 0), (16, 9), (9, 14), (12, 4)] {
+            obstacles.set_at(x, y, true);
+        }
+        let mut df = DistanceField::from(&obstacles);
+        let mut nearest = NearestObstacles::from(&obstacles);
+        NaiveFastSweepingMethod::default()
+            .with_spacing(1.0, 3.0)
+            .calculate_feature_transform(&mut df, &mut nearest, &obstacles);
+
+        for y in 0..obstacles.height() {
+            for x in 0..obstacles.width() {
+                let (nx, ny) = nearest.get_at(x, y).expect("every cell has a source");
+                let weighted = nx.abs_diff(x) as f32 + 3.0 * ny.abs_diff(y) as f32;
+                assert_eq!(*df.get_at(x, y), weighted, "({x}, {y})");
+            }
+        }
+    }
+
+    #[test]
+    fn test_feature_transform_obstacles_refer_to_themselves() {
+        let obstacles = create_test_obstacles();
+        let mut df = DistanceField::from(&obstacles);
+        let mut nearest = NearestObstacles::from(&obstacles);
+        NaiveFastSweepingMethod::default().calculate_feature_transform(
+            &mut df,
+            &mut nearest,
+            &obstacles,
+        );
+        assert_eq!(*nearest.get_at(100, 150), Some((100, 150)));
+        assert_eq!(*nearest.get_at(550, 350), Some((550, 350)));
+        assert_eq!(*nearest.get_at(250, 210), Some((250, 200)));
+    }
+
+    #[test]
+    fn test_feature_transform_empty_grid() {
+        let obstacles = Obstacles::new(6, 6);
+        let mut df = DistanceField::from(&obstacles);
+        let mut nearest = NearestObstacles::from(&obstacles);
+        NaiveFastSweepingMethod::default().calculate_feature_transform(
+            &mut df,
+            &mut nearest,
+            &obstacles,
+        );
+        assert!(nearest.iter().all(Option::is_none));
+    }
+
     #[test]
     fn test_distance_field_max_distance_constant() {
         assert_eq!(DistanceField::MAX_DISTANCE, f32::INFINITY);
@@ -603,4 +1162,45 @@ mod tests {
             assert_eq!(*a, *b);
         }
     }
+
+    #[test]
+    fn test_max_distance_clamps() {
+        let obstacles = create_test_obstacles();
+        let algo = NaiveFastSweepingMethod::default().with_step_size(0.1);
+        let mut full = DistanceField::from(&obstacles);
+        let full_report = algo.calculate_distance_field_with_report(&mut full, &obstacles);
+
+        let mut clamped = DistanceField::from(&obstacles);
+        let report = algo
+            .with_max_distance(2.5)
+            .calculate_distance_field_with_report(&mut clamped, &obstacles);
+        for (full, clamped) in full.iter().zip(clamped.iter()) {
+            assert_eq!(*clamped, full.min(2.5));
+        }
+
+        // Only the cells near the obstacles are swept.
+        let cells = |report: &SolveReport| report.cells_changed.iter().sum::<usize>();
+        assert!(cells(&report) * 4 < cells(&full_report));
+    }
+
+    #[test]
+    fn test_max_distance_unreached() {
+        let obstacles = create_test_obstacles();
+        let algo = NaiveFastSweepingMethod::default().with_spacing(0.5, 0.25);
+        let mut full = DistanceField::from(&obstacles);
+        algo.calculate_distance_field(&mut full, &obstacles);
+
+        let mut cut = DistanceField::from(&obstacles);
+        algo.with_max_distance(10.0)
+            .with_cutoff(Cutoff::Unreached)
+            .calculate_distance_field(&mut cut, &obstacles);
+        for (full, cut) in full.iter().zip(cut.iter()) {
+            let expected = if *full <= 10.0 {
+                *full
+            } else {
+                DistanceField::MAX_DISTANCE
+            };
+            assert_eq!(*cut, expected);
+        }
+    }
 }
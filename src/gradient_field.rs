@@ -0,0 +1,210 @@
+use crate::{DistanceField, Grid, GridBounds, GridN};
+
+/// A 2D grid of distance field gradients.
+///
+/// Gradients are computed with upwind differences, i.e. along each axis towards the smaller
+/// neighbor the front arrived from. Unlike central differences this stays well-defined on the
+/// ridges between obstacles. Axes without a smaller finite neighbor, such as at obstacles,
+/// local minima and unreached cells, have a zero derivative.
+#[derive(Debug, Clone)]
+pub struct GradientField {
+    gradients: GridN<(f32, f32), 2>,
+}
+
+impl GradientField {
+    /// Computes the gradients of a distance field whose cells are `dx` and `dy` units apart.
+    pub fn compute(distance_field: &DistanceField, (dx, dy): (f32, f32)) -> Self {
+        let width = distance_field.width();
+        let height = distance_field.height();
+        let value = |x: usize, y: usize| *distance_field.get_at(x, y);
+
+        let mut gradients = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for x in 0..width {
+                let center = value(x, y);
+                if !center.is_finite() {
+                    gradients.push((0.0, 0.0));
+                    continue;
+                }
+
+                let left = (x > 0).then(|| value(x - 1, y));
+                let right = (x + 1 < width).then(|| value(x + 1, y));
+                let up = (y > 0).then(|| value(x, y - 1));
+                let down = (y + 1 < height).then(|| value(x, y + 1));
+                gradients.push((
+                    upwind_difference(center, left, right) / dx,
+                    upwind_difference(center, up, down) / dy,
+                ));
+            }
+        }
+
+        Self {
+            gradients: GridN::from_vec([width, height], gradients),
+        }
+    }
+
+    /// Returns the width of the gradient field.
+    pub const fn width(&self) -> usize {
+        self.gradients.shape()[0]
+    }
+
+    /// Returns the height of the gradient field.
+    pub const fn height(&self) -> usize {
+        self.gradients.shape()[1]
+    }
+
+    /// Returns the gradients as a generic 2D grid.
+    pub const fn as_grid(&self) -> &GridN<(f32, f32), 2> {
+        &self.gradients
+    }
+
+    /// Returns an iterator over the gradients.
+    pub fn iter(&self) -> std::slice::Iter<'_, (f32, f32)> {
+        self.gradients.iter()
+    }
+
+    /// Bilinearly interpolates the gradient at a continuous position, with cell centers at
+    /// integer coordinates. Positions outside of the grid are clamped.
+    pub fn sample(&self, x: f32, y: f32) -> (f32, f32) {
+        if self.width() == 0 || self.height() == 0 {
+            return (0.0, 0.0);
+        }
+
+        let x = x.clamp(0.0, (self.width() - 1) as f32);
+        let y = y.clamp(0.0, (self.height() - 1) as f32);
+        let (x0, y0) = (x.floor() as usize, y.floor() as usize);
+        let (x1, y1) = (
+            (x0 + 1).min(self.width() - 1),
+            (y0 + 1).min(self.height() - 1),
+        );
+        let (tx, ty) = (x - x0 as f32, y - y0 as f32);
+
+        let lerp =
+            |a: (f32, f32), b: (f32, f32), t: f32| (a.0 + (b.0 - a.0) * t, a.1 + (b.1 - a.1) * t);
+        let top = lerp(*self.get_at(x0, y0), *self.get_at(x1, y0), tx);
+        let bottom = lerp(*self.get_at(x0, y1), *self.get_at(x1, y1), tx);
+        lerp(top, bottom, ty)
+    }
+}
+
+/// Returns the one-sided difference towards the smaller of the two neighbors, or zero
+/// if neither neighbor is smaller than the center.
+fn upwind_difference(center: f32, lower: Option<f32>, upper: Option<f32>) -> f32 {
+    let lower = lower.unwrap_or(f32::INFINITY);
+    let upper = upper.unwrap_or(f32::INFINITY);
+    if lower.min(upper) >= center {
+        0.0
+    } else if lower <= upper {
+        center - lower
+    } else {
+        upper - center
+    }
+}
+
+impl From<&DistanceField> for GradientField {
+    fn from(value: &DistanceField) -> Self {
+        Self::compute(value, (1.0, 1.0))
+    }
+}
+
+impl Grid for GradientField {
+    type Item = (f32, f32);
+
+    fn get_at(&self, x: usize, y: usize) -> &Self::Item {
+        &self.gradients[[x, y]]
+    }
+
+    fn set_at(&mut self, x: usize, y: usize, value: Self::Item) {
+        self.gradients[[x, y]] = value
+    }
+}
+
+impl GridBounds for GradientField {
+    fn width(&self) -> usize {
+        self.width()
+    }
+
+    fn height(&self) -> usize {
+        self.height()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DistanceFieldAlgorithm, EuclideanDistanceTransform, Obstacles};
+
+    /// A field holding `2x + 3y + 1`.
+    fn ramp(width: usize, height: usize) -> DistanceField {
+        let mut df = DistanceField::new(width, height);
+        for y in 0..height {
+            for x in 0..width {
+                df.set_at(x, y, 2.0 * x as f32 + 3.0 * y as f32 + 1.0);
+            }
+        }
+        df
+    }
+
+    #[test]
+    fn test_upwind_difference() {
+        assert_eq!(upwind_difference(3.0, Some(1.0), Some(4.0)), 2.0);
+        assert_eq!(upwind_difference(3.0, Some(5.0), Some(2.5)), -0.5);
+        assert_eq!(upwind_difference(3.0, Some(3.0), None), 0.0);
+        assert_eq!(upwind_difference(3.0, Some(f32::INFINITY), Some(1.0)), -2.0);
+    }
+
+    #[test]
+    fn test_ramp_gradient() {
+        let gradients = GradientField::from(&ramp(5, 4));
+        assert_eq!(gradients.width(), 5);
+        assert_eq!(gradients.height(), 4);
+        assert_eq!(*gradients.get_at(2, 2), (2.0, 3.0));
+        // The minimum of the ramp has no upwind neighbor.
+        assert_eq!(*gradients.get_at(0, 0), (0.0, 0.0));
+    }
+
+    #[test]
+    fn test_spacing_scales_gradient() {
+        let gradients = GradientField::compute(&ramp(5, 4), (0.5, 2.0));
+        assert_eq!(*gradients.get_at(2, 2), (4.0, 1.5));
+    }
+
+    #[test]
+    fn test_obstacles_and_infinities_have_zero_gradient() {
+        let mut obstacles = Obstacles::new(9, 9);
+        obstacles.set_at(4, 4, true);
+        let mut df = DistanceField::from(&obstacles);
+        EuclideanDistanceTransform::default().calculate_distance_field(&mut df, &obstacles);
+        df.set_at(8, 8, f32::INFINITY);
+
+        let gradients = GradientField::from(&df);
+        assert_eq!(*gradients.get_at(4, 4), (0.0, 0.0));
+        assert_eq!(*gradients.get_at(8, 8), (0.0, 0.0));
+        assert!(gradients.iter().all(|g| g.0.is_finite() && g.1.is_finite()));
+    }
+
+    #[test]
+    fn test_gradient_points_away_from_obstacle() {
+        let mut obstacles = Obstacles::new(41, 41);
+        obstacles.set_at(20, 20, true);
+        let mut df = DistanceField::from(&obstacles);
+        EuclideanDistanceTransform::default().calculate_distance_field(&mut df, &obstacles);
+
+        let gradients = GradientField::from(&df);
+        assert_eq!(*gradients.get_at(30, 20), (1.0, 0.0));
+        assert_eq!(*gradients.get_at(20, 5), (0.0, -1.0));
+
+        let (gx, gy) = *gradients.get_at(35, 35);
+        assert!(gx > 0.0 && gy > 0.0);
+        assert!(((gx * gx + gy * gy).sqrt() - 1.0).abs() < 0.05);
+    }
+
+    #[test]
+    fn test_sample_interpolates() {
+        let mut gradients = GradientField::from(&ramp(3, 3));
+        gradients.set_at(1, 1, (0.0, 0.0));
+        gradients.set_at(2, 1, (2.0, 4.0));
+        assert_eq!(gradients.sample(1.5, 1.0), (1.0, 2.0));
+        assert_eq!(gradients.sample(5.0, 1.0), (2.0, 4.0));
+    }
+}
@@ -0,0 +1,161 @@
+/// Placement of a grid in world coordinates, e.g. a robot costmap in metres.
+///
+/// Cell `(x, y)` covers the square from `(x, y)` to `(x + 1, y + 1)` in grid coordinates,
+/// scaled by [`resolution`](Self::resolution), rotated by [`rotation`](Self::rotation) and
+/// translated such that the corner of cell `(0, 0)` lies at [`origin`](Self::origin).
+/// This matches the origin convention of ROS occupancy grids.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GridGeometry {
+    origin: (f64, f64),
+    resolution: f64,
+    rotation: f64,
+}
+
+impl GridGeometry {
+    /// Creates a geometry with the given origin and cell size and no rotation.
+    pub const fn new(origin_x: f64, origin_y: f64, resolution: f64) -> Self {
+        Self {
+            origin: (origin_x, origin_y),
+            resolution,
+            rotation: 0.0,
+        }
+    }
+
+    /// Sets the counter-clockwise rotation of the grid axes, in radians.
+    #[must_use]
+    pub const fn with_rotation(mut self, rotation: f64) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    /// Returns the world coordinates of the corner of cell `(0, 0)`.
+    pub const fn origin(&self) -> (f64, f64) {
+        self.origin
+    }
+
+    /// Returns the edge length of a cell in world units.
+    pub const fn resolution(&self) -> f64 {
+        self.resolution
+    }
+
+    /// Returns the counter-clockwise rotation of the grid axes, in radians.
+    pub const fn rotation(&self) -> f64 {
+        self.rotation
+    }
+
+    /// Converts continuous grid coordinates, measured in cells, into world coordinates.
+    pub fn grid_to_world(&self, gx: f64, gy: f64) -> (f64, f64) {
+        let (sin, cos) = self.rotation.sin_cos();
+        let (x, y) = (gx * self.resolution, gy * self.resolution);
+        (
+            self.origin.0 + cos * x - sin * y,
+            self.origin.1 + sin * x + cos * y,
+        )
+    }
+
+    /// Converts world coordinates into continuous grid coordinates, measured in cells.
+    pub fn world_to_grid(&self, wx: f64, wy: f64) -> (f64, f64) {
+        let (sin, cos) = self.rotation.sin_cos();
+        let (x, y) = (wx - self.origin.0, wy - self.origin.1);
+        (
+            (cos * x + sin * y) / self.resolution,
+            (cos * y - sin * x) / self.resolution,
+        )
+    }
+
+    /// Returns the world coordinates of the center of cell `(x, y)`.
+    pub fn cell_to_world(&self, x: usize, y: usize) -> (f64, f64) {
+        self.grid_to_world(x as f64 + 0.5, y as f64 + 0.5)
+    }
+
+    /// Returns the cell containing the given world coordinates.
+    ///
+    /// Returns `None` for points before the first row or column. Points beyond the last
+    /// row or column are not detected, as the geometry does not know the grid size.
+    pub fn world_to_cell(&self, wx: f64, wy: f64) -> Option<(usize, usize)> {
+        let (gx, gy) = self.world_to_grid(wx, wy);
+        let (x, y) = (gx.floor(), gy.floor());
+        (x >= 0.0 && y >= 0.0).then_some((x as usize, y as usize))
+    }
+
+    /// Returns the PGM comment lines describing this geometry, in the style of a ROS map YAML.
+    pub(crate) fn pgm_comments(&self) -> String {
+        format!(
+            "# resolution: {}\n# origin: {} {} {}\n",
+            self.resolution, self.origin.0, self.origin.1, self.rotation
+        )
+    }
+}
+
+impl Default for GridGeometry {
+    fn default() -> Self {
+        Self::new(0.0, 0.0, 1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close((ax, ay): (f64, f64), (bx, by): (f64, f64)) {
+        assert!(
+            (ax - bx).abs() < 1e-9 && (ay - by).abs() < 1e-9,
+            "({ax}, {ay}) != ({bx}, {by})"
+        );
+    }
+
+    #[test]
+    fn test_default_values() {
+        let geometry = GridGeometry::default();
+        assert_eq!(geometry.origin(), (0.0, 0.0));
+        assert_eq!(geometry.resolution(), 1.0);
+        assert_eq!(geometry.rotation(), 0.0);
+    }
+
+    #[test]
+    fn test_cell_to_world() {
+        let geometry = GridGeometry::new(-10.0, 5.0, 0.05);
+        assert_close(geometry.cell_to_world(0, 0), (-9.975, 5.025));
+        assert_close(geometry.cell_to_world(200, 100), (0.025, 10.025));
+    }
+
+    #[test]
+    fn test_world_to_cell() {
+        let geometry = GridGeometry::new(-10.0, 5.0, 0.05);
+        assert_eq!(geometry.world_to_cell(-10.0, 5.0), Some((0, 0)));
+        assert_eq!(geometry.world_to_cell(0.01, 10.04), Some((200, 100)));
+        assert_eq!(geometry.world_to_cell(-10.01, 6.0), None);
+        assert_eq!(geometry.world_to_cell(-9.0, 4.99), None);
+    }
+
+    #[test]
+    fn test_rotation() {
+        let geometry = GridGeometry::new(1.0, 2.0, 0.5).with_rotation(std::f64::consts::FRAC_PI_2);
+        // The x axis of the grid points along the world y axis.
+        assert_close(geometry.grid_to_world(2.0, 0.0), (1.0, 3.0));
+        assert_close(geometry.grid_to_world(0.0, 2.0), (0.0, 2.0));
+        assert_eq!(geometry.world_to_cell(0.9, 3.1), Some((2, 0)));
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let geometry = GridGeometry::new(3.5, -7.25, 0.1).with_rotation(0.3);
+        for (gx, gy) in [(0.0, 0.0), (12.5, 3.25), (-4.0, 99.0)] {
+            let (wx, wy) = geometry.grid_to_world(gx, gy);
+            assert_close(geometry.world_to_grid(wx, wy), (gx, gy));
+        }
+        for (x, y) in [(0, 0), (17, 4), (250, 311)] {
+            let (wx, wy) = geometry.cell_to_world(x, y);
+            assert_eq!(geometry.world_to_cell(wx, wy), Some((x, y)));
+        }
+    }
+
+    #[test]
+    fn test_pgm_comments() {
+        let geometry = GridGeometry::new(-1.5, 2.0, 0.05).with_rotation(0.25);
+        assert_eq!(
+            geometry.pgm_comments(),
+            "# resolution: 0.05\n# origin: -1.5 2 0.25\n"
+        );
+    }
+}
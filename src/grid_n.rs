@@ -0,0 +1,225 @@
+use crate::{Grid, Grid3, GridBounds};
+use std::ops::{Index, IndexMut};
+
+/// A dense grid with `D` dimensions.
+///
+/// Cells are addressed by `[usize; D]` indices, with the first axis varying fastest in
+/// memory; a 2D grid is thus stored row by row like [`DistanceField`](crate::DistanceField).
+/// [`DistanceField`](crate::DistanceField), [`Obstacles`](crate::Obstacles) and their 3D
+/// counterparts wrap grids of this type, and [`DistanceFieldN`] and [`ObstaclesN`] provide
+/// fields of any dimension, such as 4D space-time fields.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GridN<T, const D: usize> {
+    cells: Vec<T>,
+    shape: [usize; D],
+    strides: [usize; D],
+}
+
+/// A distance field with `D` dimensions.
+pub type DistanceFieldN<const D: usize> = GridN<f32, D>;
+
+/// An obstacle grid with `D` dimensions.
+pub type ObstaclesN<const D: usize> = GridN<bool, D>;
+
+impl<T: Clone, const D: usize> GridN<T, D> {
+    /// Creates a new grid of the given shape with all cells set to `value`.
+    pub fn new(shape: [usize; D], value: T) -> Self {
+        let len = shape.iter().product();
+        Self::from_vec(shape, vec![value; len])
+    }
+}
+
+impl<T, const D: usize> GridN<T, D> {
+    /// Creates a grid of the given shape from its cells, with the first axis varying fastest.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of cells does not match the shape.
+    pub fn from_vec(shape: [usize; D], cells: Vec<T>) -> Self {
+        assert_eq!(
+            cells.len(),
+            shape.iter().product::<usize>(),
+            "the number of cells does not match the shape {shape:?}"
+        );
+        let mut strides = [1; D];
+        for axis in 1..D {
+            strides[axis] = strides[axis - 1] * shape[axis - 1];
+        }
+        Self {
+            cells,
+            shape,
+            strides,
+        }
+    }
+
+    /// Returns the number of cells along each axis.
+    pub const fn shape(&self) -> [usize; D] {
+        self.shape
+    }
+
+    /// Returns the distance in memory between neighboring cells along each axis.
+    pub const fn strides(&self) -> [usize; D] {
+        self.strides
+    }
+
+    /// Returns the number of cells.
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Returns whether the grid has no cells.
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Returns the position of a cell in memory, or `None` if it lies outside of the grid.
+    pub fn offset(&self, index: [usize; D]) -> Option<usize> {
+        index
+            .iter()
+            .zip(&self.shape)
+            .all(|(i, len)| i < len)
+            .then(|| index.iter().zip(&self.strides).map(|(i, s)| i * s).sum())
+    }
+
+    /// Returns the cell at `index`, or `None` if it lies outside of the grid.
+    pub fn get(&self, index: [usize; D]) -> Option<&T> {
+        self.offset(index).map(|offset| &self.cells[offset])
+    }
+
+    /// Returns the cell at `index` mutably, or `None` if it lies outside of the grid.
+    pub fn get_mut(&mut self, index: [usize; D]) -> Option<&mut T> {
+        self.offset(index).map(|offset| &mut self.cells[offset])
+    }
+
+    /// Returns an iterator over the cells in memory order.
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.cells.iter()
+    }
+
+    /// Returns a mutable iterator over the cells in memory order.
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, T> {
+        self.cells.iter_mut()
+    }
+
+    /// Returns the cells in memory order.
+    pub fn as_slice(&self) -> &[T] {
+        &self.cells
+    }
+
+    /// Returns the cells in memory order mutably.
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        &mut self.cells
+    }
+
+    /// Returns a grid of the same shape with `f` applied to every cell.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> GridN<U, D> {
+        GridN {
+            cells: self.cells.iter().map(f).collect(),
+            shape: self.shape,
+            strides: self.strides,
+        }
+    }
+}
+
+impl<T, const D: usize> Index<[usize; D]> for GridN<T, D> {
+    type Output = T;
+
+    fn index(&self, index: [usize; D]) -> &T {
+        match self.get(index) {
+            Some(cell) => cell,
+            None => panic!("index {index:?} is outside of the grid {:?}", self.shape),
+        }
+    }
+}
+
+impl<T, const D: usize> IndexMut<[usize; D]> for GridN<T, D> {
+    fn index_mut(&mut self, index: [usize; D]) -> &mut T {
+        let shape = self.shape;
+        match self.get_mut(index) {
+            Some(cell) => cell,
+            None => panic!("index {index:?} is outside of the grid {shape:?}"),
+        }
+    }
+}
+
+impl<T> Grid for GridN<T, 2> {
+    type Item = T;
+
+    fn get_at(&self, x: usize, y: usize) -> &T {
+        &self[[x, y]]
+    }
+
+    fn set_at(&mut self, x: usize, y: usize, value: T) {
+        self[[x, y]] = value;
+    }
+}
+
+impl<T> GridBounds for GridN<T, 2> {
+    fn width(&self) -> usize {
+        self.shape[0]
+    }
+
+    fn height(&self) -> usize {
+        self.shape[1]
+    }
+}
+
+impl<T> Grid3 for GridN<T, 3> {
+    type Item = T;
+
+    fn get_at(&self, x: usize, y: usize, z: usize) -> &T {
+        &self[[x, y, z]]
+    }
+
+    fn set_at(&mut self, x: usize, y: usize, z: usize, value: T) {
+        self[[x, y, z]] = value;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shape_and_strides() {
+        let grid = GridN::new([4, 3, 2, 5], 0_u8);
+        assert_eq!(grid.shape(), [4, 3, 2, 5]);
+        assert_eq!(grid.strides(), [1, 4, 12, 24]);
+        assert_eq!(grid.len(), 120);
+        assert!(!grid.is_empty());
+        assert!(GridN::new([4, 0], 0_u8).is_empty());
+    }
+
+    #[test]
+    fn test_index_access() {
+        let mut grid = GridN::new([4, 3, 2], 0);
+        grid[[3, 1, 1]] = 7;
+        assert_eq!(grid.offset([3, 1, 1]), Some(3 + 4 + 12));
+        assert_eq!(grid.as_slice()[19], 7);
+        assert_eq!(grid.get([3, 1, 1]), Some(&7));
+        assert_eq!(grid.get([4, 0, 0]), None);
+        assert_eq!(grid.get_mut([0, 3, 0]), None);
+        assert_eq!(Grid3::get_at(&grid, 3, 1, 1), &7);
+    }
+
+    #[test]
+    fn test_2d_layout_matches_grid() {
+        let grid = GridN::from_vec([3, 2], vec![0, 1, 2, 3, 4, 5]);
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(*grid.get_at(1, 1), 4);
+        assert_eq!(grid.map(|v| v * 2)[[2, 0]], 4);
+    }
+
+    #[test]
+    #[should_panic(expected = "outside of the grid")]
+    fn test_index_out_of_bounds_panics() {
+        let grid = GridN::new([2, 2], 0);
+        let _ = grid[[0, 2]];
+    }
+
+    #[test]
+    #[should_panic(expected = "does not match the shape")]
+    fn test_from_vec_checks_length() {
+        GridN::from_vec([2, 2], vec![0; 3]);
+    }
+}
@@ -0,0 +1,361 @@
+use crate::{DistanceField, Error, Grid, Obstacles};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+/// The unit of the distances an [`InflationCostmap`] is computed from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DistanceUnit {
+    /// Distances are measured in cells and scaled by the resolution of the field's
+    /// [`GridGeometry`](crate::GridGeometry), as for solvers with the default step size.
+    #[default]
+    Cells,
+    /// Distances are already in world units, e.g. because the solver was configured with the
+    /// map resolution as its step size or spacing.
+    World,
+}
+
+/// Configuration for deriving a ROS `costmap_2d` inflation layer from a distance field.
+///
+/// Distances are converted to world units according to the [`DistanceUnit`]. Obstacle cells
+/// are lethal, cells within the inscribed radius are inscribed, and costs decay exponentially
+/// from there up to the inflation radius:
+///
+/// `cost = 252 * exp(-cost_scaling_factor * (distance - inscribed_radius))`
+#[derive(Debug, Clone, Copy)]
+pub struct InflationCostmap {
+    inscribed_radius: f64,
+    inflation_radius: f64,
+    cost_scaling_factor: f64,
+    distance_unit: DistanceUnit,
+}
+
+impl InflationCostmap {
+    /// The cost of unknown cells.
+    pub const NO_INFORMATION: u8 = 255;
+    /// The cost of obstacle cells.
+    pub const LETHAL_OBSTACLE: u8 = 254;
+    /// The cost of cells within the inscribed radius of an obstacle.
+    pub const INSCRIBED_INFLATED_OBSTACLE: u8 = 253;
+    /// The cost of cells beyond the inflation radius.
+    pub const FREE_SPACE: u8 = 0;
+
+    /// Sets the radius of the circle inscribed in the robot footprint, in world units.
+    #[must_use]
+    pub const fn with_inscribed_radius(mut self, inscribed_radius: f64) -> Self {
+        self.inscribed_radius = inscribed_radius;
+        self
+    }
+
+    /// Sets the distance up to which costs are inflated, in world units.
+    #[must_use]
+    pub const fn with_inflation_radius(mut self, inflation_radius: f64) -> Self {
+        self.inflation_radius = inflation_radius;
+        self
+    }
+
+    /// Sets the rate at which costs decay beyond the inscribed radius.
+    #[must_use]
+    pub const fn with_cost_scaling_factor(mut self, cost_scaling_factor: f64) -> Self {
+        self.cost_scaling_factor = cost_scaling_factor;
+        self
+    }
+
+    /// Sets the unit of the distances in the distance field.
+    #[must_use]
+    pub const fn with_distance_unit(mut self, distance_unit: DistanceUnit) -> Self {
+        self.distance_unit = distance_unit;
+        self
+    }
+
+    /// Returns the cost of a cell at the given distance from the closest obstacle, in world
+    /// units.
+    pub fn cost(&self, distance: f64) -> u8 {
+        if distance <= 0.0 {
+            Self::LETHAL_OBSTACLE
+        } else if distance <= self.inscribed_radius {
+            Self::INSCRIBED_INFLATED_OBSTACLE
+        } else if distance <= self.inflation_radius {
+            let factor = (-self.cost_scaling_factor * (distance - self.inscribed_radius)).exp();
+            ((Self::INSCRIBED_INFLATED_OBSTACLE - 1) as f64 * factor) as u8
+        } else {
+            Self::FREE_SPACE
+        }
+    }
+
+    /// Returns the row-major costs of all cells. Cells set in `unknown` have no information.
+    ///
+    /// Returns an error if `unknown` does not match the dimensions of the distance field.
+    pub fn costs(
+        &self,
+        distance_field: &DistanceField,
+        unknown: Option<&Obstacles>,
+    ) -> Result<Vec<u8>, Error> {
+        let width = distance_field.width();
+        if let Some(unknown) = unknown {
+            Error::check_dimensions(
+                (width, distance_field.height()),
+                (unknown.width(), unknown.height()),
+            )?;
+        }
+        let scale = match self.distance_unit {
+            DistanceUnit::Cells => distance_field.geometry().map_or(1.0, |g| g.resolution()),
+            DistanceUnit::World => 1.0,
+        };
+        let costs = distance_field
+            .iter()
+            .enumerate()
+            .map(|(i, &distance)| {
+                if unknown.is_some_and(|u| *u.get_at(i % width, i / width)) {
+                    Self::NO_INFORMATION
+                } else {
+                    self.cost(distance as f64 * scale)
+                }
+            })
+            .collect();
+        Ok(costs)
+    }
+
+    /// Saves the costmap as a ROS `map_server` YAML file and a PGM image next to it.
+    ///
+    /// The image is written in `raw` mode with the `costmap_2d` translation of costs to
+    /// occupancy values, so that `map_server` publishes the same grid as the costmap.
+    /// Rows are flipped such that cell `(0, 0)` is the lower left pixel.
+    ///
+    /// Fails with [`InvalidInput`](std::io::ErrorKind::InvalidInput) if `yaml_path` does not
+    /// name a file or `unknown` does not match the dimensions of the distance field.
+    pub fn save_yaml<P: AsRef<Path>>(
+        &self,
+        distance_field: &DistanceField,
+        unknown: Option<&Obstacles>,
+        yaml_path: P,
+    ) -> std::io::Result<()> {
+        let invalid_input =
+            |message: String| std::io::Error::new(std::io::ErrorKind::InvalidInput, message);
+        let yaml_path = yaml_path.as_ref();
+        let Some(yaml_name) = yaml_path.file_name() else {
+            return Err(invalid_input(format!(
+                "{} does not name a file",
+                yaml_path.display()
+            )));
+        };
+        let image_path = yaml_path.with_extension("pgm");
+        let image_name = Path::new(yaml_name).with_extension("pgm");
+        let image_name = image_name.to_string_lossy();
+        let costs = self
+            .costs(distance_field, unknown)
+            .map_err(|error| invalid_input(error.to_string()))?;
+
+        let geometry = distance_field.geometry().copied().unwrap_or_default();
+        let (x, y) = geometry.origin();
+        let yaml = format!(
+            "image: {image_name}\nmode: raw\nresolution: {}\norigin: [{x}, {y}, {}]\n\
+             negate: 0\noccupied_thresh: 0.65\nfree_thresh: 0.196\n",
+            geometry.resolution(),
+            geometry.rotation()
+        );
+        std::fs::write(yaml_path, yaml)?;
+
+        let (width, height) = (distance_field.width(), distance_field.height());
+        let mut file = BufWriter::new(File::create(&image_path)?);
+        file.write_all(format!("P5\n{width} {height}\n255\n").as_bytes())?;
+        for row in costs.chunks_exact(width.max(1)).rev() {
+            let occupancy: Vec<u8> = row.iter().map(|&cost| occupancy_value(cost)).collect();
+            file.write_all(&occupancy)?;
+        }
+        file.flush()
+    }
+}
+
+/// Translates a cost into an occupancy percentage like the `costmap_2d` publisher, with
+/// unknown cells becoming `255`, i.e. `-1` as a signed byte.
+fn occupancy_value(cost: u8) -> u8 {
+    match cost {
+        InflationCostmap::FREE_SPACE => 0,
+        InflationCostmap::INSCRIBED_INFLATED_OBSTACLE => 99,
+        InflationCostmap::LETHAL_OBSTACLE => 100,
+        InflationCostmap::NO_INFORMATION => 255,
+        cost => (1 + 97 * (cost as u32 - 1) / 251) as u8,
+    }
+}
+
+impl Default for InflationCostmap {
+    fn default() -> Self {
+        Self {
+            inscribed_radius: 0.0,
+            inflation_radius: 0.55,
+            cost_scaling_factor: 10.0,
+            distance_unit: DistanceUnit::Cells,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        DistanceFieldAlgorithm, EuclideanDistanceTransform, GridGeometry, RosMapLoader,
+        UnknownCells,
+    };
+    use std::fs;
+
+    fn create_test_field() -> DistanceField {
+        let mut obstacles = Obstacles::new(20, 10).with_geometry(GridGeometry::new(-1.0, 0.5, 0.1));
+        obstacles.set_at(5, 2, true);
+        let mut df = DistanceField::from(&obstacles);
+        EuclideanDistanceTransform::default().calculate_distance_field(&mut df, &obstacles);
+        df
+    }
+
+    #[test]
+    fn test_default_values() {
+        let costmap = InflationCostmap::default();
+        assert_eq!(costmap.inscribed_radius, 0.0);
+        assert_eq!(costmap.inflation_radius, 0.55);
+        assert_eq!(costmap.cost_scaling_factor, 10.0);
+    }
+
+    #[test]
+    fn test_cost_function() {
+        let costmap = InflationCostmap::default()
+            .with_inscribed_radius(0.2)
+            .with_inflation_radius(1.0)
+            .with_cost_scaling_factor(5.0);
+        assert_eq!(costmap.cost(0.0), InflationCostmap::LETHAL_OBSTACLE);
+        assert_eq!(
+            costmap.cost(0.2),
+            InflationCostmap::INSCRIBED_INFLATED_OBSTACLE
+        );
+        assert_eq!(costmap.cost(0.2001), 251);
+        assert_eq!(costmap.cost(0.6), (252.0 * (-2.0_f64).exp()) as u8);
+        assert_eq!(costmap.cost(1.01), InflationCostmap::FREE_SPACE);
+        assert_eq!(costmap.cost(f64::INFINITY), InflationCostmap::FREE_SPACE);
+    }
+
+    #[test]
+    fn test_costs_use_resolution_and_unknown() {
+        let df = create_test_field();
+        let mut unknown = Obstacles::new(20, 10);
+        unknown.set_at(19, 9, true);
+        let costmap = InflationCostmap::default().with_inscribed_radius(0.15);
+        let costs = costmap.costs(&df, Some(&unknown)).unwrap();
+
+        assert_eq!(costs[2 * 20 + 5], InflationCostmap::LETHAL_OBSTACLE);
+        // One cell away is 0.1 world units, two cells are 0.2.
+        assert_eq!(
+            costs[2 * 20 + 6],
+            InflationCostmap::INSCRIBED_INFLATED_OBSTACLE
+        );
+        assert_eq!(costs[2 * 20 + 7], costmap.cost(0.2));
+        assert_eq!(costs[2 * 20 + 15], InflationCostmap::FREE_SPACE);
+        assert_eq!(costs[9 * 20 + 19], InflationCostmap::NO_INFORMATION);
+    }
+
+    #[test]
+    fn test_costs_in_world_units() {
+        let mut obstacles = Obstacles::new(20, 10).with_geometry(GridGeometry::new(0.0, 0.0, 0.1));
+        obstacles.set_at(5, 2, true);
+        let mut df = DistanceField::from(&obstacles);
+        EuclideanDistanceTransform::default()
+            .with_step_size(0.1)
+            .calculate_distance_field(&mut df, &obstacles);
+
+        let costmap = InflationCostmap::default().with_inscribed_radius(0.15);
+        let costs = costmap
+            .with_distance_unit(DistanceUnit::World)
+            .costs(&df, None)
+            .unwrap();
+        assert_eq!(
+            costs[2 * 20 + 6],
+            InflationCostmap::INSCRIBED_INFLATED_OBSTACLE
+        );
+        assert_eq!(costs[2 * 20 + 7], costmap.cost(*df.get_at(7, 2) as f64));
+        assert_eq!(costs[2 * 20 + 11], InflationCostmap::FREE_SPACE);
+
+        // Treating the distances as cells would scale them twice.
+        let costs = costmap.costs(&df, None).unwrap();
+        assert_eq!(
+            costs[2 * 20 + 11],
+            InflationCostmap::INSCRIBED_INFLATED_OBSTACLE
+        );
+    }
+
+    #[test]
+    fn test_mismatched_unknown_is_rejected() {
+        let df = create_test_field();
+        let unknown = Obstacles::new(10, 10);
+        let costmap = InflationCostmap::default();
+        assert_eq!(
+            costmap.costs(&df, Some(&unknown)),
+            Err(Error::DimensionMismatch {
+                expected: (20, 10),
+                actual: (10, 10)
+            })
+        );
+
+        let path = std::env::temp_dir().join("fast_sweeping_test_mismatched_costmap.yaml");
+        let error = costmap.save_yaml(&df, Some(&unknown), &path).unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_save_yaml_rejects_paths_without_file_name() {
+        let df = create_test_field();
+        for path in ["", "..", "/"] {
+            let error = InflationCostmap::default()
+                .save_yaml(&df, None, path)
+                .unwrap_err();
+            assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput, "{path:?}");
+        }
+    }
+
+    #[test]
+    fn test_occupancy_value() {
+        assert_eq!(occupancy_value(0), 0);
+        assert_eq!(occupancy_value(1), 1);
+        assert_eq!(occupancy_value(252), 98);
+        assert_eq!(occupancy_value(253), 99);
+        assert_eq!(occupancy_value(254), 100);
+        assert_eq!(occupancy_value(255), 255);
+    }
+
+    #[test]
+    fn test_save_yaml_roundtrip() {
+        let df = create_test_field();
+        let mut unknown = Obstacles::new(20, 10);
+        unknown.set_at(0, 9, true);
+        let costmap = InflationCostmap::default().with_inscribed_radius(0.25);
+
+        let dir = std::env::temp_dir().join("fast_sweeping_test_costmap");
+        fs::create_dir_all(&dir).unwrap();
+        costmap
+            .save_yaml(&df, Some(&unknown), dir.join("costmap.yaml"))
+            .unwrap();
+        let yaml = fs::read_to_string(dir.join("costmap.yaml")).unwrap();
+        let image = fs::read(dir.join("costmap.pgm")).unwrap();
+        let map = RosMapLoader::default()
+            .with_unknown_cells(UnknownCells::Free)
+            .load(dir.join("costmap.yaml"))
+            .unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(yaml.starts_with("image: costmap.pgm\nmode: raw\nresolution: 0.1\n"));
+        assert!(yaml.contains("origin: [-1, 0.5, 0]\n"));
+        assert_eq!(image.len(), "P5\n20 10\n255\n".len() + 200);
+
+        let obstacles = map.obstacles();
+        assert_eq!(obstacles.geometry(), df.geometry());
+        let costs = costmap.costs(&df, Some(&unknown)).unwrap();
+        for y in 0..10 {
+            for x in 0..20 {
+                let occupancy = occupancy_value(costs[y * 20 + x]);
+                let expected = occupancy > 65 && occupancy <= 100;
+                assert_eq!(*obstacles.get_at(x, y), expected, "({x}, {y})");
+            }
+        }
+        assert!(obstacles.get_at(6, 3));
+        assert!(map.unknown().get_at(0, 9));
+        assert_eq!(map.unknown().iter().filter(|&&u| u).count(), 1);
+    }
+}
@@ -0,0 +1,292 @@
+use crate::labeled_seeds::LabeledSeeds;
+use crate::{
+    DistanceField, Error, FeatureTransformAlgorithm, Grid, GridBounds, GridN,
+    LabeledDistanceFieldAlgorithm, NearestObstacles,
+};
+
+/// A 2D grid holding the label of the closest source for every cell.
+///
+/// Cells that cannot reach any source hold `None`. The regions of equal labels form a
+/// discrete Voronoi partition of the grid.
+#[derive(Debug, Clone)]
+pub struct LabelField {
+    labels: GridN<Option<u32>, 2>,
+}
+
+impl LabelField {
+    /// Creates a new label field with the given dimensions.
+    ///
+    /// All cells are initialized to `None`.
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            labels: GridN::new([width, height], None),
+        }
+    }
+
+    /// Returns the width of the label field.
+    pub const fn width(&self) -> usize {
+        self.labels.shape()[0]
+    }
+
+    /// Returns the height of the label field.
+    pub const fn height(&self) -> usize {
+        self.labels.shape()[1]
+    }
+
+    /// Returns the labels as a generic 2D grid.
+    pub const fn as_grid(&self) -> &GridN<Option<u32>, 2> {
+        &self.labels
+    }
+
+    /// Returns an iterator over the labels.
+    pub fn iter(&self) -> std::slice::Iter<'_, Option<u32>> {
+        self.labels.iter()
+    }
+
+    /// Returns a mutable iterator over the labels.
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, Option<u32>> {
+        self.labels.iter_mut()
+    }
+
+    /// Returns the cells on the boundary between Voronoi regions.
+    ///
+    /// A cell is on a boundary if any of its four direct neighbors carries a different label.
+    /// Both cells of such a pair are reported, in row-major order.
+    pub fn voronoi_boundaries(&self) -> Vec<(usize, usize)> {
+        let mut boundaries = Vec::new();
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                let label = self.get_at(x, y);
+                let differs = (x > 0 && self.get_at(x - 1, y) != label)
+                    || (x + 1 < self.width() && self.get_at(x + 1, y) != label)
+                    || (y > 0 && self.get_at(x, y - 1) != label)
+                    || (y + 1 < self.height() && self.get_at(x, y + 1) != label);
+                if differs {
+                    boundaries.push((x, y));
+                }
+            }
+        }
+        boundaries
+    }
+}
+
+impl From<&LabeledSeeds> for LabelField {
+    fn from(value: &LabeledSeeds) -> Self {
+        Self::new(value.width(), value.height())
+    }
+}
+
+impl From<GridN<Option<u32>, 2>> for LabelField {
+    fn from(labels: GridN<Option<u32>, 2>) -> Self {
+        Self { labels }
+    }
+}
+
+impl Grid for LabelField {
+    type Item = Option<u32>;
+
+    fn get_at(&self, x: usize, y: usize) -> &Self::Item {
+        &self.labels[[x, y]]
+    }
+
+    fn set_at(&mut self, x: usize, y: usize, value: Self::Item) {
+        self.labels[[x, y]] = value
+    }
+}
+
+impl GridBounds for LabelField {
+    fn width(&self) -> usize {
+        self.width()
+    }
+
+    fn height(&self) -> usize {
+        self.height()
+    }
+}
+
+impl<A: FeatureTransformAlgorithm> LabeledDistanceFieldAlgorithm for A {
+    fn calculate_labeled_distance_field(
+        &self,
+        distance_field: &mut DistanceField,
+        labels: &mut LabelField,
+        seeds: &LabeledSeeds,
+    ) {
+        if let Err(error) = check_dimensions(distance_field, labels, seeds) {
+            panic!("{error}");
+        }
+        let obstacles = seeds.to_obstacles();
+        let mut nearest = NearestObstacles::from(&obstacles);
+        self.calculate_feature_transform(distance_field, &mut nearest, &obstacles);
+
+        for (label, source) in labels.iter_mut().zip(nearest.iter()) {
+            *label = source.and_then(|(x, y)| *seeds.get_at(x, y));
+        }
+    }
+}
+
+/// Returns an error if the distance field or the labels differ in size from the seeds.
+pub(crate) fn check_dimensions(
+    distance_field: &DistanceField,
+    labels: &LabelField,
+    seeds: &LabeledSeeds,
+) -> Result<(), Error> {
+    let dimensions = (seeds.width(), seeds.height());
+    Error::check_dimensions(
+        dimensions,
+        (distance_field.width(), distance_field.height()),
+    )?;
+    Error::check_dimensions(dimensions, (labels.width(), labels.height()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EikonalFastSweepingMethod, EuclideanDistanceTransform, NaiveFastSweepingMethod};
+
+    fn two_rooms() -> LabeledSeeds {
+        let mut seeds = LabeledSeeds::new(12, 6);
+        seeds.set_at(1, 2, Some(10));
+        seeds.set_at(10, 3, Some(20));
+        seeds
+    }
+
+    #[test]
+    fn test_new_dimensions() {
+        let labels = LabelField::new(10, 20);
+        assert_eq!(labels.width(), 10);
+        assert_eq!(labels.height(), 20);
+        assert!(labels.iter().all(Option::is_none));
+    }
+
+    #[test]
+    fn test_grid_get_set_roundtrip() {
+        let mut labels = LabelField::new(5, 5);
+        labels.set_at(2, 3, Some(1));
+        assert_eq!(*labels.get_at(2, 3), Some(1));
+        assert_eq!(labels.labels.as_slice()[3 * 5 + 2], Some(1));
+    }
+
+    #[test]
+    fn test_voronoi_boundaries() {
+        let mut labels = LabelField::new(4, 2);
+        for y in 0..2 {
+            labels.set_at(0, y, Some(1));
+            labels.set_at(1, y, Some(1));
+            labels.set_at(2, y, Some(2));
+            labels.set_at(3, y, Some(2));
+        }
+        assert_eq!(
+            labels.voronoi_boundaries(),
+            vec![(1, 0), (2, 0), (1, 1), (2, 1)]
+        );
+    }
+
+    #[test]
+    fn test_voronoi_boundaries_uniform() {
+        let mut labels = LabelField::new(3, 3);
+        for label in labels.iter_mut() {
+            *label = Some(4);
+        }
+        assert!(labels.voronoi_boundaries().is_empty());
+    }
+
+    #[test]
+    fn test_labels_follow_closest_source() {
+        let seeds = two_rooms();
+        let mut df = DistanceField::new(seeds.width(), seeds.height());
+        let mut labels = LabelField::from(&seeds);
+        EuclideanDistanceTransform::default().calculate_labeled_distance_field(
+            &mut df,
+            &mut labels,
+            &seeds,
+        );
+
+        for y in 0..seeds.height() {
+            for x in 0..seeds.width() {
+                let d1 = ((x as f32 - 1.0).powi(2) + (y as f32 - 2.0).powi(2)).sqrt();
+                let d2 = ((x as f32 - 10.0).powi(2) + (y as f32 - 3.0).powi(2)).sqrt();
+                assert_eq!(*df.get_at(x, y), d1.min(d2));
+                if d1 < d2 {
+                    assert_eq!(*labels.get_at(x, y), Some(10));
+                } else if d2 < d1 {
+                    assert_eq!(*labels.get_at(x, y), Some(20));
+                }
+            }
+        }
+    }
+
+    fn labels_with<A: LabeledDistanceFieldAlgorithm>(algo: A, seeds: &LabeledSeeds) -> LabelField {
+        let mut df = DistanceField::new(seeds.width(), seeds.height());
+        let mut labels = LabelField::from(seeds);
+        algo.calculate_labeled_distance_field(&mut df, &mut labels, seeds);
+        labels
+    }
+
+    #[test]
+    fn test_sweeping_solvers_produce_labels() {
+        let seeds = two_rooms();
+        for labels in [
+            labels_with(NaiveFastSweepingMethod::default(), &seeds),
+            labels_with(EikonalFastSweepingMethod::default(), &seeds),
+        ] {
+            assert_eq!(*labels.get_at(0, 0), Some(10));
+            assert_eq!(*labels.get_at(11, 5), Some(20));
+            assert!(labels.iter().all(Option::is_some));
+
+            let boundaries = labels.voronoi_boundaries();
+            assert!(!boundaries.is_empty());
+            for (x, _) in boundaries {
+                assert!((4..=7).contains(&x), "Boundary at x = {x} is off-center");
+            }
+        }
+    }
+
+    #[test]
+    fn test_no_seeds_yields_no_labels() {
+        let seeds = LabeledSeeds::new(5, 5);
+        let mut df = DistanceField::new(5, 5);
+        let mut labels = LabelField::from(&seeds);
+        EuclideanDistanceTransform::default().calculate_labeled_distance_field(
+            &mut df,
+            &mut labels,
+            &seeds,
+        );
+        assert!(labels.iter().all(Option::is_none));
+        assert!(labels.voronoi_boundaries().is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "do not match")]
+    fn test_mismatched_labels_panic() {
+        let seeds = two_rooms();
+        let mut df = DistanceField::new(seeds.width(), seeds.height());
+        let mut labels = LabelField::new(seeds.width(), seeds.height() - 1);
+        EikonalFastSweepingMethod::default().calculate_labeled_distance_field(
+            &mut df,
+            &mut labels,
+            &seeds,
+        );
+    }
+
+    #[test]
+    fn test_try_checks_dimensions() {
+        let seeds = two_rooms();
+        let algo = EuclideanDistanceTransform::default();
+        let mut labels = LabelField::from(&seeds);
+        let mut small = DistanceField::new(4, 4);
+        assert_eq!(
+            algo.try_calculate_labeled_distance_field(&mut small, &mut labels, &seeds),
+            Err(Error::DimensionMismatch {
+                expected: (12, 6),
+                actual: (4, 4)
+            })
+        );
+
+        let mut df = DistanceField::new(seeds.width(), seeds.height());
+        assert_eq!(
+            algo.try_calculate_labeled_distance_field(&mut df, &mut labels, &seeds),
+            Ok(())
+        );
+        assert_eq!(*labels.get_at(0, 0), Some(10));
+    }
+}
@@ -0,0 +1,134 @@
+use crate::obstacles::Obstacles;
+use crate::{Grid, GridBounds, GridN};
+
+/// A 2D grid of labelled distance sources.
+///
+/// This generalizes [`Obstacles`]: every cell either is not a source (`None`) or is a source
+/// carrying a `u32` label, such as the room or robot it belongs to.
+#[derive(Debug, Clone)]
+pub struct LabeledSeeds {
+    labels: GridN<Option<u32>, 2>,
+}
+
+impl LabeledSeeds {
+    /// Creates a new seed grid with the given dimensions.
+    ///
+    /// All cells are initially unlabelled (no sources).
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            labels: GridN::new([width, height], None),
+        }
+    }
+
+    /// Returns the width of the seed grid.
+    pub const fn width(&self) -> usize {
+        self.labels.shape()[0]
+    }
+
+    /// Returns the height of the seed grid.
+    pub const fn height(&self) -> usize {
+        self.labels.shape()[1]
+    }
+
+    /// Returns the seed labels as a generic 2D grid.
+    pub const fn as_grid(&self) -> &GridN<Option<u32>, 2> {
+        &self.labels
+    }
+
+    /// Returns an iterator over the seed labels.
+    pub fn iter(&self) -> std::slice::Iter<'_, Option<u32>> {
+        self.labels.iter()
+    }
+
+    /// Returns a mutable iterator over the seed labels.
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, Option<u32>> {
+        self.labels.iter_mut()
+    }
+
+    /// Returns an obstacle grid with an obstacle at every labelled cell.
+    pub fn to_obstacles(&self) -> Obstacles {
+        let mut obstacles = Obstacles::new(self.width(), self.height());
+        for (is_obstacle, label) in obstacles.iter_mut().zip(self.iter()) {
+            *is_obstacle = label.is_some();
+        }
+        obstacles
+    }
+}
+
+impl From<&Obstacles> for LabeledSeeds {
+    /// Labels every obstacle cell with `0`.
+    fn from(value: &Obstacles) -> Self {
+        Self {
+            labels: value.as_grid().map(|&b| b.then_some(0)),
+        }
+    }
+}
+
+impl From<GridN<Option<u32>, 2>> for LabeledSeeds {
+    fn from(labels: GridN<Option<u32>, 2>) -> Self {
+        Self { labels }
+    }
+}
+
+impl Grid for LabeledSeeds {
+    type Item = Option<u32>;
+
+    fn get_at(&self, x: usize, y: usize) -> &Self::Item {
+        &self.labels[[x, y]]
+    }
+
+    fn set_at(&mut self, x: usize, y: usize, value: Self::Item) {
+        self.labels[[x, y]] = value
+    }
+}
+
+impl GridBounds for LabeledSeeds {
+    fn width(&self) -> usize {
+        self.width()
+    }
+
+    fn height(&self) -> usize {
+        self.height()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_dimensions() {
+        let seeds = LabeledSeeds::new(10, 20);
+        assert_eq!(seeds.width(), 10);
+        assert_eq!(seeds.height(), 20);
+        assert!(seeds.iter().all(Option::is_none));
+    }
+
+    #[test]
+    fn test_grid_get_set_roundtrip() {
+        let mut seeds = LabeledSeeds::new(5, 5);
+        seeds.set_at(2, 3, Some(7));
+        assert_eq!(*seeds.get_at(2, 3), Some(7));
+        assert_eq!(seeds.labels.as_slice()[3 * 5 + 2], Some(7));
+    }
+
+    #[test]
+    fn test_from_obstacles() {
+        let mut obs = Obstacles::new(4, 3);
+        obs.set_at(1, 2, true);
+        let seeds = LabeledSeeds::from(&obs);
+        assert_eq!(seeds.width(), 4);
+        assert_eq!(seeds.height(), 3);
+        assert_eq!(*seeds.get_at(1, 2), Some(0));
+        assert_eq!(*seeds.get_at(0, 0), None);
+    }
+
+    #[test]
+    fn test_to_obstacles() {
+        let mut seeds = LabeledSeeds::new(4, 3);
+        seeds.set_at(3, 0, Some(5));
+        let obs = seeds.to_obstacles();
+        assert!(obs.get_at(3, 0));
+        assert_eq!(obs.iter().filter(|&&b| b).count(), 1);
+    }
+}
@@ -1,16 +1,209 @@
+mod cost_field;
+mod cutoff;
 mod distance_field;
+mod distance_field1;
+mod distance_field3;
+mod drawing;
+mod dynamic_distance_map;
+mod eikonal_sweeping;
+mod error;
+mod euclidean_distance_transform;
+mod fast_marching;
 mod fast_sweeping;
+mod gradient_field;
+mod grid_geometry;
+mod grid_n;
+mod inflation_costmap;
+mod label_field;
+mod labeled_seeds;
+mod load_error;
+mod nearest_obstacles;
+mod npy;
 mod obstacles;
+mod obstacles1;
+mod obstacles3;
+#[cfg(feature = "rayon")]
+mod parallel_sweeping;
+mod path_extraction;
+mod pfm;
+mod pgm_loader;
+mod pnm;
+mod ros_map;
+mod sampling;
+mod signed_distance;
+mod solve_report;
+mod vector_geometry;
+mod visualization;
 
 use std::path::Path;
 
+pub use crate::cost_field::CostField;
+pub use crate::cutoff::Cutoff;
 pub use crate::distance_field::DistanceField;
+pub use crate::distance_field1::DistanceField1;
+pub use crate::distance_field3::DistanceField3;
+pub use crate::drawing::FillRule;
+pub use crate::dynamic_distance_map::DynamicDistanceMap;
+pub use crate::eikonal_sweeping::{EikonalFastSweepingMethod, EikonalFastSweepingMethodN};
+pub use crate::error::Error;
+pub use crate::euclidean_distance_transform::EuclideanDistanceTransform;
+pub use crate::fast_marching::FastMarchingMethod;
 pub use crate::fast_sweeping::NaiveFastSweepingMethod;
+pub use crate::gradient_field::GradientField;
+pub use crate::grid_geometry::GridGeometry;
+pub use crate::grid_n::{DistanceFieldN, GridN, ObstaclesN};
+pub use crate::inflation_costmap::{DistanceUnit, InflationCostmap};
+pub use crate::label_field::LabelField;
+pub use crate::labeled_seeds::LabeledSeeds;
+pub use crate::load_error::LoadError;
+pub use crate::nearest_obstacles::NearestObstacles;
 pub use crate::obstacles::Obstacles;
+pub use crate::obstacles1::Obstacles1;
+pub use crate::obstacles3::Obstacles3;
+#[cfg(feature = "rayon")]
+pub use crate::parallel_sweeping::ParallelFastSweepingMethod;
+pub use crate::path_extraction::GradientDescent;
+pub use crate::pgm_loader::{PgmLoader, Polarity};
+pub use crate::ros_map::{RosMap, RosMapLoader, UnknownCells};
+pub use crate::sampling::{Interpolation, OutOfBounds, Sample, Sampler};
+pub use crate::signed_distance::SignedDistance;
+pub use crate::solve_report::SolveReport;
+pub use crate::vector_geometry::VectorGeometry;
+pub use crate::visualization::{Colormap, Transfer, Visualization};
 
 /// Trait for distance field calculation algorithms.
 pub trait DistanceFieldAlgorithm {
     fn calculate_distance_field(&self, distance_field: &mut DistanceField, obstacles: &Obstacles);
+
+    /// Calculates the distance field after checking that both grids have the same dimensions.
+    fn try_calculate_distance_field(
+        &self,
+        distance_field: &mut DistanceField,
+        obstacles: &Obstacles,
+    ) -> Result<(), Error> {
+        Error::check_dimensions(
+            (obstacles.width(), obstacles.height()),
+            (distance_field.width(), distance_field.height()),
+        )?;
+        self.calculate_distance_field(distance_field, obstacles);
+        Ok(())
+    }
+
+    /// Calculates the distance field of a single line of cells.
+    fn calculate_distance_field1(
+        &self,
+        distance_field: &mut DistanceField1,
+        obstacles: &Obstacles1,
+    ) {
+        self.calculate_distance_field(distance_field.as_mut(), obstacles.as_ref());
+    }
+}
+
+/// Trait for algorithms that also compute the nearest obstacle of every cell.
+///
+/// The resulting [`NearestObstacles`] grid is the feature transform of the obstacles and is
+/// computed alongside, and consistent with, the distances written into the [`DistanceField`].
+///
+/// Implementations panic if the distance field or the nearest obstacles do not match the
+/// dimensions of the obstacles.
+pub trait FeatureTransformAlgorithm {
+    fn calculate_feature_transform(
+        &self,
+        distance_field: &mut DistanceField,
+        nearest: &mut NearestObstacles,
+        obstacles: &Obstacles,
+    );
+
+    /// Calculates the feature transform after checking that all grids have the same dimensions.
+    fn try_calculate_feature_transform(
+        &self,
+        distance_field: &mut DistanceField,
+        nearest: &mut NearestObstacles,
+        obstacles: &Obstacles,
+    ) -> Result<(), Error> {
+        nearest_obstacles::check_dimensions(distance_field, nearest, obstacles)?;
+        self.calculate_feature_transform(distance_field, nearest, obstacles);
+        Ok(())
+    }
+}
+
+/// Trait for distance field calculation from several labelled sources.
+///
+/// Every cell of the [`LabelField`] receives the label of its closest source in
+/// [`LabeledSeeds`]. This is implemented for all [`FeatureTransformAlgorithm`]s, which panic
+/// if the distance field or the labels do not match the dimensions of the seeds.
+pub trait LabeledDistanceFieldAlgorithm {
+    fn calculate_labeled_distance_field(
+        &self,
+        distance_field: &mut DistanceField,
+        labels: &mut LabelField,
+        seeds: &LabeledSeeds,
+    );
+
+    /// Calculates the labeled distance field after checking that all grids have the same
+    /// dimensions.
+    fn try_calculate_labeled_distance_field(
+        &self,
+        distance_field: &mut DistanceField,
+        labels: &mut LabelField,
+        seeds: &LabeledSeeds,
+    ) -> Result<(), Error> {
+        label_field::check_dimensions(distance_field, labels, seeds)?;
+        self.calculate_labeled_distance_field(distance_field, labels, seeds);
+        Ok(())
+    }
+}
+
+/// Trait for volumetric distance field calculation algorithms.
+pub trait DistanceFieldAlgorithm3 {
+    fn calculate_distance_field3(
+        &self,
+        distance_field: &mut DistanceField3,
+        obstacles: &Obstacles3,
+    );
+}
+
+/// Trait for distance field calculation algorithms on grids with `D` axes.
+///
+/// Implementations panic if the obstacles do not match the shape of the distance field.
+pub trait DistanceFieldAlgorithmN<const D: usize> {
+    fn calculate_distance_field_n(
+        &self,
+        distance_field: &mut DistanceFieldN<D>,
+        obstacles: &ObstaclesN<D>,
+    );
+}
+
+/// Trait for travel time calculation algorithms.
+///
+/// Solves the Eikonal equation `|∇T| = 1 / F(x)`, where the per-cell traversal cost
+/// `1 / F(x)` is given by a [`CostField`].
+///
+/// Implementations panic if the cost field does not match the dimensions of the obstacles and
+/// the travel time grid.
+pub trait TravelTimeAlgorithm {
+    fn calculate_travel_time(
+        &self,
+        travel_time: &mut DistanceField,
+        obstacles: &Obstacles,
+        costs: &CostField,
+    );
+
+    /// Calculates the travel time after checking that all grids have the same dimensions and
+    /// that every cost is greater than zero.
+    fn try_calculate_travel_time(
+        &self,
+        travel_time: &mut DistanceField,
+        obstacles: &Obstacles,
+        costs: &CostField,
+    ) -> Result<(), Error> {
+        let dimensions = (obstacles.width(), obstacles.height());
+        Error::check_dimensions(dimensions, (travel_time.width(), travel_time.height()))?;
+        Error::check_dimensions(dimensions, (costs.width(), costs.height()))?;
+        costs.check_costs()?;
+        self.calculate_travel_time(travel_time, obstacles, costs);
+        Ok(())
+    }
 }
 
 /// Trait abstracting grid access for distance field and obstacle data.
@@ -21,11 +214,60 @@ pub trait Grid {
     fn set_at(&mut self, x: usize, y: usize, value: Self::Item);
 }
 
+/// Extension of [`Grid`] with the grid dimensions and bounds-checked cell access.
+///
+/// This is implemented for all 2D grids of this crate.
+pub trait GridBounds: Grid {
+    fn width(&self) -> usize;
+    fn height(&self) -> usize;
+
+    /// Returns the cell at `(x, y)`, or an error if it lies outside of the grid.
+    fn try_get_at(&self, x: usize, y: usize) -> Result<&Self::Item, Error> {
+        self.check_bounds(x, y)?;
+        Ok(self.get_at(x, y))
+    }
+
+    /// Sets the cell at `(x, y)`, or returns an error if it lies outside of the grid.
+    fn try_set_at(&mut self, x: usize, y: usize, value: Self::Item) -> Result<(), Error> {
+        self.check_bounds(x, y)?;
+        self.set_at(x, y, value);
+        Ok(())
+    }
+
+    /// Returns an error if `(x, y)` lies outside of the grid.
+    fn check_bounds(&self, x: usize, y: usize) -> Result<(), Error> {
+        let (width, height) = (self.width(), self.height());
+        if x < width && y < height {
+            Ok(())
+        } else {
+            Err(Error::OutOfBounds {
+                x,
+                y,
+                width,
+                height,
+            })
+        }
+    }
+}
+
+/// Trait abstracting grid access for volumetric distance field and obstacle data.
+pub trait Grid3 {
+    type Item;
+
+    fn get_at(&self, x: usize, y: usize, z: usize) -> &Self::Item;
+    fn set_at(&mut self, x: usize, y: usize, z: usize, value: Self::Item);
+}
+
 /// Trait for saving grid data as PGM image files.
 pub trait SavePgm {
     fn save_pgm<P: AsRef<Path>>(&self, path: P) -> std::io::Result<()>;
 }
 
+/// Trait for loading grid data from PGM and related netpbm image files.
+pub trait LoadPgm: Sized {
+    fn load_pgm<P: AsRef<Path>>(path: P) -> Result<Self, LoadError>;
+}
+
 fn min3(a: f32, b: f32, c: f32) -> f32 {
     a.min(b).min(c)
 }
@@ -53,4 +295,98 @@ mod tests {
     fn test_min3_infinity() {
         assert_eq!(min3(f32::INFINITY, 1.0, 2.0), 1.0);
     }
+
+    #[test]
+    fn test_try_get_and_set_at() {
+        let mut obstacles = Obstacles::new(4, 3);
+        assert_eq!(obstacles.try_set_at(3, 2, true), Ok(()));
+        assert_eq!(obstacles.try_get_at(3, 2), Ok(&true));
+
+        // Out-of-row coordinates are rejected even if they fall within the storage.
+        let expected = Err(Error::OutOfBounds {
+            x: 4,
+            y: 0,
+            width: 4,
+            height: 3,
+        });
+        assert_eq!(obstacles.try_get_at(4, 0), expected);
+        assert_eq!(obstacles.try_set_at(4, 0, true), expected.map(|_| ()));
+        assert!(obstacles.try_get_at(0, 3).is_err());
+        assert!(!*obstacles.get_at(0, 1));
+    }
+
+    #[test]
+    fn test_grid_needs_only_cell_access() {
+        struct Single(u8);
+
+        impl Grid for Single {
+            type Item = u8;
+
+            fn get_at(&self, _x: usize, _y: usize) -> &u8 {
+                &self.0
+            }
+
+            fn set_at(&mut self, _x: usize, _y: usize, value: u8) {
+                self.0 = value;
+            }
+        }
+
+        let mut single = Single(1);
+        single.set_at(0, 0, 2);
+        assert_eq!(*single.get_at(0, 0), 2);
+    }
+
+    #[test]
+    fn test_try_calculate_distance_field_checks_dimensions() {
+        let mut obstacles = Obstacles::new(4, 3);
+        obstacles.set_at(0, 0, true);
+        let algo = NaiveFastSweepingMethod::default();
+
+        let mut mismatched = DistanceField::from(&Obstacles::new(3, 4));
+        assert_eq!(
+            algo.try_calculate_distance_field(&mut mismatched, &obstacles),
+            Err(Error::DimensionMismatch {
+                expected: (4, 3),
+                actual: (3, 4)
+            })
+        );
+        assert!(mismatched.iter().all(|d| d.is_infinite()));
+
+        let mut df = DistanceField::from(&obstacles);
+        assert_eq!(
+            algo.try_calculate_distance_field(&mut df, &obstacles),
+            Ok(())
+        );
+        assert_eq!(*df.get_at(3, 2), 5.0);
+    }
+
+    #[test]
+    fn test_try_calculate_feature_transform_checks_dimensions() {
+        let mut obstacles = Obstacles::new(4, 3);
+        obstacles.set_at(0, 0, true);
+        let algo = EikonalFastSweepingMethod::default();
+
+        let mut df = DistanceField::from(&obstacles);
+        let mut mismatched = NearestObstacles::new(4, 2);
+        assert_eq!(
+            algo.try_calculate_feature_transform(&mut df, &mut mismatched, &obstacles),
+            Err(Error::DimensionMismatch {
+                expected: (4, 3),
+                actual: (4, 2)
+            })
+        );
+        assert!(mismatched.iter().all(Option::is_none));
+
+        let mut small = DistanceField::new(3, 3);
+        let mut nearest = NearestObstacles::from(&obstacles);
+        assert!(algo
+            .try_calculate_feature_transform(&mut small, &mut nearest, &obstacles)
+            .is_err());
+
+        assert_eq!(
+            algo.try_calculate_feature_transform(&mut df, &mut nearest, &obstacles),
+            Ok(())
+        );
+        assert_eq!(*nearest.get_at(3, 2), Some((0, 0)));
+    }
 }
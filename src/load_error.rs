@@ -0,0 +1,69 @@
+use std::fmt;
+use std::io;
+
+/// Errors reported when reading grids from files.
+#[derive(Debug)]
+pub enum LoadError {
+    /// The file could not be read.
+    Io(io::Error),
+    /// The file is not in a supported format, e.g. an unknown magic number.
+    UnsupportedFormat(String),
+    /// The header or metadata of the file is malformed.
+    InvalidHeader(String),
+    /// The file ended before all cells were read.
+    UnexpectedEof,
+    /// A cell value is malformed or exceeds the range given in the header.
+    InvalidData(String),
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "failed to read file: {error}"),
+            Self::UnsupportedFormat(message) => write!(f, "unsupported format: {message}"),
+            Self::InvalidHeader(message) => write!(f, "invalid header: {message}"),
+            Self::UnexpectedEof => write!(f, "unexpected end of file"),
+            Self::InvalidData(message) => write!(f, "invalid data: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for LoadError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for LoadError {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            LoadError::InvalidHeader("missing width".into()).to_string(),
+            "invalid header: missing width"
+        );
+        assert_eq!(
+            LoadError::UnexpectedEof.to_string(),
+            "unexpected end of file"
+        );
+    }
+
+    #[test]
+    fn test_io_source() {
+        let error = LoadError::from(io::Error::new(io::ErrorKind::NotFound, "gone"));
+        assert!(error.source().is_some());
+        assert!(LoadError::UnexpectedEof.source().is_none());
+    }
+}
@@ -0,0 +1,149 @@
+use crate::obstacles::Obstacles;
+use crate::{DistanceField, Error, Grid, GridBounds, GridN};
+
+/// A 2D grid holding the coordinates of the nearest obstacle cell for every cell.
+///
+/// This is the feature transform companion of a [`DistanceField`](crate::DistanceField).
+/// Obstacle cells refer to themselves, and cells that cannot reach any obstacle hold `None`.
+#[derive(Debug, Clone)]
+pub struct NearestObstacles {
+    nearest: GridN<Option<(usize, usize)>, 2>,
+}
+
+impl NearestObstacles {
+    /// Creates a new feature grid with the given dimensions.
+    ///
+    /// All cells are initialized to `None`.
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            nearest: GridN::new([width, height], None),
+        }
+    }
+
+    /// Returns the width of the feature grid.
+    pub const fn width(&self) -> usize {
+        self.nearest.shape()[0]
+    }
+
+    /// Returns the height of the feature grid.
+    pub const fn height(&self) -> usize {
+        self.nearest.shape()[1]
+    }
+
+    /// Returns the nearest obstacles as a generic 2D grid.
+    pub const fn as_grid(&self) -> &GridN<Option<(usize, usize)>, 2> {
+        &self.nearest
+    }
+
+    /// Returns an iterator over the nearest obstacle coordinates.
+    pub fn iter(&self) -> std::slice::Iter<'_, Option<(usize, usize)>> {
+        self.nearest.iter()
+    }
+
+    /// Returns a mutable iterator over the nearest obstacle coordinates.
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, Option<(usize, usize)>> {
+        self.nearest.iter_mut()
+    }
+
+    /// Resets the grid such that obstacle cells refer to themselves and all others are `None`.
+    pub(crate) fn initialize(&mut self, obstacles: &Obstacles) {
+        let width = self.width();
+        for (index, (nearest, &is_obstacle)) in
+            self.nearest.iter_mut().zip(obstacles.iter()).enumerate()
+        {
+            *nearest = is_obstacle.then_some((index % width, index / width));
+        }
+    }
+
+    /// Copies the nearest obstacle of the `from` cell to the `to` cell.
+    pub(crate) fn propagate(&mut self, from: (usize, usize), to: (usize, usize)) {
+        self.nearest[[to.0, to.1]] = self.nearest[[from.0, from.1]];
+    }
+
+    /// Returns the feature values as a mutable row-major slice.
+    pub(crate) fn as_mut_slice(&mut self) -> &mut [Option<(usize, usize)>] {
+        self.nearest.as_mut_slice()
+    }
+}
+
+impl From<&Obstacles> for NearestObstacles {
+    fn from(value: &Obstacles) -> Self {
+        Self::new(value.width(), value.height())
+    }
+}
+
+impl From<GridN<Option<(usize, usize)>, 2>> for NearestObstacles {
+    fn from(nearest: GridN<Option<(usize, usize)>, 2>) -> Self {
+        Self { nearest }
+    }
+}
+
+impl Grid for NearestObstacles {
+    type Item = Option<(usize, usize)>;
+
+    fn get_at(&self, x: usize, y: usize) -> &Self::Item {
+        &self.nearest[[x, y]]
+    }
+
+    fn set_at(&mut self, x: usize, y: usize, value: Self::Item) {
+        self.nearest[[x, y]] = value
+    }
+}
+
+impl GridBounds for NearestObstacles {
+    fn width(&self) -> usize {
+        self.width()
+    }
+
+    fn height(&self) -> usize {
+        self.height()
+    }
+}
+
+/// Returns an error if the distance field or the feature grid differ in size from the
+/// obstacles.
+pub(crate) fn check_dimensions(
+    distance_field: &DistanceField,
+    nearest: &NearestObstacles,
+    obstacles: &Obstacles,
+) -> Result<(), Error> {
+    let dimensions = (obstacles.width(), obstacles.height());
+    Error::check_dimensions(
+        dimensions,
+        (distance_field.width(), distance_field.height()),
+    )?;
+    Error::check_dimensions(dimensions, (nearest.width(), nearest.height()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_dimensions() {
+        let nearest = NearestObstacles::new(10, 20);
+        assert_eq!(nearest.width(), 10);
+        assert_eq!(nearest.height(), 20);
+        assert_eq!(nearest.iter().count(), 200);
+        assert!(nearest.iter().all(Option::is_none));
+    }
+
+    #[test]
+    fn test_grid_get_set_roundtrip() {
+        let mut nearest = NearestObstacles::new(5, 5);
+        nearest.set_at(2, 3, Some((4, 1)));
+        assert_eq!(*nearest.get_at(2, 3), Some((4, 1)));
+        assert_eq!(nearest.nearest.as_slice()[3 * 5 + 2], Some((4, 1)));
+    }
+
+    #[test]
+    fn test_initialize_from_obstacles() {
+        let mut obstacles = Obstacles::new(4, 3);
+        obstacles.set_at(3, 2, true);
+        let mut nearest = NearestObstacles::from(&obstacles);
+        nearest.set_at(0, 0, Some((1, 1)));
+        nearest.initialize(&obstacles);
+        assert_eq!(*nearest.get_at(3, 2), Some((3, 2)));
+        assert_eq!(*nearest.get_at(0, 0), None);
+    }
+}
@@ -0,0 +1,255 @@
+use crate::LoadError;
+
+const MAGIC: &[u8] = b"\x93NUMPY";
+
+/// Encodes a row-major `(height, width)` array of little-endian 32-bit floats in the NumPy
+/// `.npy` format, version 1.0.
+pub(crate) fn encode(width: usize, height: usize, values: &[f32]) -> Vec<u8> {
+    let mut header =
+        format!("{{'descr': '<f4', 'fortran_order': False, 'shape': ({height}, {width}), }}");
+    // The header is padded with spaces so that the data is 64-byte aligned.
+    let unpadded = MAGIC.len() + 4 + header.len() + 1;
+    header.extend(std::iter::repeat(' ').take(unpadded.next_multiple_of(64) - unpadded));
+    header.push('\n');
+
+    let mut bytes = Vec::with_capacity(MAGIC.len() + 4 + header.len() + values.len() * 4);
+    bytes.extend_from_slice(MAGIC);
+    bytes.extend_from_slice(&[1, 0]);
+    bytes.extend_from_slice(&(header.len() as u16).to_le_bytes());
+    bytes.extend_from_slice(header.as_bytes());
+    for value in values {
+        bytes.extend_from_slice(&value.to_le_bytes());
+    }
+    bytes
+}
+
+/// Decodes a NumPy `.npy` file holding a one- or two-dimensional array of 32- or 64-bit
+/// floats into its width, height and row-major samples.
+///
+/// One-dimensional arrays become a single row. Fortran-ordered arrays are transposed.
+pub(crate) fn decode(bytes: &[u8]) -> Result<(usize, usize, Vec<f32>), LoadError> {
+    if !bytes.starts_with(MAGIC) {
+        return Err(LoadError::UnsupportedFormat(
+            "expected the NumPy magic string".into(),
+        ));
+    }
+    let (header_len, header_start) = match bytes.get(MAGIC.len()..MAGIC.len() + 2) {
+        Some([1, _]) => {
+            let len = bytes.get(8..10).ok_or(LoadError::UnexpectedEof)?;
+            (u16::from_le_bytes([len[0], len[1]]) as usize, 10)
+        }
+        Some([2 | 3, _]) => {
+            let len = bytes.get(8..12).ok_or(LoadError::UnexpectedEof)?;
+            (
+                u32::from_le_bytes([len[0], len[1], len[2], len[3]]) as usize,
+                12,
+            )
+        }
+        Some([major, _]) => {
+            return Err(LoadError::UnsupportedFormat(format!(
+                "NumPy format version {major} is not supported"
+            )))
+        }
+        _ => return Err(LoadError::UnexpectedEof),
+    };
+    let header = bytes
+        .get(header_start..header_start + header_len)
+        .ok_or(LoadError::UnexpectedEof)?;
+    let header = std::str::from_utf8(header)
+        .map_err(|_| LoadError::InvalidHeader("header is not valid UTF-8".into()))?;
+
+    let descr = header_value(header, "descr")?;
+    let (size, little_endian) = match descr.trim_matches(|c| c == '\'' || c == '"') {
+        "<f4" => (4, true),
+        ">f4" => (4, false),
+        "<f8" => (8, true),
+        ">f8" => (8, false),
+        descr => {
+            return Err(LoadError::UnsupportedFormat(format!(
+                "data type {descr} is not a 32- or 64-bit float"
+            )))
+        }
+    };
+    let fortran_order = match header_value(header, "fortran_order")? {
+        "False" => false,
+        "True" => true,
+        _ => return Err(LoadError::InvalidHeader("malformed fortran_order".into())),
+    };
+    let shape = header_value(header, "shape")?
+        .trim_start_matches('(')
+        .trim_end_matches(')')
+        .split(',')
+        .map(str::trim)
+        .filter(|dimension| !dimension.is_empty())
+        .map(str::parse::<usize>)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| LoadError::InvalidHeader("malformed shape".into()))?;
+    let (height, width) = match shape[..] {
+        [len] => (1, len),
+        [height, width] => (height, width),
+        _ => {
+            return Err(LoadError::UnsupportedFormat(format!(
+                "{}-dimensional arrays are not supported",
+                shape.len()
+            )))
+        }
+    };
+
+    let data_start = header_start + header_len;
+    let data_end = width
+        .checked_mul(height)
+        .and_then(|len| len.checked_mul(size))
+        .and_then(|len| len.checked_add(data_start))
+        .ok_or_else(|| LoadError::InvalidHeader(format!("{width}x{height} is too large")))?;
+    let data = bytes
+        .get(data_start..data_end)
+        .ok_or(LoadError::UnexpectedEof)?;
+    let samples: Vec<f32> = data
+        .chunks_exact(size)
+        .map(|b| match (size, little_endian) {
+            (4, true) => f32::from_le_bytes([b[0], b[1], b[2], b[3]]),
+            (4, false) => f32::from_be_bytes([b[0], b[1], b[2], b[3]]),
+            (_, true) => {
+                f64::from_le_bytes([b[0], b[1], b[2], b[3], b[4], b[5], b[6], b[7]]) as f32
+            }
+            (_, false) => {
+                f64::from_be_bytes([b[0], b[1], b[2], b[3], b[4], b[5], b[6], b[7]]) as f32
+            }
+        })
+        .collect();
+
+    let values = if fortran_order {
+        (0..width * height)
+            .map(|i| samples[(i % width) * height + i / width])
+            .collect()
+    } else {
+        samples
+    };
+    Ok((width, height, values))
+}
+
+/// Returns the raw value of `key` in the Python dictionary literal of an `.npy` header.
+fn header_value<'a>(header: &'a str, key: &str) -> Result<&'a str, LoadError> {
+    let missing = || LoadError::InvalidHeader(format!("missing {key}"));
+    let start = [format!("'{key}'"), format!("\"{key}\"")]
+        .iter()
+        .find_map(|quoted| header.find(quoted.as_str()).map(|i| i + quoted.len()))
+        .ok_or_else(missing)?;
+    let value = header[start..]
+        .trim_start()
+        .strip_prefix(':')
+        .ok_or_else(missing)?;
+    let value = value.trim_start();
+    let end = if value.starts_with('(') {
+        value.find(')').map(|i| i + 1)
+    } else {
+        value.find([',', '}'])
+    }
+    .ok_or_else(missing)?;
+    Ok(value[..end].trim())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Builds a version 1.0 file around a header and raw data.
+    fn npy(header: &str, data: &[u8]) -> Vec<u8> {
+        let mut bytes = MAGIC.to_vec();
+        bytes.extend_from_slice(&[1, 0]);
+        bytes.extend_from_slice(&(header.len() as u16).to_le_bytes());
+        bytes.extend_from_slice(header.as_bytes());
+        bytes.extend_from_slice(data);
+        bytes
+    }
+
+    #[test]
+    fn test_encode_header() {
+        let bytes = encode(3, 2, &[0.0; 6]);
+        let header_len = u16::from_le_bytes([bytes[8], bytes[9]]) as usize;
+        assert_eq!((10 + header_len) % 64, 0);
+        let header = std::str::from_utf8(&bytes[10..10 + header_len]).unwrap();
+        assert!(header.starts_with("{'descr': '<f4', 'fortran_order': False, 'shape': (2, 3), }"));
+        assert!(header.ends_with('\n'));
+        assert_eq!(bytes.len(), 10 + header_len + 24);
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let values = [0.0, 1.5, f32::INFINITY, -2.25, f32::NEG_INFINITY, 1e-7];
+        assert_eq!(
+            decode(&encode(2, 3, &values)).unwrap(),
+            (2, 3, values.to_vec())
+        );
+    }
+
+    #[test]
+    fn test_decode_f8_and_fortran_order() {
+        let data: Vec<u8> = [1.0_f64, 4.0, 2.0, 5.0, 3.0, f64::INFINITY]
+            .iter()
+            .flat_map(|v| v.to_le_bytes())
+            .collect();
+        let bytes = npy(
+            "{'descr': '<f8', 'fortran_order': True, 'shape': (2, 3), }\n",
+            &data,
+        );
+        assert_eq!(
+            decode(&bytes).unwrap(),
+            (3, 2, vec![1.0, 2.0, 3.0, 4.0, 5.0, f32::INFINITY])
+        );
+    }
+
+    #[test]
+    fn test_decode_one_dimensional_big_endian() {
+        let data: Vec<u8> = [1.0_f32, 2.0]
+            .iter()
+            .flat_map(|v| v.to_be_bytes())
+            .collect();
+        let bytes = npy(
+            "{'descr': '>f4', 'fortran_order': False, 'shape': (2,), }\n",
+            &data,
+        );
+        assert_eq!(decode(&bytes).unwrap(), (2, 1, vec![1.0, 2.0]));
+    }
+
+    #[test]
+    fn test_decode_errors() {
+        assert!(matches!(
+            decode(b"NUMPY"),
+            Err(LoadError::UnsupportedFormat(_))
+        ));
+        let int = npy(
+            "{'descr': '<i4', 'fortran_order': False, 'shape': (1,), }",
+            &[0; 4],
+        );
+        assert!(matches!(decode(&int), Err(LoadError::UnsupportedFormat(_))));
+        let cube = npy(
+            "{'descr': '<f4', 'fortran_order': False, 'shape': (1, 1, 1), }",
+            &[0; 4],
+        );
+        assert!(matches!(
+            decode(&cube),
+            Err(LoadError::UnsupportedFormat(_))
+        ));
+        let no_shape = npy("{'descr': '<f4', 'fortran_order': False, }", &[0; 4]);
+        assert!(matches!(
+            decode(&no_shape),
+            Err(LoadError::InvalidHeader(_))
+        ));
+        let short = npy(
+            "{'descr': '<f4', 'fortran_order': False, 'shape': (2, 2), }",
+            &[0; 12],
+        );
+        assert!(matches!(decode(&short), Err(LoadError::UnexpectedEof)));
+
+        // The data length fits, but not once the header is added.
+        let huge = npy(
+            &format!(
+                "{{'descr': '<f4', 'fortran_order': False, 'shape': ({},), }}",
+                usize::MAX / 4
+            ),
+            &[0; 4],
+        );
+        assert!(matches!(decode(&huge), Err(LoadError::InvalidHeader(_))));
+    }
+}
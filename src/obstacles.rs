@@ -1,4 +1,4 @@
-use crate::{Grid, SavePgm};
+use crate::{Grid, GridBounds, GridGeometry, GridN, LoadError, LoadPgm, PgmLoader, SavePgm};
 use std::fs::File;
 use std::io::Write;
 use std::path::Path;
@@ -8,9 +8,8 @@ use std::path::Path;
 /// Each cell indicates whether an obstacle is present at that location.
 #[derive(Debug, Clone)]
 pub struct Obstacles {
-    obstacles: Vec<bool>,
-    width: usize,
-    height: usize,
+    obstacles: GridN<bool, 2>,
+    geometry: Option<GridGeometry>,
 }
 
 impl Obstacles {
@@ -19,20 +18,51 @@ impl Obstacles {
     /// All cells are initially empty (no obstacles).
     pub fn new(width: usize, height: usize) -> Self {
         Self {
-            obstacles: vec![false; width * height],
-            width,
-            height,
+            obstacles: GridN::new([width, height], false),
+            geometry: None,
         }
     }
 
     /// Returns the width of the obstacle grid.
     pub const fn width(&self) -> usize {
-        self.width
+        self.obstacles.shape()[0]
     }
 
     /// Returns the height of the obstacle grid.
     pub const fn height(&self) -> usize {
-        self.height
+        self.obstacles.shape()[1]
+    }
+
+    /// Returns the placement of the grid in world coordinates, if known.
+    pub const fn geometry(&self) -> Option<&GridGeometry> {
+        self.geometry.as_ref()
+    }
+
+    /// Sets the placement of the grid in world coordinates.
+    #[must_use]
+    pub fn with_geometry(mut self, geometry: GridGeometry) -> Self {
+        self.geometry = Some(geometry);
+        self
+    }
+
+    /// Sets or clears the placement of the grid in world coordinates.
+    pub fn set_geometry(&mut self, geometry: Option<GridGeometry>) {
+        self.geometry = geometry;
+    }
+
+    /// Returns the world coordinates of the center of cell `(x, y)`.
+    ///
+    /// Without a geometry, cells are one unit apart with the origin at the corner of cell `(0, 0)`.
+    pub fn cell_to_world(&self, x: usize, y: usize) -> (f64, f64) {
+        self.geometry.unwrap_or_default().cell_to_world(x, y)
+    }
+
+    /// Returns the cell containing the given world coordinates, or `None` if it lies outside.
+    ///
+    /// Without a geometry, cells are one unit apart with the origin at the corner of cell `(0, 0)`.
+    pub fn world_to_cell(&self, wx: f64, wy: f64) -> Option<(usize, usize)> {
+        let (x, y) = self.geometry.unwrap_or_default().world_to_cell(wx, wy)?;
+        (x < self.width() && y < self.height()).then_some((x, y))
     }
 
     /// Returns an iterator over the obstacle values.
@@ -44,17 +74,51 @@ impl Obstacles {
     pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, bool> {
         self.obstacles.iter_mut()
     }
+
+    /// Returns a copy of the grid with obstacle and free cells swapped.
+    pub fn inverted(&self) -> Self {
+        Self {
+            obstacles: self.obstacles.map(|&b| !b),
+            geometry: self.geometry,
+        }
+    }
+
+    /// Returns the obstacles as a generic 2D grid, e.g. for
+    /// [`DistanceFieldAlgorithmN`](crate::DistanceFieldAlgorithmN).
+    pub const fn as_grid(&self) -> &GridN<bool, 2> {
+        &self.obstacles
+    }
+}
+
+impl From<GridN<bool, 2>> for Obstacles {
+    fn from(obstacles: GridN<bool, 2>) -> Self {
+        Self {
+            obstacles,
+            geometry: None,
+        }
+    }
 }
 
 impl Grid for Obstacles {
     type Item = bool;
 
     fn get_at(&self, x: usize, y: usize) -> &Self::Item {
-        &self.obstacles[y * self.width + x]
+        &self.obstacles.as_slice()[y * self.width() + x]
     }
 
     fn set_at(&mut self, x: usize, y: usize, value: Self::Item) {
-        self.obstacles[y * self.width + x] = value
+        let width = self.width();
+        self.obstacles.as_mut_slice()[y * width + x] = value
+    }
+}
+
+impl GridBounds for Obstacles {
+    fn width(&self) -> usize {
+        self.width()
+    }
+
+    fn height(&self) -> usize {
+        self.height()
     }
 }
 
@@ -64,7 +128,14 @@ impl SavePgm for Obstacles {
 
         let max_value: u8 = 255;
 
-        let header = format!("P5\n{} {}\n{}\n", self.width, self.height, max_value);
+        let comments = self.geometry.map(|g| g.pgm_comments()).unwrap_or_default();
+        let header = format!(
+            "P5\n{}{} {}\n{}\n",
+            comments,
+            self.width(),
+            self.height(),
+            max_value
+        );
         file.write_all(header.as_bytes())?;
 
         for &is_obstacle in self.iter() {
@@ -76,6 +147,15 @@ impl SavePgm for Obstacles {
     }
 }
 
+impl LoadPgm for Obstacles {
+    /// Reads obstacles from an image file, treating dark pixels as obstacles.
+    ///
+    /// Use [`PgmLoader`] to configure the threshold and polarity.
+    fn load_pgm<P: AsRef<Path>>(path: P) -> Result<Self, LoadError> {
+        PgmLoader::default().load(path)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -133,13 +213,44 @@ mod tests {
     fn test_grid_coordinate_mapping() {
         let mut obs = Obstacles::new(10, 10);
         obs.set_at(1, 2, true);
-        assert!(obs.obstacles[2 * 10 + 1]);
+        assert!(obs.obstacles.as_slice()[2 * 10 + 1]);
 
         obs.set_at(0, 0, true);
-        assert!(obs.obstacles[0]);
+        assert!(obs.obstacles.as_slice()[0]);
 
         obs.set_at(9, 9, true);
-        assert!(obs.obstacles[99]);
+        assert!(obs.obstacles.as_slice()[99]);
+    }
+
+    #[test]
+    fn test_inverted() {
+        let mut obs = Obstacles::new(3, 2);
+        obs.set_at(1, 1, true);
+        let inv = obs.inverted();
+        assert_eq!(inv.width(), 3);
+        assert_eq!(inv.height(), 2);
+        assert!(!inv.get_at(1, 1));
+        assert!(inv.get_at(0, 0));
+        assert_eq!(inv.iter().filter(|&&b| b).count(), 5);
+    }
+
+    #[test]
+    fn test_inverted_keeps_geometry() {
+        let geometry = GridGeometry::new(1.0, 2.0, 0.5).with_rotation(0.1);
+        let obs = Obstacles::new(3, 2).with_geometry(geometry);
+        assert_eq!(obs.inverted().geometry(), Some(&geometry));
+    }
+
+    #[test]
+    fn test_save_pgm_writes_geometry() {
+        let obs = Obstacles::new(2, 2).with_geometry(GridGeometry::new(-3.0, 4.0, 0.25));
+        let path = "test_obstacles_geometry.pgm";
+        obs.save_pgm(path).unwrap();
+        let content = fs::read(path).unwrap();
+        let header = "P5\n# resolution: 0.25\n# origin: -3 4 0\n2 2\n255\n";
+        assert!(content.starts_with(header.as_bytes()));
+        assert_eq!(content.len(), header.len() + 4);
+        fs::remove_file(path).unwrap();
     }
 
     #[test]
@@ -0,0 +1,104 @@
+use crate::{Grid, GridBounds, Obstacles};
+
+/// A 1D line of obstacle positions, such as a single lidar ray or a timeline.
+///
+/// This is stored as an [`Obstacles`] grid with a height of one, so it can be passed to every
+/// [`DistanceFieldAlgorithm`](crate::DistanceFieldAlgorithm) through
+/// [`calculate_distance_field1`](crate::DistanceFieldAlgorithm::calculate_distance_field1).
+#[derive(Debug, Clone)]
+pub struct Obstacles1 {
+    obstacles: Obstacles,
+}
+
+impl Obstacles1 {
+    /// Creates a new obstacle line with the given number of cells.
+    ///
+    /// All cells are initially empty (no obstacles).
+    pub fn new(len: usize) -> Self {
+        Self {
+            obstacles: Obstacles::new(len, 1),
+        }
+    }
+
+    /// Returns the number of cells.
+    pub const fn len(&self) -> usize {
+        self.obstacles.width()
+    }
+
+    /// Returns `true` if the line has no cells.
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns whether an obstacle is present at `x`.
+    pub fn get(&self, x: usize) -> bool {
+        *self.obstacles.get_at(x, 0)
+    }
+
+    /// Sets whether an obstacle is present at `x`.
+    pub fn set(&mut self, x: usize, value: bool) {
+        self.obstacles.set_at(x, 0, value)
+    }
+
+    /// Returns an iterator over the obstacle values.
+    pub fn iter(&self) -> std::slice::Iter<'_, bool> {
+        self.obstacles.iter()
+    }
+
+    /// Returns a mutable iterator over the obstacle values.
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, bool> {
+        self.obstacles.iter_mut()
+    }
+}
+
+impl AsRef<Obstacles> for Obstacles1 {
+    fn as_ref(&self) -> &Obstacles {
+        &self.obstacles
+    }
+}
+
+impl Grid for Obstacles1 {
+    type Item = bool;
+
+    fn get_at(&self, x: usize, y: usize) -> &Self::Item {
+        self.obstacles.get_at(x, y)
+    }
+
+    fn set_at(&mut self, x: usize, y: usize, value: Self::Item) {
+        self.obstacles.set_at(x, y, value)
+    }
+}
+
+impl GridBounds for Obstacles1 {
+    fn width(&self) -> usize {
+        self.obstacles.width()
+    }
+
+    fn height(&self) -> usize {
+        1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_is_empty_line() {
+        let obs = Obstacles1::new(7);
+        assert_eq!(obs.len(), 7);
+        assert!(!obs.is_empty());
+        assert!(obs.iter().all(|&b| !b));
+        assert_eq!(obs.as_ref().height(), 1);
+        assert!(Obstacles1::new(0).is_empty());
+    }
+
+    #[test]
+    fn test_get_set() {
+        let mut obs = Obstacles1::new(4);
+        obs.set(2, true);
+        assert!(obs.get(2));
+        assert!(*obs.get_at(2, 0));
+        assert!(obs.try_get_at(0, 1).is_err());
+    }
+}
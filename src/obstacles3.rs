@@ -0,0 +1,129 @@
+use crate::{Grid3, GridN};
+
+/// A 3D grid representing obstacle positions.
+///
+/// Each cell indicates whether an obstacle is present at that location.
+#[derive(Debug, Clone)]
+pub struct Obstacles3 {
+    obstacles: GridN<bool, 3>,
+}
+
+impl Obstacles3 {
+    /// Creates a new obstacle grid with the given dimensions.
+    ///
+    /// All cells are initially empty (no obstacles).
+    pub fn new(width: usize, height: usize, depth: usize) -> Self {
+        Self {
+            obstacles: GridN::new([width, height, depth], false),
+        }
+    }
+
+    /// Returns the width of the obstacle grid.
+    pub const fn width(&self) -> usize {
+        self.obstacles.shape()[0]
+    }
+
+    /// Returns the height of the obstacle grid.
+    pub const fn height(&self) -> usize {
+        self.obstacles.shape()[1]
+    }
+
+    /// Returns the depth of the obstacle grid.
+    pub const fn depth(&self) -> usize {
+        self.obstacles.shape()[2]
+    }
+
+    /// Returns an iterator over the obstacle values.
+    pub fn iter(&self) -> std::slice::Iter<'_, bool> {
+        self.obstacles.iter()
+    }
+
+    /// Returns a mutable iterator over the obstacle values.
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, bool> {
+        self.obstacles.iter_mut()
+    }
+
+    /// Returns the obstacles as a generic 3D grid, e.g. for
+    /// [`DistanceFieldAlgorithmN`](crate::DistanceFieldAlgorithmN).
+    pub const fn as_grid(&self) -> &GridN<bool, 3> {
+        &self.obstacles
+    }
+}
+
+impl From<GridN<bool, 3>> for Obstacles3 {
+    fn from(obstacles: GridN<bool, 3>) -> Self {
+        Self { obstacles }
+    }
+}
+
+impl Grid3 for Obstacles3 {
+    type Item = bool;
+
+    fn get_at(&self, x: usize, y: usize, z: usize) -> &Self::Item {
+        &self.obstacles[[x, y, z]]
+    }
+
+    fn set_at(&mut self, x: usize, y: usize, z: usize, value: Self::Item) {
+        self.obstacles[[x, y, z]] = value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_dimensions() {
+        let obs = Obstacles3::new(10, 20, 30);
+        assert_eq!(obs.width(), 10);
+        assert_eq!(obs.height(), 20);
+        assert_eq!(obs.depth(), 30);
+    }
+
+    #[test]
+    fn test_new_initial_values() {
+        let obs = Obstacles3::new(5, 5, 5);
+        for &b in obs.iter() {
+            assert!(!b, "All cells should be empty");
+        }
+    }
+
+    #[test]
+    fn test_iter_length() {
+        let obs = Obstacles3::new(10, 20, 3);
+        assert_eq!(obs.iter().count(), 600);
+    }
+
+    #[test]
+    fn test_iter_mut_modifies() {
+        let mut obs = Obstacles3::new(3, 3, 3);
+        for b in obs.iter_mut() {
+            *b = true;
+        }
+        for &b in obs.iter() {
+            assert!(b);
+        }
+    }
+
+    #[test]
+    fn test_grid_get_set_roundtrip() {
+        let mut obs = Obstacles3::new(5, 5, 5);
+        obs.set_at(2, 3, 4, true);
+        assert!(obs.get_at(2, 3, 4));
+        obs.set_at(2, 3, 4, false);
+        assert!(!obs.get_at(2, 3, 4));
+    }
+
+    #[test]
+    fn test_grid_coordinate_mapping() {
+        let mut obs = Obstacles3::new(10, 10, 10);
+        obs.set_at(1, 2, 3, true);
+        assert!(obs.obstacles.as_slice()[(3 * 10 + 2) * 10 + 1]);
+
+        obs.set_at(0, 0, 0, true);
+        assert!(obs.obstacles.as_slice()[0]);
+
+        obs.set_at(9, 9, 9, true);
+        assert!(obs.obstacles.as_slice()[999]);
+    }
+}
@@ -0,0 +1,418 @@
+use crate::fast_sweeping::SweepDirection;
+use crate::solve_report::SweepStats;
+use crate::{
+    min3, Cutoff, DistanceField, DistanceFieldAlgorithm, NaiveFastSweepingMethod, Obstacles,
+    SolveReport,
+};
+use rayon::prelude::*;
+use std::ops::Range;
+
+/// Parallel implementation of the naive fast sweeping method.
+///
+/// Every directional sweep is split into square blocks which are processed in anti-diagonal
+/// wavefronts (the hyperplane ordering of Detrixhe et al.). The blocks of one wavefront only
+/// depend on blocks of earlier wavefronts and are swept concurrently using rayon, while cells
+/// within a block are swept row by row. Every cell sees exactly the same neighbor values as in
+/// the serial sweep, so the results are identical to [`NaiveFastSweepingMethod`].
+#[derive(Debug, Clone, Copy)]
+pub struct ParallelFastSweepingMethod {
+    method: NaiveFastSweepingMethod,
+    block_size: usize,
+}
+
+impl ParallelFastSweepingMethod {
+    /// Default edge length of the square blocks processed by a single task.
+    pub const DEFAULT_BLOCK_SIZE: usize = 64;
+
+    /// Sets the step size for distance propagation.
+    ///
+    /// See [`NaiveFastSweepingMethod::with_step_size`].
+    #[must_use]
+    pub const fn with_step_size(mut self, step_size: f32) -> Self {
+        self.method = self.method.with_step_size(step_size);
+        self
+    }
+
+    /// Sets separate step sizes along the x and y axis.
+    ///
+    /// See [`NaiveFastSweepingMethod::with_spacing`].
+    #[must_use]
+    pub const fn with_spacing(mut self, dx: f32, dy: f32) -> Self {
+        self.method = self.method.with_spacing(dx, dy);
+        self
+    }
+
+    /// Sets the maximum number of sweep iterations.
+    ///
+    /// See [`NaiveFastSweepingMethod::with_max_iterations`].
+    #[must_use]
+    pub const fn with_max_iterations(mut self, max_iterations: usize) -> Self {
+        self.method = self.method.with_max_iterations(max_iterations);
+        self
+    }
+
+    /// Sets the convergence tolerance.
+    ///
+    /// See [`NaiveFastSweepingMethod::with_tolerance`].
+    #[must_use]
+    pub const fn with_tolerance(mut self, tolerance: f32) -> Self {
+        self.method = self.method.with_tolerance(tolerance);
+        self
+    }
+
+    /// Sets the largest distance of interest.
+    ///
+    /// See [`NaiveFastSweepingMethod::with_max_distance`].
+    #[must_use]
+    pub const fn with_max_distance(mut self, max_distance: f32) -> Self {
+        self.method = self.method.with_max_distance(max_distance);
+        self
+    }
+
+    /// Sets how distances beyond the maximum distance are represented.
+    ///
+    /// See [`NaiveFastSweepingMethod::with_cutoff`].
+    #[must_use]
+    pub const fn with_cutoff(mut self, cutoff: Cutoff) -> Self {
+        self.method = self.method.with_cutoff(cutoff);
+        self
+    }
+
+    /// Sets the edge length of the square blocks processed by a single task.
+    ///
+    /// Smaller blocks expose more parallelism, larger blocks reduce scheduling overhead.
+    /// A value of `0` is treated as `1`.
+    #[must_use]
+    pub const fn with_block_size(mut self, block_size: usize) -> Self {
+        self.block_size = block_size;
+        self
+    }
+
+    /// Calculates the distance field like
+    /// [`calculate_distance_field`](DistanceFieldAlgorithm::calculate_distance_field)
+    /// and reports how the solve went.
+    ///
+    /// The report is identical to the one of the serial solver.
+    pub fn calculate_distance_field_with_report(
+        &self,
+        distance_field: &mut DistanceField,
+        obstacles: &Obstacles,
+    ) -> SolveReport {
+        self.fast_sweeping(distance_field, obstacles)
+    }
+
+    fn fast_sweeping(
+        &self,
+        distance_field: &mut DistanceField,
+        obstacles: &Obstacles,
+    ) -> SolveReport {
+        // Single rows and columns are inherently sequential.
+        if distance_field.width() <= 1 || distance_field.height() <= 1 {
+            return self
+                .method
+                .calculate_distance_field_with_report(distance_field, obstacles);
+        }
+
+        let spacing = self.method.spacing();
+        let block_size = self.block_size.max(1);
+
+        self.method.initialize(distance_field, obstacles);
+        let spans = self.method.row_spans(obstacles);
+        let report =
+            self.method
+                .perform_sweeps_with(distance_field, |distance_field, direction| {
+                    sweep(distance_field, direction, spacing, block_size, &spans)
+                });
+        self.method.apply_cutoff(distance_field);
+        report
+    }
+}
+
+/// Raw access to the distances shared between the tasks of a wavefront.
+#[derive(Clone, Copy)]
+struct SharedDistances {
+    distances: *mut f32,
+    width: usize,
+    height: usize,
+}
+
+// SAFETY: Tasks of one wavefront write to disjoint blocks and only read cells that are either
+// within their own block or were written by an earlier, already joined wavefront.
+unsafe impl Send for SharedDistances {}
+unsafe impl Sync for SharedDistances {}
+
+impl SharedDistances {
+    /// Sweeps a single block given in sweep coordinates, i.e. with the sweep direction
+    /// mapped onto increasing indices.
+    ///
+    /// # Safety
+    /// No other thread may concurrently access the cells of this block or its upwind
+    /// neighbor cells.
+    unsafe fn sweep_block(
+        self,
+        direction: SweepDirection,
+        (dx, dy): (f32, f32),
+        columns: Range<usize>,
+        rows: Range<usize>,
+        spans: &[Range<usize>],
+    ) -> SweepStats {
+        let (width, height) = (self.width, self.height);
+        let (reverse_x, reverse_y) = (direction.reverse_x(), direction.reverse_y());
+        let mut stats = SweepStats::default();
+
+        // The first row and column in sweep order have no upwind neighbor and are skipped,
+        // exactly like in the serial sweeps, as are the columns outside of the row spans.
+        for j in rows.start.max(1)..rows.end {
+            let y = if reverse_y { height - 1 - j } else { j };
+            let upwind_y = if reverse_y { y + 1 } else { y - 1 };
+            let span = if reverse_x {
+                width - spans[y].end..width - spans[y].start
+            } else {
+                spans[y].clone()
+            };
+
+            for i in columns.start.max(1).max(span.start)..columns.end.min(span.end) {
+                let x = if reverse_x { width - 1 - i } else { i };
+                let upwind_x = if reverse_x { x + 1 } else { x - 1 };
+
+                let center_ptr = self.distances.add(y * width + x);
+                let center = *center_ptr;
+                let vertical = *self.distances.add(upwind_y * width + x);
+                let horizontal = *self.distances.add(y * width + upwind_x);
+                let new_value = min3(center, vertical + dy, horizontal + dx);
+
+                if new_value != center {
+                    *center_ptr = new_value;
+                    stats.record(center, new_value);
+                }
+            }
+        }
+        stats
+    }
+}
+
+fn sweep(
+    distance_field: &mut DistanceField,
+    direction: SweepDirection,
+    spacing: (f32, f32),
+    block_size: usize,
+    spans: &[Range<usize>],
+) -> SweepStats {
+    let width = distance_field.width();
+    let height = distance_field.height();
+    if width == 0 || height == 0 {
+        return SweepStats::default();
+    }
+
+    let shared = SharedDistances {
+        distances: distance_field.as_mut_slice().as_mut_ptr(),
+        width,
+        height,
+    };
+
+    let blocks_x = width.div_ceil(block_size);
+    let blocks_y = height.div_ceil(block_size);
+    let mut stats = SweepStats::default();
+
+    for wave in 0..blocks_x + blocks_y - 1 {
+        let first = wave.saturating_sub(blocks_y - 1);
+        let last = wave.min(blocks_x - 1);
+
+        stats = (first..=last)
+            .into_par_iter()
+            .map(|block_x| {
+                let block_y = wave - block_x;
+                let columns = block_x * block_size..((block_x + 1) * block_size).min(width);
+                let rows = block_y * block_size..((block_y + 1) * block_size).min(height);
+
+                // SAFETY: Blocks on the same anti-diagonal are disjoint and do not neighbor
+                // each other along an axis; their upwind neighbors belong to earlier wavefronts.
+                unsafe { shared.sweep_block(direction, spacing, columns, rows, spans) }
+            })
+            .reduce(SweepStats::default, SweepStats::merge)
+            .merge(stats);
+    }
+    stats
+}
+
+impl From<NaiveFastSweepingMethod> for ParallelFastSweepingMethod {
+    fn from(method: NaiveFastSweepingMethod) -> Self {
+        Self {
+            method,
+            block_size: Self::DEFAULT_BLOCK_SIZE,
+        }
+    }
+}
+
+impl Default for ParallelFastSweepingMethod {
+    fn default() -> Self {
+        Self::from(NaiveFastSweepingMethod::default())
+    }
+}
+
+impl DistanceFieldAlgorithm for ParallelFastSweepingMethod {
+    fn calculate_distance_field(&self, distance_field: &mut DistanceField, obstacles: &Obstacles) {
+        self.fast_sweeping(distance_field, obstacles);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Grid;
+
+    fn create_test_obstacles(width: usize, height: usize) -> Obstacles {
+        let mut obstacles = Obstacles::new(width, height);
+
+        for y in 100..200 {
+            obstacles.set_at(100, y, true);
+        }
+
+        for x in 100..400 {
+            obstacles.set_at(x, 200, true);
+        }
+
+        for x in 100..200 {
+            obstacles.set_at(400 + x, 200 + x, true);
+        }
+
+        obstacles
+    }
+
+    fn assert_identical(
+        serial: NaiveFastSweepingMethod,
+        parallel: ParallelFastSweepingMethod,
+        obstacles: &Obstacles,
+    ) {
+        let mut expected = DistanceField::from(obstacles);
+        serial.calculate_distance_field(&mut expected, obstacles);
+
+        let mut actual = DistanceField::from(obstacles);
+        parallel.calculate_distance_field(&mut actual, obstacles);
+
+        for (index, (a, b)) in expected.iter().zip(actual.iter()).enumerate() {
+            assert_eq!(a.to_bits(), b.to_bits(), "Mismatch at index {index}");
+        }
+    }
+
+    #[test]
+    fn test_default_values() {
+        let algo = ParallelFastSweepingMethod::default();
+        assert_eq!(
+            algo.block_size,
+            ParallelFastSweepingMethod::DEFAULT_BLOCK_SIZE
+        );
+        assert_eq!(algo.method.spacing(), (1.0, 1.0));
+    }
+
+    #[test]
+    fn test_builders() {
+        let algo = ParallelFastSweepingMethod::default()
+            .with_step_size(0.5)
+            .with_block_size(16);
+        assert_eq!(algo.method.spacing(), (0.5, 0.5));
+        assert_eq!(algo.block_size, 16);
+    }
+
+    #[test]
+    fn test_identical_to_serial_until_convergence() {
+        let obstacles = create_test_obstacles(640, 480);
+        let serial = NaiveFastSweepingMethod::default().with_step_size(0.1);
+        assert_identical(serial, serial.into(), &obstacles);
+    }
+
+    #[test]
+    fn test_identical_to_serial_with_iteration_limits() {
+        let obstacles = create_test_obstacles(640, 480);
+        for max_iterations in [1, 2, 5] {
+            let serial = NaiveFastSweepingMethod::default()
+                .with_step_size(0.1)
+                .with_max_iterations(max_iterations);
+            assert_identical(serial, serial.into(), &obstacles);
+        }
+    }
+
+    #[test]
+    fn test_identical_to_serial_for_block_sizes() {
+        let mut obstacles = Obstacles::new(97, 61);
+        for (x, y) in [(0, 0), (13, 40), (96, 60), (50, 7), (70, 33)] {
+            obstacles.set_at(x, y, true);
+        }
+        for block_size in [0, 1, 3, 7, 16, 61, 200] {
+            for max_iterations in [0, 1] {
+                let serial = NaiveFastSweepingMethod::default()
+                    .with_step_size(0.7)
+                    .with_max_iterations(max_iterations);
+                let parallel = ParallelFastSweepingMethod::from(serial).with_block_size(block_size);
+                assert_identical(serial, parallel, &obstacles);
+            }
+        }
+    }
+
+    #[test]
+    fn test_identical_to_serial_with_anisotropic_spacing() {
+        let obstacles = create_test_obstacles(640, 480);
+        let serial = NaiveFastSweepingMethod::default().with_spacing(0.5, 1.0);
+        assert_identical(serial, serial.into(), &obstacles);
+    }
+
+    #[test]
+    fn test_report_matches_serial() {
+        let obstacles = create_test_obstacles(640, 480);
+        for max_iterations in [0, 1] {
+            let serial = NaiveFastSweepingMethod::default()
+                .with_step_size(0.1)
+                .with_max_iterations(max_iterations);
+            let parallel = ParallelFastSweepingMethod::from(serial).with_block_size(48);
+
+            let mut expected = DistanceField::from(&obstacles);
+            let mut actual = DistanceField::from(&obstacles);
+            assert_eq!(
+                serial.calculate_distance_field_with_report(&mut expected, &obstacles),
+                parallel.calculate_distance_field_with_report(&mut actual, &obstacles)
+            );
+        }
+    }
+
+    #[test]
+    fn test_identical_to_serial_with_max_distance() {
+        let obstacles = create_test_obstacles(640, 480);
+        for cutoff in [Cutoff::Clamp, Cutoff::Unreached] {
+            let serial = NaiveFastSweepingMethod::default()
+                .with_step_size(0.1)
+                .with_max_distance(3.0)
+                .with_cutoff(cutoff);
+            let parallel = ParallelFastSweepingMethod::from(serial).with_block_size(48);
+            assert_identical(serial, parallel, &obstacles);
+
+            let mut expected = DistanceField::from(&obstacles);
+            let mut actual = DistanceField::from(&obstacles);
+            assert_eq!(
+                serial.calculate_distance_field_with_report(&mut expected, &obstacles),
+                parallel.calculate_distance_field_with_report(&mut actual, &obstacles)
+            );
+        }
+    }
+
+    #[test]
+    fn test_identical_to_serial_on_small_grids() {
+        for (width, height) in [(1, 1), (2, 2), (1, 5), (5, 1), (3, 7)] {
+            let mut obstacles = Obstacles::new(width, height);
+            obstacles.set_at(0, 0, true);
+            let serial = NaiveFastSweepingMethod::default();
+            assert_identical(
+                serial,
+                ParallelFastSweepingMethod::from(serial).with_block_size(2),
+                &obstacles,
+            );
+        }
+    }
+
+    #[test]
+    fn test_empty_grid() {
+        let obstacles = Obstacles::new(0, 0);
+        let mut df = DistanceField::from(&obstacles);
+        ParallelFastSweepingMethod::default().calculate_distance_field(&mut df, &obstacles);
+        assert_eq!(df.iter().count(), 0);
+    }
+}
@@ -0,0 +1,332 @@
+use crate::{DistanceField, Error, GradientField, Grid, OutOfBounds, Sampler};
+
+/// Configuration and implementation of shortest path extraction by gradient descent.
+///
+/// Starting from a position, the path follows the negative [`GradientField`] of a distance
+/// or travel time field until it reaches a cell with a value of zero or less, i.e. an
+/// obstacle or seed. Where the interpolated gradient does not lead downhill, such as on
+/// ridges or next to unreachable cells, the path steps to the lowest of the eight neighboring
+/// cells instead, so descent always terminates on a converged field.
+///
+/// Positions are given in cell units with cell centers at integer coordinates, as for
+/// [`Sampler`].
+#[derive(Debug, Clone, Copy)]
+pub struct GradientDescent {
+    step_length: f32,
+    max_steps: usize,
+}
+
+impl GradientDescent {
+    /// Sets the distance between two consecutive path points, in cells.
+    #[must_use]
+    pub const fn with_step_length(mut self, step_length: f32) -> Self {
+        self.step_length = step_length;
+        self
+    }
+
+    /// Sets the maximum number of path points.
+    ///
+    /// If set to `0`, the limit is derived from the grid size.
+    #[must_use]
+    pub const fn with_max_steps(mut self, max_steps: usize) -> Self {
+        self.max_steps = max_steps;
+        self
+    }
+
+    /// Returns the polyline descending from `start` to the closest zero cell.
+    ///
+    /// Fails if `start` is outside of the grid or in an unreachable cell, or if no zero cell
+    /// was reached within the maximum number of steps.
+    pub fn extract_path(
+        &self,
+        distance_field: &DistanceField,
+        start: (f32, f32),
+    ) -> Result<Vec<(f32, f32)>, Error> {
+        let gradients = GradientField::from(distance_field);
+        self.extract_path_with(distance_field, &gradients, start)
+    }
+
+    /// Like [`extract_path`](Self::extract_path), but reuses precomputed gradients.
+    pub fn extract_path_with(
+        &self,
+        distance_field: &DistanceField,
+        gradients: &GradientField,
+        start: (f32, f32),
+    ) -> Result<Vec<(f32, f32)>, Error> {
+        Error::check_dimensions(
+            (distance_field.width(), distance_field.height()),
+            (gradients.width(), gradients.height()),
+        )?;
+
+        let width = distance_field.width();
+        let height = distance_field.height();
+        let sampler = Sampler::default().with_out_of_bounds(OutOfBounds::Error);
+        let mut current = sampler.sample(distance_field, start.0, start.1)?;
+        let mut point = start;
+        let mut cell = nearest_cell(point);
+        if !distance_field.get_at(cell.0, cell.1).is_finite() {
+            return Err(Error::Unreachable {
+                x: start.0,
+                y: start.1,
+            });
+        }
+
+        let max_steps = match self.max_steps {
+            0 => (width * height).saturating_mul(2 + (1.0 / self.step_length) as usize),
+            max_steps => max_steps,
+        };
+
+        let mut path = vec![point];
+        while path.len() < max_steps {
+            let cell_value = *distance_field.get_at(cell.0, cell.1);
+            if cell_value <= 0.0 {
+                let target = (cell.0 as f32, cell.1 as f32);
+                if target != point {
+                    path.push(target);
+                }
+                return Ok(path);
+            }
+
+            if let Some((next, value)) = self.gradient_step(distance_field, gradients, point) {
+                if value < current {
+                    point = next;
+                    current = value;
+                    cell = nearest_cell(point);
+                    path.push(point);
+                    continue;
+                }
+            }
+
+            // Fall back to the lowest neighboring cell.
+            let Some(next) = lowest_neighbor(distance_field, cell) else {
+                break;
+            };
+            cell = next;
+            point = (cell.0 as f32, cell.1 as f32);
+            current = *distance_field.get_at(cell.0, cell.1);
+            path.push(point);
+        }
+
+        Err(Error::Unreachable {
+            x: start.0,
+            y: start.1,
+        })
+    }
+
+    /// Takes a step along the negative interpolated gradient, returning the new position
+    /// and its interpolated value.
+    fn gradient_step(
+        &self,
+        distance_field: &DistanceField,
+        gradients: &GradientField,
+        (x, y): (f32, f32),
+    ) -> Option<((f32, f32), f32)> {
+        let (gx, gy) = gradients.sample(x, y);
+        let norm = (gx * gx + gy * gy).sqrt();
+        if norm == 0.0 {
+            return None;
+        }
+
+        let max_x = (distance_field.width() - 1) as f32;
+        let max_y = (distance_field.height() - 1) as f32;
+        let next = (
+            (x - self.step_length * gx / norm).clamp(0.0, max_x),
+            (y - self.step_length * gy / norm).clamp(0.0, max_y),
+        );
+        let value = distance_field.sample(next.0, next.1).ok()?;
+        value.is_finite().then_some((next, value))
+    }
+}
+
+/// Returns the cell whose center is closest to the position.
+fn nearest_cell((x, y): (f32, f32)) -> (usize, usize) {
+    (x.round() as usize, y.round() as usize)
+}
+
+/// Returns the lowest of the eight neighbors if it is lower than the cell itself.
+fn lowest_neighbor(
+    distance_field: &DistanceField,
+    (x, y): (usize, usize),
+) -> Option<(usize, usize)> {
+    let mut lowest = (*distance_field.get_at(x, y), None);
+    for ny in y.saturating_sub(1)..=(y + 1).min(distance_field.height() - 1) {
+        for nx in x.saturating_sub(1)..=(x + 1).min(distance_field.width() - 1) {
+            let value = *distance_field.get_at(nx, ny);
+            if value < lowest.0 {
+                lowest = (value, Some((nx, ny)));
+            }
+        }
+    }
+    lowest.1
+}
+
+impl Default for GradientDescent {
+    fn default() -> Self {
+        Self {
+            step_length: 0.5,
+            max_steps: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        CostField, DistanceFieldAlgorithm, EikonalFastSweepingMethod, EuclideanDistanceTransform,
+        Obstacles, TravelTimeAlgorithm,
+    };
+
+    /// The L-shaped obstacle scene of the example and benchmark.
+    fn create_test_obstacles() -> Obstacles {
+        let mut obstacles = Obstacles::new(640, 480);
+        for y in 100..200 {
+            obstacles.set_at(100, y, true);
+        }
+        for x in 100..400 {
+            obstacles.set_at(x, 200, true);
+        }
+        for x in 100..200 {
+            obstacles.set_at(400 + x, 200 + x, true);
+        }
+        obstacles
+    }
+
+    fn length(path: &[(f32, f32)]) -> f32 {
+        path.windows(2)
+            .map(|w| ((w[1].0 - w[0].0).powi(2) + (w[1].1 - w[0].1).powi(2)).sqrt())
+            .sum()
+    }
+
+    fn assert_descends(df: &DistanceField, path: &[(f32, f32)]) {
+        let values: Vec<f32> = path.iter().map(|p| df.sample(p.0, p.1).unwrap()).collect();
+        for pair in values.windows(2) {
+            assert!(pair[1] < pair[0], "Path does not descend: {values:?}");
+        }
+        let end = path.last().unwrap();
+        assert_eq!(*df.get_at(end.0 as usize, end.1 as usize), 0.0);
+    }
+
+    #[test]
+    fn test_default_values() {
+        let descent = GradientDescent::default();
+        assert_eq!(descent.step_length, 0.5);
+        assert_eq!(descent.max_steps, 0);
+    }
+
+    #[test]
+    fn test_l_shape_paths_end_on_closest_obstacle() {
+        let obstacles = create_test_obstacles();
+        let mut df = DistanceField::from(&obstacles);
+        EuclideanDistanceTransform::default().calculate_distance_field(&mut df, &obstacles);
+        let gradients = GradientField::from(&df);
+        let descent = GradientDescent::default();
+
+        // Inside the L, the horizontal bar is closest; below it, the diagonal.
+        for (start, end) in [
+            ((250.0, 150.0), (250.0, 200.0)),
+            ((30.0, 120.5), (100.0, 120.0)),
+        ] {
+            let path = descent.extract_path_with(&df, &gradients, start).unwrap();
+            assert_descends(&df, &path);
+            let last = *path.last().unwrap();
+            assert!(
+                (last.0 - end.0).abs() <= 1.0 && (last.1 - end.1).abs() <= 1.0,
+                "{last:?}"
+            );
+            assert!(length(&path) <= df.sample(start.0, start.1).unwrap() + 1.0);
+        }
+
+        for start in [
+            (600.0, 50.0),
+            (450.5, 400.25),
+            (10.0, 470.0),
+            (399.0, 300.0),
+        ] {
+            let path = descent.extract_path_with(&df, &gradients, start).unwrap();
+            assert_descends(&df, &path);
+            let distance = df.sample(start.0, start.1).unwrap();
+            assert!(length(&path) <= distance * 1.05 + 1.0, "{start:?}");
+        }
+    }
+
+    #[test]
+    fn test_path_avoids_impassable_cells() {
+        let mut obstacles = Obstacles::new(60, 40);
+        obstacles.set_at(10, 20, true);
+        let mut costs = CostField::from(&obstacles);
+        for y in 5..40 {
+            costs.set_at(30, y, CostField::IMPASSABLE);
+        }
+
+        let mut travel_time = DistanceField::from(&obstacles);
+        EikonalFastSweepingMethod::default().calculate_travel_time(
+            &mut travel_time,
+            &obstacles,
+            &costs,
+        );
+
+        let path = GradientDescent::default()
+            .extract_path(&travel_time, (50.0, 30.0))
+            .unwrap();
+        assert_descends(&travel_time, &path);
+        assert_eq!(*path.last().unwrap(), (10.0, 20.0));
+        for &(x, y) in &path {
+            let cell = nearest_cell((x, y));
+            assert!(travel_time.get_at(cell.0, cell.1).is_finite());
+        }
+        assert!(
+            path.iter().any(|&(_, y)| y < 5.0),
+            "Path must pass above the wall"
+        );
+    }
+
+    #[test]
+    fn test_start_on_obstacle() {
+        let mut obstacles = Obstacles::new(5, 5);
+        obstacles.set_at(2, 2, true);
+        let mut df = DistanceField::from(&obstacles);
+        EuclideanDistanceTransform::default().calculate_distance_field(&mut df, &obstacles);
+        let path = GradientDescent::default()
+            .extract_path(&df, (2.0, 2.0))
+            .unwrap();
+        assert_eq!(path, vec![(2.0, 2.0)]);
+    }
+
+    #[test]
+    fn test_errors() {
+        let obstacles = Obstacles::new(5, 5);
+        let mut df = DistanceField::from(&obstacles);
+        EuclideanDistanceTransform::default().calculate_distance_field(&mut df, &obstacles);
+        assert_eq!(
+            GradientDescent::default().extract_path(&df, (1.0, 1.0)),
+            Err(Error::Unreachable { x: 1.0, y: 1.0 })
+        );
+        assert!(matches!(
+            GradientDescent::default().extract_path(&df, (5.5, 1.0)),
+            Err(Error::SampleOutOfBounds { .. })
+        ));
+        assert!(matches!(
+            GradientDescent::default().extract_path_with(
+                &df,
+                &GradientField::from(&DistanceField::new(4, 4)),
+                (1.0, 1.0)
+            ),
+            Err(Error::DimensionMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_max_steps() {
+        let mut obstacles = Obstacles::new(50, 5);
+        obstacles.set_at(0, 2, true);
+        let mut df = DistanceField::from(&obstacles);
+        EuclideanDistanceTransform::default().calculate_distance_field(&mut df, &obstacles);
+        let descent = GradientDescent::default().with_max_steps(10);
+        assert!(descent.extract_path(&df, (49.0, 2.0)).is_err());
+        assert!(descent
+            .with_step_length(8.0)
+            .extract_path(&df, (49.0, 2.0))
+            .is_ok());
+    }
+}
@@ -0,0 +1,152 @@
+use crate::LoadError;
+
+/// Encodes a grayscale Portable Float Map.
+///
+/// Samples are written as little-endian 32-bit floats. Following the format, the bottom row
+/// comes first, so that the image appears the same way up as the PGM output.
+pub(crate) fn encode(width: usize, height: usize, values: &[f32]) -> Vec<u8> {
+    let header = format!("Pf\n{width} {height}\n-1.0\n");
+    let mut bytes = Vec::with_capacity(header.len() + values.len() * 4);
+    bytes.extend_from_slice(header.as_bytes());
+    for row in values.chunks_exact(width.max(1)).rev() {
+        for value in row {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+    }
+    bytes
+}
+
+/// Decodes a grayscale Portable Float Map into its width, height and top-down samples.
+pub(crate) fn decode(bytes: &[u8]) -> Result<(usize, usize, Vec<f32>), LoadError> {
+    let mut fields = Vec::with_capacity(4);
+    let mut position = 0;
+    while fields.len() < 4 {
+        while bytes.get(position).is_some_and(u8::is_ascii_whitespace) {
+            position += 1;
+        }
+        let len = bytes[position..]
+            .iter()
+            .take_while(|b| !b.is_ascii_whitespace())
+            .count();
+        if len == 0 || position + len == bytes.len() {
+            return Err(LoadError::UnexpectedEof);
+        }
+        fields.push(String::from_utf8_lossy(&bytes[position..position + len]));
+        position += len;
+    }
+    // A single whitespace byte separates the header from the raster.
+    position += 1;
+
+    match fields[0].as_ref() {
+        "Pf" => {}
+        "PF" => {
+            return Err(LoadError::UnsupportedFormat(
+                "color PFM images are not supported".into(),
+            ))
+        }
+        _ => {
+            return Err(LoadError::UnsupportedFormat(
+                "expected the PFM magic number Pf".into(),
+            ))
+        }
+    }
+    let dimension = |field: &str, name: &str| {
+        field
+            .parse::<usize>()
+            .map_err(|_| LoadError::InvalidHeader(format!("malformed {name}")))
+    };
+    let width = dimension(&fields[1], "width")?;
+    let height = dimension(&fields[2], "height")?;
+    let little_endian = match fields[3].parse::<f32>() {
+        Ok(scale) if scale < 0.0 => true,
+        Ok(scale) if scale > 0.0 => false,
+        _ => return Err(LoadError::InvalidHeader("malformed scale".into())),
+    };
+
+    let end = width
+        .checked_mul(height)
+        .and_then(|len| len.checked_mul(4))
+        .and_then(|len| len.checked_add(position))
+        .ok_or_else(|| LoadError::InvalidHeader(format!("{width}x{height} is too large")))?;
+    let raster = bytes.get(position..end).ok_or(LoadError::UnexpectedEof)?;
+    let mut values: Vec<f32> = raster
+        .chunks_exact(4)
+        .map(|b| {
+            let b = [b[0], b[1], b[2], b[3]];
+            if little_endian {
+                f32::from_le_bytes(b)
+            } else {
+                f32::from_be_bytes(b)
+            }
+        })
+        .collect();
+
+    // Flip the bottom-up rows.
+    if width > 0 {
+        let rows: Vec<&[f32]> = values.chunks_exact(width).rev().collect();
+        values = rows.concat();
+    }
+    Ok((width, height, values))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_header_and_row_order() {
+        let bytes = encode(2, 2, &[1.0, 2.0, 3.0, f32::INFINITY]);
+        let header = b"Pf\n2 2\n-1.0\n";
+        assert!(bytes.starts_with(header));
+        assert_eq!(bytes.len(), header.len() + 16);
+        assert_eq!(
+            &bytes[header.len()..header.len() + 4],
+            &3.0_f32.to_le_bytes()
+        );
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let values = [0.0, 1.5, f32::INFINITY, -2.25, f32::NEG_INFINITY, 1e-7];
+        assert_eq!(
+            decode(&encode(3, 2, &values)).unwrap(),
+            (3, 2, values.to_vec())
+        );
+    }
+
+    #[test]
+    fn test_decode_big_endian() {
+        let mut bytes = b"Pf 2 1 1.0\n".to_vec();
+        bytes.extend_from_slice(&0.5_f32.to_be_bytes());
+        bytes.extend_from_slice(&f32::INFINITY.to_be_bytes());
+        assert_eq!(decode(&bytes).unwrap(), (2, 1, vec![0.5, f32::INFINITY]));
+    }
+
+    #[test]
+    fn test_decode_errors() {
+        assert!(matches!(
+            decode(b"PF\n1 1\n-1.0\n\0\0\0\0\0\0\0\0\0\0\0\0"),
+            Err(LoadError::UnsupportedFormat(_))
+        ));
+        assert!(matches!(
+            decode(b"Pf\n1 1\n0\n\0\0\0\0"),
+            Err(LoadError::InvalidHeader(_))
+        ));
+        assert!(matches!(
+            decode(b"Pf\n-1 1\n-1.0\n\0\0\0\0"),
+            Err(LoadError::InvalidHeader(_))
+        ));
+        assert!(matches!(
+            decode(b"Pf\n2 1\n-1.0\n\0\0\0\0"),
+            Err(LoadError::UnexpectedEof)
+        ));
+        assert!(matches!(decode(b"Pf\n2 1"), Err(LoadError::UnexpectedEof)));
+
+        // The raster length fits, but not once the header is added.
+        let huge = format!("Pf\n{} 1\n-1.0\n", usize::MAX / 4);
+        assert!(matches!(
+            decode(huge.as_bytes()),
+            Err(LoadError::InvalidHeader(_))
+        ));
+    }
+}
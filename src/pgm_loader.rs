@@ -0,0 +1,187 @@
+use crate::pnm::PnmImage;
+use crate::{LoadError, Obstacles};
+use std::path::Path;
+
+/// Which pixels of an image become obstacles.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Polarity {
+    /// Pixels darker than the threshold are obstacles, as written by
+    /// [`SavePgm`](crate::SavePgm) and used by occupancy grid maps.
+    #[default]
+    DarkIsObstacle,
+    /// Pixels brighter than the threshold are obstacles.
+    LightIsObstacle,
+}
+
+/// Configuration for reading obstacles from PBM, PGM and PPM images.
+///
+/// Both the plain (`P1` to `P3`) and the binary (`P4` to `P6`) variants are supported,
+/// including 16-bit samples. Color images are converted to gray first. Pixel brightness is
+/// normalized to `0.0` (black) to `1.0` (white) and compared against the threshold.
+///
+/// A grid geometry written by [`SavePgm`](crate::SavePgm) as `# resolution:` and `# origin:`
+/// comments is restored.
+#[derive(Debug, Clone, Copy)]
+pub struct PgmLoader {
+    threshold: f32,
+    polarity: Polarity,
+}
+
+impl PgmLoader {
+    /// Sets the normalized brightness that separates obstacles from free cells.
+    #[must_use]
+    pub const fn with_threshold(mut self, threshold: f32) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    /// Sets whether dark or light pixels are obstacles.
+    #[must_use]
+    pub const fn with_polarity(mut self, polarity: Polarity) -> Self {
+        self.polarity = polarity;
+        self
+    }
+
+    /// Reads obstacles from an image file.
+    pub fn load<P: AsRef<Path>>(&self, path: P) -> Result<Obstacles, LoadError> {
+        self.parse(&std::fs::read(path)?)
+    }
+
+    /// Reads obstacles from the contents of an image file.
+    pub fn parse(&self, bytes: &[u8]) -> Result<Obstacles, LoadError> {
+        let image = PnmImage::parse(bytes)?;
+        let mut obstacles = Obstacles::new(image.width, image.height);
+        for (i, cell) in obstacles.iter_mut().enumerate() {
+            let intensity = image.intensity(i);
+            *cell = match self.polarity {
+                Polarity::DarkIsObstacle => intensity < self.threshold,
+                Polarity::LightIsObstacle => intensity > self.threshold,
+            };
+        }
+        obstacles.set_geometry(image.geometry);
+        Ok(obstacles)
+    }
+}
+
+impl Default for PgmLoader {
+    fn default() -> Self {
+        Self {
+            threshold: 0.5,
+            polarity: Polarity::DarkIsObstacle,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Grid, GridGeometry, LoadPgm, SavePgm};
+    use std::fs;
+
+    fn create_test_obstacles() -> Obstacles {
+        let mut obstacles = Obstacles::new(7, 5);
+        for (x, y) in [(0, 0), (3, 2), (6, 4), (1, 4), (5, 0)] {
+            obstacles.set_at(x, y, true);
+        }
+        obstacles
+    }
+
+    #[test]
+    fn test_default_values() {
+        let loader = PgmLoader::default();
+        assert_eq!(loader.threshold, 0.5);
+        assert_eq!(loader.polarity, Polarity::DarkIsObstacle);
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let obstacles = create_test_obstacles();
+        let path = "test_pgm_loader_roundtrip.pgm";
+        obstacles.save_pgm(path).unwrap();
+        let loaded = Obstacles::load_pgm(path).unwrap();
+        fs::remove_file(path).unwrap();
+
+        assert_eq!(loaded.width(), 7);
+        assert_eq!(loaded.height(), 5);
+        assert!(loaded.iter().eq(obstacles.iter()));
+        assert_eq!(loaded.geometry(), None);
+    }
+
+    #[test]
+    fn test_roundtrip_keeps_geometry() {
+        let geometry = GridGeometry::new(-12.5, 3.0, 0.05).with_rotation(0.5);
+        let obstacles = create_test_obstacles().with_geometry(geometry);
+        let path = "test_pgm_loader_geometry.pgm";
+        obstacles.save_pgm(path).unwrap();
+        let loaded = PgmLoader::default().load(path).unwrap();
+        fs::remove_file(path).unwrap();
+
+        assert!(loaded.iter().eq(obstacles.iter()));
+        assert_eq!(loaded.geometry(), Some(&geometry));
+    }
+
+    #[test]
+    fn test_roundtrip_light_polarity() {
+        let obstacles = create_test_obstacles();
+        let path = "test_pgm_loader_polarity.pgm";
+        obstacles.inverted().save_pgm(path).unwrap();
+        let loaded = PgmLoader::default()
+            .with_polarity(Polarity::LightIsObstacle)
+            .load(path)
+            .unwrap();
+        fs::remove_file(path).unwrap();
+
+        assert!(loaded.iter().eq(obstacles.iter()));
+    }
+
+    #[test]
+    fn test_threshold() {
+        let bytes = b"P2\n4 1\n100\n0 40 60 100\n";
+        let count = |loader: PgmLoader| loader.parse(bytes).unwrap().iter().filter(|&&b| b).count();
+        assert_eq!(count(PgmLoader::default()), 2);
+        assert_eq!(count(PgmLoader::default().with_threshold(0.3)), 1);
+        assert_eq!(count(PgmLoader::default().with_threshold(0.65)), 3);
+        assert_eq!(
+            count(PgmLoader::default().with_polarity(Polarity::LightIsObstacle)),
+            2
+        );
+    }
+
+    #[test]
+    fn test_16_bit() {
+        let bytes = b"P5\n3 1\n1000\n\x00\x00\x02\x58\x03\xe8";
+        let obstacles = PgmLoader::default().parse(bytes).unwrap();
+        assert_eq!(
+            obstacles.iter().copied().collect::<Vec<_>>(),
+            vec![true, false, false]
+        );
+    }
+
+    #[test]
+    fn test_bitmaps_mark_set_bits() {
+        let plain = PgmLoader::default().parse(b"P1\n3 1\n1 0 1\n").unwrap();
+        let raw = PgmLoader::default().parse(b"P4\n3 1\n\xa0").unwrap();
+        assert!(plain.iter().eq([true, false, true].iter()));
+        assert!(raw.iter().eq(plain.iter()));
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(matches!(
+            Obstacles::load_pgm("does_not_exist.pgm"),
+            Err(LoadError::Io(_))
+        ));
+        assert!(matches!(
+            PgmLoader::default().parse(b"P5\n# truncated"),
+            Err(LoadError::InvalidHeader(_))
+        ));
+        assert!(matches!(
+            PgmLoader::default().parse(b"P5\n4 4\n255\n\x00"),
+            Err(LoadError::UnexpectedEof)
+        ));
+        assert!(matches!(
+            PgmLoader::default().parse(b"P1\n100000 100000\n0"),
+            Err(LoadError::UnexpectedEof)
+        ));
+    }
+}
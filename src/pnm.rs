@@ -0,0 +1,435 @@
+use crate::{GridGeometry, LoadError};
+
+/// A decoded netpbm image (PBM, PGM or PPM), reduced to a single gray channel.
+///
+/// Pixels are stored row by row, starting with the top row, as values from `0` (black) to
+/// `max_value` (white). Bitmaps are converted accordingly, i.e. a set bit becomes `0`.
+#[derive(Debug, Clone)]
+pub(crate) struct PnmImage {
+    pub(crate) width: usize,
+    pub(crate) height: usize,
+    pub(crate) max_value: u16,
+    pub(crate) pixels: Vec<u16>,
+    pub(crate) geometry: Option<GridGeometry>,
+}
+
+impl PnmImage {
+    /// Decodes a P1 to P6 image.
+    pub(crate) fn parse(bytes: &[u8]) -> Result<Self, LoadError> {
+        let mut reader = Reader::new(bytes);
+        let magic = match bytes {
+            [b'P', digit @ b'1'..=b'6', ..] => digit - b'0',
+            _ => {
+                return Err(LoadError::UnsupportedFormat(
+                    "expected a netpbm magic number from P1 to P6".into(),
+                ))
+            }
+        };
+        reader.position = 2;
+        if !matches!(bytes.get(2), Some(byte) if byte.is_ascii_whitespace() || *byte == b'#') {
+            return Err(LoadError::InvalidHeader("malformed magic number".into()));
+        }
+
+        let width = reader.header_value("width")?;
+        let height = reader.header_value("height")?;
+        if width == 0 || height == 0 {
+            return Err(LoadError::InvalidHeader(format!(
+                "{width}x{height} has no pixels"
+            )));
+        }
+        let max_value = match magic {
+            1 | 4 => 1,
+            _ => match reader.header_value("maximum value")? {
+                max_value @ 1..=65535 => max_value as u16,
+                max_value => {
+                    return Err(LoadError::InvalidHeader(format!(
+                        "maximum value {max_value} is not within 1 to 65535"
+                    )))
+                }
+            },
+        };
+        let len = width
+            .checked_mul(height)
+            .ok_or_else(|| LoadError::InvalidHeader(format!("{width}x{height} is too large")))?;
+
+        let channels = if magic == 3 || magic == 6 { 3 } else { 1 };
+        let sample_count = len
+            .checked_mul(channels)
+            .ok_or_else(|| LoadError::InvalidHeader(format!("{width}x{height} is too large")))?;
+        let samples = match magic {
+            1 => reader.plain_bits(len)?,
+            2 | 3 => reader.plain_samples(sample_count, max_value)?,
+            4 => reader.raw_bits(width, height)?,
+            _ => reader.raw_samples(sample_count, max_value)?,
+        };
+        let pixels = if channels == 3 {
+            samples.chunks_exact(3).map(luminance).collect()
+        } else {
+            samples
+        };
+
+        Ok(Self {
+            width,
+            height,
+            max_value,
+            pixels,
+            geometry: parse_geometry(&reader.comments)?,
+        })
+    }
+
+    /// Returns the brightness of the pixel at `index`, from `0.0` (black) to `1.0` (white).
+    pub(crate) fn intensity(&self, index: usize) -> f32 {
+        self.pixels[index] as f32 / self.max_value as f32
+    }
+}
+
+/// Converts an RGB sample to gray using the Rec. 601 luma weights.
+fn luminance(rgb: &[u16]) -> u16 {
+    let [r, g, b] = [rgb[0], rgb[1], rgb[2]].map(f32::from);
+    (0.299 * r + 0.587 * g + 0.114 * b).round() as u16
+}
+
+/// Reads the `# resolution:` and `# origin:` comments written by
+/// [`SavePgm`](crate::SavePgm), in the style of a ROS map YAML.
+fn parse_geometry(comments: &[&str]) -> Result<Option<GridGeometry>, LoadError> {
+    let mut resolution = None;
+    let mut origin = None;
+    for comment in comments {
+        let Some((key, value)) = comment.split_once(':') else {
+            continue;
+        };
+        let numbers = value
+            .split_whitespace()
+            .map(str::parse::<f64>)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| LoadError::InvalidHeader(format!("malformed {} comment", key.trim())));
+        match key.trim() {
+            "resolution" => match numbers?[..] {
+                [r] if r > 0.0 && r.is_finite() => resolution = Some(r),
+                _ => {
+                    return Err(LoadError::InvalidHeader(
+                        "malformed resolution comment".into(),
+                    ))
+                }
+            },
+            "origin" => match numbers?[..] {
+                [x, y] => origin = Some((x, y, 0.0)),
+                [x, y, rotation] => origin = Some((x, y, rotation)),
+                _ => return Err(LoadError::InvalidHeader("malformed origin comment".into())),
+            },
+            _ => {}
+        }
+    }
+
+    Ok(resolution.map(|resolution| {
+        let (x, y, rotation) = origin.unwrap_or_default();
+        GridGeometry::new(x, y, resolution).with_rotation(rotation)
+    }))
+}
+
+/// A cursor over the bytes of a netpbm file.
+struct Reader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+    comments: Vec<&'a str>,
+}
+
+impl<'a> Reader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self {
+            bytes,
+            position: 0,
+            comments: Vec::new(),
+        }
+    }
+
+    /// Returns the number of bytes left to read.
+    fn remaining(&self) -> usize {
+        self.bytes.len() - self.position
+    }
+
+    /// Skips whitespace and comments, collecting the comment text.
+    fn skip_whitespace(&mut self) -> Result<(), LoadError> {
+        while let Some(&byte) = self.bytes.get(self.position) {
+            if byte == b'#' {
+                let start = self.position + 1;
+                let Some(len) = self.bytes[start..].iter().position(|&b| b == b'\n') else {
+                    return Err(LoadError::InvalidHeader("unterminated comment".into()));
+                };
+                let comment = std::str::from_utf8(&self.bytes[start..start + len])
+                    .map_err(|_| LoadError::InvalidHeader("comment is not valid UTF-8".into()))?;
+                self.comments.push(comment.trim());
+                self.position = start + len + 1;
+            } else if byte.is_ascii_whitespace() {
+                self.position += 1;
+            } else {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads an unsigned decimal number, or `None` at the end of the input.
+    fn number(&mut self) -> Result<Option<usize>, LoadError> {
+        self.skip_whitespace()?;
+        let digits = self.bytes[self.position..]
+            .iter()
+            .take_while(|b| b.is_ascii_digit())
+            .count();
+        if digits == 0 {
+            return match self.bytes.get(self.position) {
+                None => Ok(None),
+                Some(&byte) => Err(LoadError::InvalidData(format!(
+                    "unexpected character {:?}",
+                    byte as char
+                ))),
+            };
+        }
+
+        let text = std::str::from_utf8(&self.bytes[self.position..self.position + digits])
+            .expect("digits are ASCII");
+        self.position += digits;
+        text.parse()
+            .map(Some)
+            .map_err(|_| LoadError::InvalidData(format!("number {text} is too large")))
+    }
+
+    /// Reads a header field, which must be a number followed by whitespace or a comment.
+    fn header_value(&mut self, name: &str) -> Result<usize, LoadError> {
+        let value = match self.number() {
+            Ok(Some(value)) => value,
+            Ok(None) => return Err(LoadError::InvalidHeader(format!("missing {name}"))),
+            Err(LoadError::InvalidData(_)) => {
+                return Err(LoadError::InvalidHeader(format!("malformed {name}")))
+            }
+            Err(error) => return Err(error),
+        };
+        match self.bytes.get(self.position) {
+            Some(byte) if byte.is_ascii_whitespace() || *byte == b'#' => Ok(value),
+            Some(_) => Err(LoadError::InvalidHeader(format!("malformed {name}"))),
+            None => Err(LoadError::UnexpectedEof),
+        }
+    }
+
+    /// Skips the single whitespace byte separating the header from the raster.
+    fn raster_start(&mut self) -> Result<(), LoadError> {
+        match self.bytes.get(self.position) {
+            Some(byte) if byte.is_ascii_whitespace() => {
+                self.position += 1;
+                Ok(())
+            }
+            _ => Err(LoadError::UnexpectedEof),
+        }
+    }
+
+    /// Reads `len` pixels of a P1 bitmap, whose digits need not be separated.
+    fn plain_bits(&mut self, len: usize) -> Result<Vec<u16>, LoadError> {
+        // Every pixel takes at least one byte, so the header cannot force a larger allocation
+        // than the input itself.
+        let mut pixels = Vec::with_capacity(len.min(self.remaining()));
+        while pixels.len() < len {
+            self.skip_whitespace()?;
+            match self.bytes.get(self.position) {
+                Some(b'0') => pixels.push(1),
+                Some(b'1') => pixels.push(0),
+                Some(&byte) => {
+                    return Err(LoadError::InvalidData(format!(
+                        "unexpected character {:?} in bitmap",
+                        byte as char
+                    )))
+                }
+                None => return Err(LoadError::UnexpectedEof),
+            }
+            self.position += 1;
+        }
+        Ok(pixels)
+    }
+
+    /// Reads `len` whitespace separated decimal samples.
+    fn plain_samples(&mut self, len: usize, max_value: u16) -> Result<Vec<u16>, LoadError> {
+        let mut samples = Vec::with_capacity(len.min(self.remaining()));
+        while samples.len() < len {
+            let sample = self.number()?.ok_or(LoadError::UnexpectedEof)?;
+            samples.push(check_sample(sample, max_value)?);
+        }
+        Ok(samples)
+    }
+
+    /// Reads a P4 bitmap, whose rows are packed into bytes with the most significant bit first.
+    fn raw_bits(&mut self, width: usize, height: usize) -> Result<Vec<u16>, LoadError> {
+        self.raster_start()?;
+        let row_len = width.div_ceil(8);
+        let raster = self.take(row_len * height)?;
+        let pixels = raster
+            .chunks_exact(row_len.max(1))
+            .take(height)
+            .flat_map(|row| (0..width).map(move |x| u16::from(row[x / 8] & (0x80 >> (x % 8)) == 0)))
+            .collect();
+        Ok(pixels)
+    }
+
+    /// Reads `len` binary samples, which take two big-endian bytes if `max_value` exceeds 255.
+    fn raw_samples(&mut self, len: usize, max_value: u16) -> Result<Vec<u16>, LoadError> {
+        self.raster_start()?;
+        if max_value < 256 {
+            let raster = self.take(len)?;
+            raster
+                .iter()
+                .map(|&b| check_sample(b.into(), max_value))
+                .collect()
+        } else {
+            let raster = self.take(len.checked_mul(2).ok_or(LoadError::UnexpectedEof)?)?;
+            raster
+                .chunks_exact(2)
+                .map(|b| check_sample(u16::from_be_bytes([b[0], b[1]]).into(), max_value))
+                .collect()
+        }
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], LoadError> {
+        let end = self
+            .position
+            .checked_add(len)
+            .filter(|&end| end <= self.bytes.len())
+            .ok_or(LoadError::UnexpectedEof)?;
+        let bytes = &self.bytes[self.position..end];
+        self.position = end;
+        Ok(bytes)
+    }
+}
+
+fn check_sample(sample: usize, max_value: u16) -> Result<u16, LoadError> {
+    if sample <= max_value as usize {
+        Ok(sample as u16)
+    } else {
+        Err(LoadError::InvalidData(format!(
+            "sample {sample} exceeds the maximum value {max_value}"
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_plain_formats() {
+        let pbm = PnmImage::parse(b"P1\n# comment\n3 2\n010\n1 1 0\n").unwrap();
+        assert_eq!((pbm.width, pbm.height, pbm.max_value), (3, 2, 1));
+        assert_eq!(pbm.pixels, vec![1, 0, 1, 0, 0, 1]);
+
+        let pgm = PnmImage::parse(b"P2 2 2 # size\n15\n0 5\n10 15").unwrap();
+        assert_eq!(pgm.max_value, 15);
+        assert_eq!(pgm.pixels, vec![0, 5, 10, 15]);
+        assert_eq!(pgm.intensity(2), 10.0 / 15.0);
+
+        let ppm = PnmImage::parse(b"P3\n2 1\n255\n255 255 255 255 0 0\n").unwrap();
+        assert_eq!(ppm.pixels, vec![255, 76]);
+    }
+
+    #[test]
+    fn test_parse_raw_formats() {
+        let pbm = PnmImage::parse(b"P4\n10 2\n\x80\x40\xff\xc0").unwrap();
+        assert_eq!(
+            pbm.pixels,
+            vec![0, 1, 1, 1, 1, 1, 1, 1, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]
+        );
+
+        let pgm = PnmImage::parse(b"P5\n3 1\n255\n\x00\x7f\xff").unwrap();
+        assert_eq!(pgm.pixels, vec![0, 127, 255]);
+
+        let wide = PnmImage::parse(b"P5\n2 1\n65535\n\x01\x02\xff\xff").unwrap();
+        assert_eq!(wide.max_value, 65535);
+        assert_eq!(wide.pixels, vec![0x0102, 0xffff]);
+
+        let ppm = PnmImage::parse(b"P6\n1 1\n255\n\x00\xff\x00").unwrap();
+        assert_eq!(ppm.pixels, vec![150]);
+    }
+
+    #[test]
+    fn test_parse_geometry_comments() {
+        let image = PnmImage::parse(b"P5\n# resolution: 0.05\n# origin: -10 5.5 0\n1 1\n255\n\x00")
+            .unwrap();
+        assert_eq!(image.geometry, Some(GridGeometry::new(-10.0, 5.5, 0.05)));
+
+        let image = PnmImage::parse(b"P5\n# CREATOR: GIMP\n1 1\n255\n\x00").unwrap();
+        assert_eq!(image.geometry, None);
+
+        assert!(matches!(
+            PnmImage::parse(b"P5\n# resolution: fine\n1 1\n255\n\x00"),
+            Err(LoadError::InvalidHeader(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let cases: [&[u8]; 6] = [
+            b"P7\n1 1\n255\n\x00",
+            b"P5\n1 x\n255\n\x00",
+            b"P5\n1 1\n0\n\x00",
+            b"P5\n1 1 # no newline",
+            b"P2\n2 1\n10\n3 11\n",
+            b"P1\n2 1\n02\n",
+        ];
+        assert!(matches!(
+            PnmImage::parse(cases[0]),
+            Err(LoadError::UnsupportedFormat(_))
+        ));
+        for case in &cases[1..4] {
+            assert!(matches!(
+                PnmImage::parse(case),
+                Err(LoadError::InvalidHeader(_))
+            ));
+        }
+        for case in &cases[4..] {
+            assert!(matches!(
+                PnmImage::parse(case),
+                Err(LoadError::InvalidData(_))
+            ));
+        }
+        assert!(matches!(
+            PnmImage::parse(b"P5\n2 2\n255\n\x00\x00\x00"),
+            Err(LoadError::UnexpectedEof)
+        ));
+        assert!(matches!(
+            PnmImage::parse(b"P5\n2 2"),
+            Err(LoadError::UnexpectedEof)
+        ));
+    }
+
+    #[test]
+    fn test_oversized_headers_fail_without_allocating() {
+        let cases: [&[u8]; 5] = [
+            b"P1\n100000 100000\n0",
+            b"P2\n4294967295 4294967295\n255\n0",
+            b"P2\n100000 100000\n255\n0 0",
+            b"P3\n100000 100000\n255\n0 0 0",
+            b"P6\n100000 100000\n255\n\x00\x00\x00",
+        ];
+        for case in cases {
+            assert!(matches!(
+                PnmImage::parse(case),
+                Err(LoadError::InvalidHeader(_) | LoadError::UnexpectedEof)
+            ));
+        }
+
+        // Zero-sized images are rejected regardless of the other dimension.
+        for case in [
+            format!("P5\n0 {}\n255\n", usize::MAX),
+            format!("P4\n{} 0\n", usize::MAX),
+            "P2\n0 0\n255\n".to_string(),
+        ] {
+            assert!(matches!(
+                PnmImage::parse(case.as_bytes()),
+                Err(LoadError::InvalidHeader(_))
+            ));
+        }
+
+        // Sizes whose sample count overflows are rejected as part of the header.
+        let huge = format!("P3\n{} 2\n255\n0", usize::MAX / 4);
+        assert!(matches!(
+            PnmImage::parse(huge.as_bytes()),
+            Err(LoadError::InvalidHeader(_))
+        ));
+    }
+}
@@ -0,0 +1,284 @@
+use crate::pnm::PnmImage;
+use crate::{Grid, GridGeometry, LoadError, Obstacles};
+use std::path::Path;
+
+/// How cells of unknown occupancy are represented in the obstacle grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum UnknownCells {
+    /// Unknown cells are obstacles, so nothing is planned through unexplored space.
+    #[default]
+    Obstacle,
+    /// Unknown cells are free.
+    Free,
+}
+
+/// An occupancy map read from a ROS `map_server` YAML file and its image.
+#[derive(Debug, Clone)]
+pub struct RosMap {
+    obstacles: Obstacles,
+    unknown: Obstacles,
+}
+
+impl RosMap {
+    /// Returns the obstacles, including unknown cells if they are treated as obstacles.
+    pub const fn obstacles(&self) -> &Obstacles {
+        &self.obstacles
+    }
+
+    /// Returns the mask of cells whose occupancy is unknown.
+    pub const fn unknown(&self) -> &Obstacles {
+        &self.unknown
+    }
+
+    /// Returns the obstacles, discarding the unknown mask.
+    pub fn into_obstacles(self) -> Obstacles {
+        self.obstacles
+    }
+}
+
+/// Configuration for reading ROS `map_server` maps.
+///
+/// The YAML file provides `image`, `resolution` and `origin`, and optionally `negate`,
+/// `occupied_thresh`, `free_thresh` and `mode`. The image must be in a netpbm format, such as
+/// the PGM written by `map_saver`. As in `map_server`, the occupancy of a pixel is its darkness
+/// (or brightness if negated); it is occupied above `occupied_thresh`, free below `free_thresh`
+/// and unknown in between. In `raw` mode, pixel values are occupancy percentages, which are
+/// occupied above `occupied_thresh` and free otherwise, and values above 100 are unknown.
+///
+/// The image rows are flipped, such that cell `(0, 0)` is the lower left pixel and the grid
+/// geometry matches the `origin` of the map.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RosMapLoader {
+    unknown_cells: UnknownCells,
+}
+
+impl RosMapLoader {
+    /// Sets how cells of unknown occupancy are represented in the obstacle grid.
+    #[must_use]
+    pub const fn with_unknown_cells(mut self, unknown_cells: UnknownCells) -> Self {
+        self.unknown_cells = unknown_cells;
+        self
+    }
+
+    /// Reads a map from a YAML file, resolving the image relative to it.
+    pub fn load<P: AsRef<Path>>(&self, yaml_path: P) -> Result<RosMap, LoadError> {
+        let yaml_path = yaml_path.as_ref();
+        let yaml = std::fs::read_to_string(yaml_path)?;
+        let image = yaml_value(&yaml, "image")?
+            .ok_or_else(|| LoadError::InvalidHeader("missing image".into()))?;
+        let image_path = yaml_path
+            .parent()
+            .unwrap_or_else(|| Path::new(""))
+            .join(image);
+        self.parse(&yaml, &std::fs::read(image_path)?)
+    }
+
+    /// Reads a map from the contents of its YAML file and image.
+    pub fn parse(&self, yaml: &str, image: &[u8]) -> Result<RosMap, LoadError> {
+        let number = |key: &str| -> Result<Option<f64>, LoadError> {
+            yaml_value(yaml, key)?
+                .map(|value| {
+                    value
+                        .parse()
+                        .map_err(|_| LoadError::InvalidHeader(format!("malformed {key}")))
+                })
+                .transpose()
+        };
+        let resolution = number("resolution")?
+            .filter(|r| *r > 0.0 && r.is_finite())
+            .ok_or_else(|| LoadError::InvalidHeader("missing or invalid resolution".into()))?;
+        let origin = yaml_value(yaml, "origin")?
+            .ok_or_else(|| LoadError::InvalidHeader("missing origin".into()))?;
+        let origin = origin
+            .strip_prefix('[')
+            .and_then(|o| o.strip_suffix(']'))
+            .map(|o| o.split(',').map(|v| v.trim().parse::<f64>()).collect())
+            .and_then(|o: Result<Vec<_>, _>| o.ok())
+            .filter(|o| o.len() == 3)
+            .ok_or_else(|| LoadError::InvalidHeader("malformed origin".into()))?;
+        let negate = number("negate")?.unwrap_or(0.0) != 0.0;
+        let occupied_thresh = number("occupied_thresh")?.unwrap_or(0.65) as f32;
+        let free_thresh = number("free_thresh")?.unwrap_or(0.196) as f32;
+        let raw = match yaml_value(yaml, "mode")? {
+            None | Some("trinary" | "scale") => false,
+            Some("raw") => true,
+            Some(mode) => return Err(LoadError::UnsupportedFormat(format!("mode {mode}"))),
+        };
+
+        let image = PnmImage::parse(image)?;
+        let geometry = GridGeometry::new(origin[0], origin[1], resolution).with_rotation(origin[2]);
+        let mut obstacles = Obstacles::new(image.width, image.height).with_geometry(geometry);
+        let mut unknown = Obstacles::new(image.width, image.height).with_geometry(geometry);
+        for row in 0..image.height {
+            let y = image.height - 1 - row;
+            for x in 0..image.width {
+                let index = row * image.width + x;
+                let (occupancy, free_thresh) = if raw {
+                    let value = image.pixels[index];
+                    ((value <= 100).then(|| value as f32 / 100.0), f32::INFINITY)
+                } else {
+                    let intensity = image.intensity(index);
+                    (
+                        Some(if negate { intensity } else { 1.0 - intensity }),
+                        free_thresh,
+                    )
+                };
+
+                match occupancy {
+                    Some(occupancy) if occupancy > occupied_thresh => obstacles.set_at(x, y, true),
+                    Some(occupancy) if occupancy < free_thresh => {}
+                    _ => {
+                        unknown.set_at(x, y, true);
+                        obstacles.set_at(x, y, self.unknown_cells == UnknownCells::Obstacle);
+                    }
+                }
+            }
+        }
+
+        Ok(RosMap { obstacles, unknown })
+    }
+}
+
+/// Returns the value of a top-level `key: value` entry of a flat YAML document, without quotes
+/// and trailing comments.
+fn yaml_value<'a>(yaml: &'a str, key: &str) -> Result<Option<&'a str>, LoadError> {
+    for line in yaml.lines() {
+        let Some((k, value)) = line.split_once(':') else {
+            continue;
+        };
+        if k.trim() != key || k.starts_with(char::is_whitespace) {
+            continue;
+        }
+
+        let value = match value.find(" #") {
+            Some(comment) => &value[..comment],
+            None => value,
+        }
+        .trim();
+        let unquoted = ['"', '\''].iter().find_map(|&quote| {
+            value
+                .strip_prefix(quote)
+                .map(|v| v.strip_suffix(quote).ok_or(()))
+        });
+        return match unquoted {
+            Some(Ok(value)) => Ok(Some(value)),
+            Some(Err(())) => Err(LoadError::InvalidHeader(format!("unterminated {key}"))),
+            None if value.is_empty() => Ok(None),
+            None => Ok(Some(value)),
+        };
+    }
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    const YAML: &str = "image: map.pgm
+resolution: 0.05
+origin: [-1.0, 2.5, 0.0]  # lower left corner
+negate: 0
+occupied_thresh: 0.65
+free_thresh: 0.196
+";
+
+    /// A 3x2 map_saver image: occupied (0), unknown (205) and free (254) pixels in the top
+    /// row, and three shades of gray in the bottom row.
+    const IMAGE: &[u8] = b"P5\n3 2\n255\n\x00\xcd\xfe\x50\x80\xe0";
+
+    #[test]
+    fn test_yaml_value() {
+        let yaml = "image: \"my map.pgm\"\nmode: trinary # comment\n  nested: 1\nempty:\n";
+        assert_eq!(yaml_value(yaml, "image").unwrap(), Some("my map.pgm"));
+        assert_eq!(yaml_value(yaml, "mode").unwrap(), Some("trinary"));
+        assert_eq!(yaml_value(yaml, "nested").unwrap(), None);
+        assert_eq!(yaml_value(yaml, "empty").unwrap(), None);
+        assert!(yaml_value("image: 'map.pgm\n", "image").is_err());
+    }
+
+    #[test]
+    fn test_parse_trinary() {
+        let map = RosMapLoader::default().parse(YAML, IMAGE).unwrap();
+        let obstacles = map.obstacles();
+        assert_eq!((obstacles.width(), obstacles.height()), (3, 2));
+        assert_eq!(
+            obstacles.geometry(),
+            Some(&GridGeometry::new(-1.0, 2.5, 0.05))
+        );
+
+        // The top image row is the upper grid row.
+        assert!(obstacles.get_at(0, 1));
+        assert!(map.unknown().get_at(1, 1));
+        assert!(obstacles.get_at(1, 1));
+        assert!(!obstacles.get_at(2, 1));
+
+        // 0x50 is 69% dark, 0x80 is 50% and 0xe0 is 12%.
+        assert!(obstacles.get_at(0, 0));
+        assert!(map.unknown().get_at(1, 0));
+        assert!(!obstacles.get_at(2, 0) && !map.unknown().get_at(2, 0));
+        assert_eq!(map.unknown().iter().filter(|&&u| u).count(), 2);
+    }
+
+    #[test]
+    fn test_unknown_cells_free() {
+        let map = RosMapLoader::default()
+            .with_unknown_cells(UnknownCells::Free)
+            .parse(YAML, IMAGE)
+            .unwrap();
+        assert!(!map.obstacles().get_at(1, 1));
+        assert!(map.unknown().get_at(1, 1));
+        assert_eq!(map.into_obstacles().iter().filter(|&&b| b).count(), 2);
+    }
+
+    #[test]
+    fn test_negate_and_raw_mode() {
+        let negated = YAML.replace("negate: 0", "negate: 1");
+        let map = RosMapLoader::default().parse(&negated, IMAGE).unwrap();
+        assert!(!map.obstacles().get_at(0, 1));
+        assert!(map.obstacles().get_at(2, 1));
+
+        let raw = format!("{YAML}mode: raw\n");
+        let image = b"P5\n3 1\n255\n\x32\x64\xff";
+        let map = RosMapLoader::default().parse(&raw, image).unwrap();
+        assert!(!map.obstacles().get_at(0, 0) && !map.unknown().get_at(0, 0));
+        assert!(*map.obstacles().get_at(1, 0) && !map.unknown().get_at(1, 0));
+        assert!(map.unknown().get_at(2, 0));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let without_origin = YAML.replace("origin: [-1.0, 2.5, 0.0]", "");
+        assert!(matches!(
+            RosMapLoader::default().parse(&without_origin, IMAGE),
+            Err(LoadError::InvalidHeader(_))
+        ));
+        let short_origin = YAML.replace("[-1.0, 2.5, 0.0]", "[-1.0, 2.5]");
+        assert!(matches!(
+            RosMapLoader::default().parse(&short_origin, IMAGE),
+            Err(LoadError::InvalidHeader(_))
+        ));
+        let bad_resolution = YAML.replace("0.05", "fine");
+        assert!(matches!(
+            RosMapLoader::default().parse(&bad_resolution, IMAGE),
+            Err(LoadError::InvalidHeader(_))
+        ));
+        let png = format!("{YAML}mode: trinary\n");
+        assert!(matches!(
+            RosMapLoader::default().parse(&png, b"\x89PNG\r\n"),
+            Err(LoadError::UnsupportedFormat(_))
+        ));
+    }
+
+    #[test]
+    fn test_load_resolves_image_path() {
+        let dir = std::env::temp_dir().join("fast_sweeping_test_ros_map");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("map.yaml"), YAML).unwrap();
+        fs::write(dir.join("map.pgm"), IMAGE).unwrap();
+        let map = RosMapLoader::default().load(dir.join("map.yaml"));
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(map.unwrap().obstacles().iter().filter(|&&b| b).count(), 4);
+    }
+}
@@ -0,0 +1,416 @@
+use crate::{DistanceField, Error, Grid};
+
+/// Interpolation scheme used to sample a distance field between cell centers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Interpolation {
+    /// The value of the closest cell.
+    Nearest,
+    /// Bilinear interpolation of the four surrounding cells.
+    #[default]
+    Bilinear,
+    /// Catmull-Rom bicubic interpolation of the sixteen surrounding cells.
+    Bicubic,
+}
+
+/// Handling of sampling positions outside of the grid.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum OutOfBounds {
+    /// Moves the position onto the closest point of the grid.
+    #[default]
+    Clamp,
+    /// Returns [`Error::SampleOutOfBounds`].
+    Error,
+    /// Returns the given value with a zero gradient.
+    Fill(f32),
+}
+
+/// A sampled value together with its gradient.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sample {
+    /// The interpolated value.
+    pub value: f32,
+    /// The partial derivatives of the interpolated value along the x and y axis.
+    pub gradient: (f32, f32),
+}
+
+/// Configuration and implementation of continuous sampling of a [`DistanceField`].
+///
+/// Positions are given in cell units with cell centers at integer coordinates, i.e.
+/// sampling at `(x as f32, y as f32)` returns the value of cell `(x, y)` in every mode.
+/// Gradients are given per cell along the grid axes.
+///
+/// Infinite cells cannot be interpolated. If a cell contributing to the value is infinite,
+/// the sample is infinite with a zero gradient; if it only contributes to one derivative,
+/// that derivative is zero.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Sampler {
+    interpolation: Interpolation,
+    out_of_bounds: OutOfBounds,
+}
+
+impl Sampler {
+    /// Sets the interpolation scheme. Defaults to [`Interpolation::Bilinear`].
+    #[must_use]
+    pub const fn with_interpolation(mut self, interpolation: Interpolation) -> Self {
+        self.interpolation = interpolation;
+        self
+    }
+
+    /// Sets the handling of positions outside of the grid. Defaults to [`OutOfBounds::Clamp`].
+    #[must_use]
+    pub const fn with_out_of_bounds(mut self, out_of_bounds: OutOfBounds) -> Self {
+        self.out_of_bounds = out_of_bounds;
+        self
+    }
+
+    /// Samples the distance field at the given position.
+    pub fn sample(&self, distance_field: &DistanceField, x: f32, y: f32) -> Result<f32, Error> {
+        self.sample_with_gradient(distance_field, x, y)
+            .map(|sample| sample.value)
+    }
+
+    /// Samples the distance field and its gradient at the given position.
+    pub fn sample_with_gradient(
+        &self,
+        distance_field: &DistanceField,
+        x: f32,
+        y: f32,
+    ) -> Result<Sample, Error> {
+        let width = distance_field.width();
+        let height = distance_field.height();
+        let max_x = width.saturating_sub(1) as f32;
+        let max_y = height.saturating_sub(1) as f32;
+        let is_empty = width == 0 || height == 0;
+
+        let inside = !is_empty && (0.0..=max_x).contains(&x) && (0.0..=max_y).contains(&y);
+        let (x, y) = if inside {
+            (x, y)
+        } else {
+            match self.out_of_bounds {
+                OutOfBounds::Fill(value) => {
+                    return Ok(Sample {
+                        value,
+                        gradient: (0.0, 0.0),
+                    })
+                }
+                OutOfBounds::Clamp if !is_empty && !x.is_nan() && !y.is_nan() => {
+                    (x.clamp(0.0, max_x), y.clamp(0.0, max_y))
+                }
+                _ => return Err(Error::SampleOutOfBounds { x, y }),
+            }
+        };
+
+        Ok(match self.interpolation {
+            Interpolation::Nearest => nearest(distance_field, x, y),
+            Interpolation::Bilinear => interpolate(distance_field, x, y, linear_taps),
+            Interpolation::Bicubic => interpolate(distance_field, x, y, cubic_taps),
+        })
+    }
+
+    /// Samples the distance field and its gradient at the given world coordinates.
+    ///
+    /// The position is converted using the [`GridGeometry`](crate::GridGeometry) of the
+    /// distance field, and the gradient is returned along the world axes in world units.
+    pub fn sample_world(
+        &self,
+        distance_field: &DistanceField,
+        wx: f64,
+        wy: f64,
+    ) -> Result<Sample, Error> {
+        let geometry = distance_field.geometry().copied().unwrap_or_default();
+        let (gx, gy) = geometry.world_to_grid(wx, wy);
+        let sample =
+            self.sample_with_gradient(distance_field, (gx - 0.5) as f32, (gy - 0.5) as f32)?;
+
+        let (sin, cos) = geometry.rotation().sin_cos();
+        let (dx, dy) = (
+            f64::from(sample.gradient.0) / geometry.resolution(),
+            f64::from(sample.gradient.1) / geometry.resolution(),
+        );
+        Ok(Sample {
+            value: sample.value,
+            gradient: ((cos * dx - sin * dy) as f32, (sin * dx + cos * dy) as f32),
+        })
+    }
+}
+
+/// Returns the first cell of four taps along one axis, together with the value and
+/// derivative weights of the taps.
+type Taps = fn(f32, usize) -> (isize, [f32; 4], [f32; 4]);
+
+/// The cell before `position`, moved back such that the last cell can be interpolated.
+fn base_cell(position: f32, len: usize) -> usize {
+    (position.floor() as usize).min(len.saturating_sub(2))
+}
+
+fn linear_taps(position: f32, len: usize) -> (isize, [f32; 4], [f32; 4]) {
+    let base = base_cell(position, len);
+    let t = position - base as f32;
+    (base as isize, [1.0 - t, t, 0.0, 0.0], [-1.0, 1.0, 0.0, 0.0])
+}
+
+fn cubic_taps(position: f32, len: usize) -> (isize, [f32; 4], [f32; 4]) {
+    let base = base_cell(position, len);
+    let t = position - base as f32;
+    let (t2, t3) = (t * t, t * t * t);
+    (
+        base as isize - 1,
+        [
+            0.5 * (-t3 + 2.0 * t2 - t),
+            0.5 * (3.0 * t3 - 5.0 * t2 + 2.0),
+            0.5 * (-3.0 * t3 + 4.0 * t2 + t),
+            0.5 * (t3 - t2),
+        ],
+        [
+            0.5 * (-3.0 * t2 + 4.0 * t - 1.0),
+            0.5 * (9.0 * t2 - 10.0 * t),
+            0.5 * (-9.0 * t2 + 8.0 * t + 1.0),
+            0.5 * (3.0 * t2 - 2.0 * t),
+        ],
+    )
+}
+
+/// Interpolates the tensor product of the taps, replicating the border cells.
+fn interpolate(distance_field: &DistanceField, x: f32, y: f32, taps: Taps) -> Sample {
+    let width = distance_field.width();
+    let height = distance_field.height();
+    let (x0, wx, dx) = taps(x, width);
+    let (y0, wy, dy) = taps(y, height);
+
+    let mut value = 0.0;
+    let mut gradient = (0.0, 0.0);
+    let mut infinite = (None, false, false);
+
+    for j in 0..4 {
+        let cy = (y0 + j as isize).clamp(0, height as isize - 1) as usize;
+        for i in 0..4 {
+            let weights = (wx[i] * wy[j], dx[i] * wy[j], wx[i] * dy[j]);
+            if weights == (0.0, 0.0, 0.0) {
+                continue;
+            }
+
+            let cx = (x0 + i as isize).clamp(0, width as isize - 1) as usize;
+            let cell = *distance_field.get_at(cx, cy);
+            if cell.is_infinite() {
+                if weights.0 != 0.0 {
+                    infinite.0 = Some(cell);
+                }
+                infinite.1 |= weights.1 != 0.0;
+                infinite.2 |= weights.2 != 0.0;
+                continue;
+            }
+
+            value += weights.0 * cell;
+            gradient.0 += weights.1 * cell;
+            gradient.1 += weights.2 * cell;
+        }
+    }
+
+    match infinite {
+        (Some(value), _, _) => Sample {
+            value,
+            gradient: (0.0, 0.0),
+        },
+        (None, infinite_x, infinite_y) => Sample {
+            value,
+            gradient: (
+                if infinite_x { 0.0 } else { gradient.0 },
+                if infinite_y { 0.0 } else { gradient.1 },
+            ),
+        },
+    }
+}
+
+/// Returns the closest cell, with the gradient estimated by central differences.
+fn nearest(distance_field: &DistanceField, x: f32, y: f32) -> Sample {
+    let (cx, cy) = (x.round() as usize, y.round() as usize);
+    let value = *distance_field.get_at(cx, cy);
+    if value.is_infinite() {
+        return Sample {
+            value,
+            gradient: (0.0, 0.0),
+        };
+    }
+
+    let difference = |lower: (usize, usize), upper: (usize, usize), steps: usize| {
+        let delta =
+            distance_field.get_at(upper.0, upper.1) - distance_field.get_at(lower.0, lower.1);
+        if steps == 0 || !delta.is_finite() {
+            0.0
+        } else {
+            delta / steps as f32
+        }
+    };
+
+    let (left, right) = (
+        cx.saturating_sub(1),
+        (cx + 1).min(distance_field.width() - 1),
+    );
+    let (up, down) = (
+        cy.saturating_sub(1),
+        (cy + 1).min(distance_field.height() - 1),
+    );
+    Sample {
+        value,
+        gradient: (
+            difference((left, cy), (right, cy), right - left),
+            difference((cx, up), (cx, down), down - up),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GridGeometry;
+
+    const MODES: [Interpolation; 3] = [
+        Interpolation::Nearest,
+        Interpolation::Bilinear,
+        Interpolation::Bicubic,
+    ];
+
+    /// A field holding `2x + 3y + 1`.
+    fn ramp(width: usize, height: usize) -> DistanceField {
+        let mut df = DistanceField::new(width, height);
+        for y in 0..height {
+            for x in 0..width {
+                df.set_at(x, y, 2.0 * x as f32 + 3.0 * y as f32 + 1.0);
+            }
+        }
+        df
+    }
+
+    fn assert_close(actual: f32, expected: f32) {
+        assert!((actual - expected).abs() < 1e-4, "{actual} != {expected}");
+    }
+
+    #[test]
+    fn test_default_values() {
+        let sampler = Sampler::default();
+        assert_eq!(sampler.interpolation, Interpolation::Bilinear);
+        assert_eq!(sampler.out_of_bounds, OutOfBounds::Clamp);
+    }
+
+    #[test]
+    fn test_cell_centers_are_exact() {
+        let df = ramp(6, 5);
+        for interpolation in MODES {
+            let sampler = Sampler::default().with_interpolation(interpolation);
+            for y in 0..5 {
+                for x in 0..6 {
+                    let value = sampler.sample(&df, x as f32, y as f32).unwrap();
+                    assert_eq!(value, *df.get_at(x, y), "{interpolation:?} at ({x}, {y})");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_linear_ramp_is_reproduced() {
+        let df = ramp(8, 8);
+        for interpolation in [Interpolation::Bilinear, Interpolation::Bicubic] {
+            let sampler = Sampler::default().with_interpolation(interpolation);
+            for (x, y) in [(1.5, 1.5), (3.25, 4.75), (5.9, 2.1)] {
+                let sample = sampler.sample_with_gradient(&df, x, y).unwrap();
+                assert_close(sample.value, 2.0 * x + 3.0 * y + 1.0);
+                assert_close(sample.gradient.0, 2.0);
+                assert_close(sample.gradient.1, 3.0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_bilinear_uses_last_cells_at_border() {
+        let df = ramp(4, 3);
+        let sample = Sampler::default()
+            .sample_with_gradient(&df, 3.0, 2.0)
+            .unwrap();
+        assert_eq!(sample.value, 13.0);
+        assert_eq!(sample.gradient, (2.0, 3.0));
+    }
+
+    #[test]
+    fn test_nearest_gradient_uses_central_differences() {
+        let df = ramp(4, 3);
+        let sampler = Sampler::default().with_interpolation(Interpolation::Nearest);
+        let sample = sampler.sample_with_gradient(&df, 1.4, 0.6).unwrap();
+        assert_eq!(sample.value, *df.get_at(1, 1));
+        assert_eq!(sample.gradient, (2.0, 3.0));
+        let sample = sampler.sample_with_gradient(&df, 0.0, 2.0).unwrap();
+        assert_eq!(sample.gradient, (2.0, 3.0));
+    }
+
+    #[test]
+    fn test_out_of_bounds_policies() {
+        let df = ramp(4, 3);
+        let clamp = Sampler::default();
+        assert_eq!(clamp.sample(&df, -2.0, 1.0), Ok(4.0));
+        assert_eq!(clamp.sample(&df, 10.0, 10.0), Ok(13.0));
+
+        let error = Sampler::default().with_out_of_bounds(OutOfBounds::Error);
+        assert_eq!(
+            error.sample(&df, 3.5, 0.0),
+            Err(Error::SampleOutOfBounds { x: 3.5, y: 0.0 })
+        );
+        assert_eq!(error.sample(&df, 3.0, 2.0), Ok(13.0));
+
+        let fill = Sampler::default().with_out_of_bounds(OutOfBounds::Fill(-1.0));
+        let sample = fill.sample_with_gradient(&df, 0.0, -0.1).unwrap();
+        assert_eq!(sample.value, -1.0);
+        assert_eq!(sample.gradient, (0.0, 0.0));
+    }
+
+    #[test]
+    fn test_nan_and_empty_grids_are_errors() {
+        let df = ramp(4, 3);
+        assert!(Sampler::default().sample(&df, f32::NAN, 1.0).is_err());
+
+        let empty = DistanceField::new(0, 0);
+        assert!(Sampler::default().sample(&empty, 0.0, 0.0).is_err());
+        let fill = Sampler::default().with_out_of_bounds(OutOfBounds::Fill(5.0));
+        assert_eq!(fill.sample(&empty, 0.0, 0.0), Ok(5.0));
+    }
+
+    #[test]
+    fn test_infinite_cells() {
+        let mut df = ramp(4, 4);
+        df.set_at(2, 1, f32::INFINITY);
+
+        for interpolation in MODES {
+            let sampler = Sampler::default().with_interpolation(interpolation);
+
+            // Cells next to an infinite cell remain exact and finite.
+            let sample = sampler.sample_with_gradient(&df, 1.0, 1.0).unwrap();
+            assert_eq!(sample.value, *df.get_at(1, 1));
+            assert_eq!(sample.gradient.0, 0.0, "{interpolation:?}");
+            assert!(sample.gradient.1.is_finite());
+
+            let sample = sampler.sample_with_gradient(&df, 1.9, 1.0).unwrap();
+            assert!(sample.value.is_infinite(), "{interpolation:?}");
+            assert_eq!(sample.gradient, (0.0, 0.0));
+        }
+    }
+
+    #[test]
+    fn test_sample_world() {
+        let geometry =
+            GridGeometry::new(10.0, -5.0, 0.5).with_rotation(std::f64::consts::FRAC_PI_2);
+        let df = ramp(8, 8).with_geometry(geometry);
+
+        // The center of cell (2, 3) lies at grid coordinates (2.5, 3.5).
+        let (wx, wy) = geometry.grid_to_world(2.5, 3.5);
+        let sample = Sampler::default().sample_world(&df, wx, wy).unwrap();
+        assert_close(sample.value, *df.get_at(2, 3));
+
+        // The grid x axis points along world y, and cells are half a unit wide.
+        assert_close(sample.gradient.0, -6.0);
+        assert_close(sample.gradient.1, 4.0);
+    }
+
+    #[test]
+    fn test_distance_field_sample() {
+        let df = ramp(4, 3);
+        assert_eq!(df.sample(0.5, 0.5), Ok(3.5));
+    }
+}
@@ -0,0 +1,207 @@
+use crate::{DistanceField, DistanceFieldAlgorithm, Obstacles};
+
+/// Computes signed distance fields using an unsigned distance field algorithm.
+///
+/// The wrapped algorithm is run twice: once for the distances outside the obstacles, and once
+/// on the [inverted](Obstacles::inverted) grid for the distances inside of them. The outside
+/// distance is positive and the inside distance is negative, so that every obstacle cell holds
+/// the negated distance to the nearest free cell and every free cell holds the distance to the
+/// nearest obstacle cell. Interpolating between two neighboring cells on either side of an
+/// obstacle boundary places the zero crossing exactly on the boundary between them.
+///
+/// ```
+/// use fast_sweeping::{
+///     DistanceField, DistanceFieldAlgorithm, EuclideanDistanceTransform, Grid, Obstacles,
+///     SignedDistance,
+/// };
+///
+/// let mut obstacles = Obstacles::new(5, 1);
+/// obstacles.set_at(0, 0, true);
+/// obstacles.set_at(1, 0, true);
+///
+/// let mut df = DistanceField::from(&obstacles);
+/// SignedDistance::new(EuclideanDistanceTransform::default())
+///     .calculate_distance_field(&mut df, &obstacles);
+///
+/// assert_eq!(*df.get_at(0, 0), -2.0);
+/// assert_eq!(*df.get_at(1, 0), -1.0);
+/// assert_eq!(*df.get_at(2, 0), 1.0);
+/// ```
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SignedDistance<A> {
+    algorithm: A,
+}
+
+impl<A> SignedDistance<A> {
+    /// Creates a signed distance computation using the given unsigned algorithm.
+    pub const fn new(algorithm: A) -> Self {
+        Self { algorithm }
+    }
+
+    /// Returns the wrapped unsigned algorithm.
+    pub const fn algorithm(&self) -> &A {
+        &self.algorithm
+    }
+}
+
+impl<A: DistanceFieldAlgorithm> DistanceFieldAlgorithm for SignedDistance<A> {
+    fn calculate_distance_field(&self, distance_field: &mut DistanceField, obstacles: &Obstacles) {
+        self.algorithm
+            .calculate_distance_field(distance_field, obstacles);
+
+        let inside_obstacles = obstacles.inverted();
+        let mut inside = DistanceField::from(&inside_obstacles);
+        self.algorithm
+            .calculate_distance_field(&mut inside, &inside_obstacles);
+
+        // Exactly one of both distances is zero for every cell.
+        for (dist, &inside_dist) in distance_field.iter_mut().zip(inside.iter()) {
+            *dist -= inside_dist;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EikonalFastSweepingMethod, EuclideanDistanceTransform, Grid};
+
+    fn filled_rectangle(x0: usize, y0: usize, x1: usize, y1: usize) -> Obstacles {
+        let mut obstacles = Obstacles::new(20, 16);
+        for y in y0..y1 {
+            for x in x0..x1 {
+                obstacles.set_at(x, y, true);
+            }
+        }
+        obstacles
+    }
+
+    fn filled_disk(size: usize, radius: f32) -> Obstacles {
+        let mut obstacles = Obstacles::new(size, size);
+        let center = (size - 1) as f32 * 0.5;
+        for y in 0..size {
+            for x in 0..size {
+                let dx = x as f32 - center;
+                let dy = y as f32 - center;
+                if (dx * dx + dy * dy).sqrt() <= radius {
+                    obstacles.set_at(x, y, true);
+                }
+            }
+        }
+        obstacles
+    }
+
+    fn signed<A: DistanceFieldAlgorithm>(algorithm: A, obstacles: &Obstacles) -> DistanceField {
+        let mut df = DistanceField::from(obstacles);
+        SignedDistance::new(algorithm).calculate_distance_field(&mut df, obstacles);
+        df
+    }
+
+    #[test]
+    fn test_rectangle_signs() {
+        let obstacles = filled_rectangle(5, 4, 15, 12);
+        let df = signed(EuclideanDistanceTransform::default(), &obstacles);
+
+        for y in 0..obstacles.height() {
+            for x in 0..obstacles.width() {
+                let dist = *df.get_at(x, y);
+                if *obstacles.get_at(x, y) {
+                    assert!(dist < 0.0, "({x}, {y}) inside should be negative");
+                } else {
+                    assert!(dist > 0.0, "({x}, {y}) outside should be positive");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_rectangle_values() {
+        let obstacles = filled_rectangle(5, 4, 15, 12);
+        let df = signed(EuclideanDistanceTransform::default(), &obstacles);
+
+        // Boundary cells on either side of the left edge.
+        assert_eq!(*df.get_at(4, 8), 1.0);
+        assert_eq!(*df.get_at(5, 8), -1.0);
+        // Deeper inside and outside.
+        assert_eq!(*df.get_at(7, 8), -3.0);
+        assert_eq!(*df.get_at(1, 8), 4.0);
+        // Outside the corner.
+        assert_eq!(*df.get_at(2, 0), 5.0);
+    }
+
+    #[test]
+    fn test_zero_crossing_on_boundary() {
+        let obstacles = filled_rectangle(5, 4, 15, 12);
+        let df = signed(EuclideanDistanceTransform::default(), &obstacles);
+
+        for y in 4..12 {
+            let outside = *df.get_at(4, y);
+            let inside = *df.get_at(5, y);
+            assert!(outside > 0.0 && inside < 0.0);
+            // Linear interpolation crosses zero halfway between both cell centers.
+            assert_eq!(outside + inside, 0.0);
+        }
+    }
+
+    #[test]
+    fn test_disk_signs_and_symmetry() {
+        let obstacles = filled_disk(31, 8.0);
+        let df = signed(EuclideanDistanceTransform::default(), &obstacles);
+
+        let center = *df.get_at(15, 15);
+        assert!(
+            center < -7.0,
+            "Disk center should be deep inside, got {center}"
+        );
+        assert_eq!(*df.get_at(15, 15 - 4), *df.get_at(15, 15 + 4));
+        assert_eq!(*df.get_at(15 - 4, 15), *df.get_at(15 + 4, 15));
+        assert!(*df.get_at(0, 0) > 0.0);
+
+        for y in 0..obstacles.height() {
+            for x in 0..obstacles.width() {
+                assert_eq!(*df.get_at(x, y) < 0.0, *obstacles.get_at(x, y));
+            }
+        }
+    }
+
+    #[test]
+    fn test_disk_approximates_analytic_distance() {
+        let obstacles = filled_disk(41, 12.0);
+        let df = signed(EikonalFastSweepingMethod::default(), &obstacles);
+
+        for y in 0..obstacles.height() {
+            for x in 0..obstacles.width() {
+                let dx = x as f32 - 20.0;
+                let dy = y as f32 - 20.0;
+                let expected = (dx * dx + dy * dy).sqrt() - 12.0;
+                let actual = *df.get_at(x, y);
+                // Cell-based seeding is off by up to one cell at the boundary.
+                assert!(
+                    (actual - expected).abs() <= 2.0,
+                    "({x}, {y}): {actual} vs {expected}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_no_obstacles_is_positive_infinity() {
+        let obstacles = Obstacles::new(4, 4);
+        let df = signed(EuclideanDistanceTransform::default(), &obstacles);
+        for &dist in df.iter() {
+            assert_eq!(dist, f32::INFINITY);
+        }
+    }
+
+    #[test]
+    fn test_all_obstacles_is_negative_infinity() {
+        let mut obstacles = Obstacles::new(4, 4);
+        for cell in obstacles.iter_mut() {
+            *cell = true;
+        }
+        let df = signed(EuclideanDistanceTransform::default(), &obstacles);
+        for &dist in df.iter() {
+            assert_eq!(dist, f32::NEG_INFINITY);
+        }
+    }
+}
@@ -0,0 +1,154 @@
+/// Summary of an iterative fast sweeping solve.
+///
+/// Use this to detect solves that were truncated by the iteration cap before converging.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SolveReport {
+    /// Number of sweep iterations performed, each consisting of one sweep per direction.
+    pub iterations: usize,
+    /// Number of cell updates in each directional sweep, in the order the sweeps ran.
+    ///
+    /// Every iteration contributes one entry per sweep direction, e.g. four for 2D grids.
+    pub cells_changed: Vec<usize>,
+    /// Largest change of a single cell during the final iteration.
+    ///
+    /// This is infinite if a previously unreached cell was reached in the final iteration.
+    pub max_delta: f32,
+    /// Whether the final iteration changed no cell by more than the tolerance.
+    ///
+    /// If `false`, the solve stopped because it reached the maximum number of iterations.
+    pub converged: bool,
+}
+
+/// Change statistics of one or more sweeps.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub(crate) struct SweepStats {
+    pub(crate) cells_changed: usize,
+    pub(crate) max_delta: f32,
+}
+
+impl SweepStats {
+    /// Records that a cell decreased from `old` to `new`.
+    pub(crate) fn record(&mut self, old: f32, new: f32) {
+        self.cells_changed += 1;
+        self.max_delta = self.max_delta.max(old - new);
+    }
+
+    /// Combines the statistics of two sweeps.
+    pub(crate) fn merge(self, other: Self) -> Self {
+        Self {
+            cells_changed: self.cells_changed + other.cells_changed,
+            max_delta: self.max_delta.max(other.max_delta),
+        }
+    }
+}
+
+/// Runs `iteration` until no cell changes by more than `tolerance`, or until
+/// `max_iterations` is reached. A `max_iterations` of `0` means no limit.
+///
+/// Each iteration returns the statistics of its directional sweeps in sweep order.
+pub(crate) fn iterate_sweeps<F, I>(
+    max_iterations: usize,
+    tolerance: f32,
+    mut iteration: F,
+) -> SolveReport
+where
+    F: FnMut() -> I,
+    I: IntoIterator<Item = SweepStats>,
+{
+    let mut report = SolveReport {
+        iterations: 0,
+        cells_changed: Vec::new(),
+        max_delta: 0.0,
+        converged: false,
+    };
+
+    loop {
+        let mut stats = SweepStats::default();
+        for sweep in iteration() {
+            report.cells_changed.push(sweep.cells_changed);
+            stats = stats.merge(sweep);
+        }
+        report.iterations += 1;
+        report.max_delta = stats.max_delta;
+        report.converged = stats.max_delta <= tolerance;
+
+        if report.converged || (max_iterations > 0 && report.iterations >= max_iterations) {
+            return report;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_and_merge() {
+        let mut a = SweepStats::default();
+        a.record(3.0, 1.0);
+        a.record(f32::INFINITY, 5.0);
+        assert_eq!(a.cells_changed, 2);
+        assert!(a.max_delta.is_infinite());
+
+        let mut b = SweepStats::default();
+        b.record(1.0, 0.5);
+        let merged = b.merge(SweepStats::default());
+        assert_eq!(merged.cells_changed, 1);
+        assert_eq!(merged.max_delta, 0.5);
+    }
+
+    #[test]
+    fn test_iterate_until_converged() {
+        let mut deltas = [4.0, 2.0, 0.0].into_iter();
+        let report = iterate_sweeps(0, 0.0, || {
+            [SweepStats {
+                cells_changed: 1,
+                max_delta: deltas.next().unwrap(),
+            }]
+        });
+        assert_eq!(report.iterations, 3);
+        assert_eq!(report.cells_changed, vec![1, 1, 1]);
+        assert_eq!(report.max_delta, 0.0);
+        assert!(report.converged);
+    }
+
+    #[test]
+    fn test_iterate_stops_within_tolerance() {
+        let mut deltas = [4.0, 0.25, 0.0].into_iter();
+        let report = iterate_sweeps(0, 0.5, || {
+            [SweepStats {
+                cells_changed: 1,
+                max_delta: deltas.next().unwrap(),
+            }]
+        });
+        assert_eq!(report.iterations, 2);
+        assert!(report.converged);
+    }
+
+    #[test]
+    fn test_iterate_hits_cap() {
+        let report = iterate_sweeps(3, 0.0, || {
+            [SweepStats {
+                cells_changed: 7,
+                max_delta: 1.0,
+            }]
+        });
+        assert_eq!(report.iterations, 3);
+        assert_eq!(report.cells_changed, vec![7, 7, 7]);
+        assert!(!report.converged);
+    }
+
+    #[test]
+    fn test_iterate_records_every_sweep() {
+        let mut changed = 0..;
+        let report = iterate_sweeps(2, 0.0, || {
+            [0.5, 2.0].map(|max_delta| SweepStats {
+                cells_changed: changed.next().unwrap(),
+                max_delta,
+            })
+        });
+        assert_eq!(report.iterations, 2);
+        assert_eq!(report.cells_changed, vec![0, 1, 2, 3]);
+        assert_eq!(report.max_delta, 2.0);
+    }
+}
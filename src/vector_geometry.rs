@@ -0,0 +1,210 @@
+use crate::{DistanceField, Grid};
+
+/// Line segments in continuous cell coordinates, used to seed distance fields with sub-cell
+/// accuracy.
+///
+/// Positions are measured in cells with cell centers at integer coordinates, as for
+/// [`Sampler`](crate::Sampler). Rasterizing geometry into [`Obstacles`](crate::Obstacles)
+/// moves it to the nearest cell centers, which introduces errors of up to half a cell.
+/// [`seed`](Self::seed) instead writes the exact Euclidean distances to the geometry into a
+/// narrow band of cells, which a solver such as
+/// [`EikonalFastSweepingMethod::propagate`](crate::EikonalFastSweepingMethod::propagate)
+/// then extends to the whole grid.
+#[derive(Debug, Clone, Default)]
+pub struct VectorGeometry {
+    segments: Vec<[(f32, f32); 2]>,
+}
+
+impl VectorGeometry {
+    /// Creates an empty geometry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the line segment from `a` to `b`.
+    pub fn add_segment(&mut self, a: (f32, f32), b: (f32, f32)) {
+        self.segments.push([a, b]);
+    }
+
+    /// Adds segments connecting consecutive points.
+    pub fn add_polyline(&mut self, points: &[(f32, f32)]) {
+        for pair in points.windows(2) {
+            self.add_segment(pair[0], pair[1]);
+        }
+    }
+
+    /// Adds the closed boundary of a polygon.
+    pub fn add_polygon(&mut self, vertices: &[(f32, f32)]) {
+        self.add_polyline(vertices);
+        if let (Some(&first), Some(&last)) = (vertices.first(), vertices.last()) {
+            if vertices.len() > 2 {
+                self.add_segment(last, first);
+            }
+        }
+    }
+
+    /// Returns the segments of the geometry.
+    pub fn segments(&self) -> &[[(f32, f32); 2]] {
+        &self.segments
+    }
+
+    /// Returns the exact distance from a position to the closest segment, or infinity if the
+    /// geometry is empty.
+    pub fn distance(&self, x: f32, y: f32) -> f32 {
+        self.segments
+            .iter()
+            .map(|&[a, b]| segment_distance((x, y), a, b))
+            .fold(f32::INFINITY, f32::min)
+    }
+
+    /// Initializes a distance field from the geometry.
+    ///
+    /// Cells whose centers lie within `band` of a segment receive their exact distance; all
+    /// other cells are set to [`DistanceField::MAX_DISTANCE`]. A band of at least `1.5` cells
+    /// gives every cell next to the geometry an exact value to propagate from.
+    pub fn seed(&self, distance_field: &mut DistanceField, band: f32) {
+        for distance in distance_field.iter_mut() {
+            *distance = DistanceField::MAX_DISTANCE;
+        }
+
+        let (width, height) = (distance_field.width(), distance_field.height());
+        if width == 0 || height == 0 {
+            return;
+        }
+        for &[a, b] in &self.segments {
+            let clip = |value: f32, len: usize| value.clamp(0.0, (len - 1) as f32) as usize;
+            let (x0, x1) = (
+                clip((a.0.min(b.0) - band).ceil(), width),
+                clip((a.0.max(b.0) + band).floor(), width),
+            );
+            let (y0, y1) = (
+                clip((a.1.min(b.1) - band).ceil(), height),
+                clip((a.1.max(b.1) + band).floor(), height),
+            );
+            for y in y0..=y1 {
+                for x in x0..=x1 {
+                    let distance = segment_distance((x as f32, y as f32), a, b);
+                    if distance <= band && distance < *distance_field.get_at(x, y) {
+                        distance_field.set_at(x, y, distance);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Returns the distance from `p` to the segment from `a` to `b`.
+fn segment_distance(p: (f32, f32), a: (f32, f32), b: (f32, f32)) -> f32 {
+    let (vx, vy) = (b.0 - a.0, b.1 - a.1);
+    let (px, py) = (p.0 - a.0, p.1 - a.1);
+    let length_squared = vx * vx + vy * vy;
+    let t = if length_squared > 0.0 {
+        ((px * vx + py * vy) / length_squared).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    (px - t * vx).hypot(py - t * vy)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DistanceFieldAlgorithm, EikonalFastSweepingMethod, Obstacles};
+
+    const A: (f32, f32) = (12.3, 20.7);
+    const B: (f32, f32) = (50.6, 41.2);
+
+    /// Returns the mean error over all cells and the maximum error of cells within `radius`
+    /// of the geometry.
+    fn errors(df: &DistanceField, geometry: &VectorGeometry, radius: f32) -> (f32, f32) {
+        let width = df.width();
+        let (sum, max) = df
+            .iter()
+            .enumerate()
+            .map(|(i, &d)| {
+                let exact = geometry.distance((i % width) as f32, (i / width) as f32);
+                ((d - exact).abs(), exact <= radius)
+            })
+            .fold((0.0, 0_f32), |(sum, max), (e, near)| {
+                (sum + e, if near { max.max(e) } else { max })
+            });
+        (sum / df.iter().len() as f32, max)
+    }
+
+    #[test]
+    fn test_segment_distance() {
+        assert_eq!(segment_distance((2.0, 3.0), (0.0, 0.0), (4.0, 0.0)), 3.0);
+        assert_eq!(segment_distance((7.0, 4.0), (0.0, 0.0), (4.0, 0.0)), 5.0);
+        assert_eq!(segment_distance((-3.0, -4.0), (0.0, 0.0), (4.0, 0.0)), 5.0);
+        assert_eq!(
+            segment_distance((1.0, 1.0), (2.0, 2.0), (2.0, 2.0)),
+            2_f32.sqrt()
+        );
+    }
+
+    #[test]
+    fn test_polygon_is_closed() {
+        let mut geometry = VectorGeometry::new();
+        geometry.add_polygon(&[(0.0, 0.0), (4.0, 0.0), (4.0, 3.0)]);
+        assert_eq!(geometry.segments().len(), 3);
+        assert_eq!(geometry.segments()[2], [(4.0, 3.0), (0.0, 0.0)]);
+        assert_eq!(geometry.distance(2.0, 1.5), 0.0);
+        assert_eq!(VectorGeometry::new().distance(1.0, 1.0), f32::INFINITY);
+    }
+
+    #[test]
+    fn test_seed_band_is_exact() {
+        let mut geometry = VectorGeometry::new();
+        geometry.add_segment(A, B);
+        let mut df = DistanceField::new(64, 64);
+        geometry.seed(&mut df, 2.0);
+
+        for y in 0..64 {
+            for x in 0..64 {
+                let exact = geometry.distance(x as f32, y as f32);
+                let seeded = *df.get_at(x, y);
+                if exact <= 2.0 {
+                    assert_eq!(seeded, exact);
+                } else {
+                    assert_eq!(seeded, DistanceField::MAX_DISTANCE);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_propagated_segment_matches_analytic_distance() {
+        let mut geometry = VectorGeometry::new();
+        geometry.add_segment(A, B);
+        let solver = EikonalFastSweepingMethod::default();
+
+        let mut df = DistanceField::new(64, 64);
+        geometry.seed(&mut df, 1.5);
+        assert!(solver.propagate(&mut df).converged);
+        let (mean, near) = errors(&df, &geometry, 4.0);
+        assert!(mean < 0.15, "mean error {mean}");
+        assert!(near < 0.5, "error near the geometry {near}");
+
+        // Seeding from the rasterized segment is considerably less accurate.
+        let mut obstacles = Obstacles::new(64, 64);
+        obstacles.draw_line((12, 21), (51, 41));
+        let mut rasterized = DistanceField::from(&obstacles);
+        solver.calculate_distance_field(&mut rasterized, &obstacles);
+        let (rasterized_mean, rasterized_near) = errors(&rasterized, &geometry, 4.0);
+        assert!(mean * 2.0 < rasterized_mean, "{mean} vs {rasterized_mean}");
+        assert!(near * 2.0 < rasterized_near, "{near} vs {rasterized_near}");
+    }
+
+    #[test]
+    fn test_propagated_polygon_matches_analytic_distance() {
+        let mut geometry = VectorGeometry::new();
+        geometry.add_polygon(&[(10.5, 8.25), (40.75, 12.5), (30.2, 35.9), (8.0, 28.0)]);
+        let mut df = DistanceField::new(48, 48);
+        geometry.seed(&mut df, 1.5);
+        EikonalFastSweepingMethod::default().propagate(&mut df);
+
+        let (mean, near) = errors(&df, &geometry, 4.0);
+        assert!(mean < 0.15, "mean error {mean}");
+        assert!(near < 0.5, "error near the geometry {near}");
+    }
+}
@@ -0,0 +1,394 @@
+use crate::{DistanceField, Grid};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+/// Color scales for mapping normalized values to RGB.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Colormap {
+    /// Black to white.
+    #[default]
+    Grayscale,
+    /// The perceptually uniform blue-green-yellow scale of matplotlib.
+    Viridis,
+    /// The rainbow-like high contrast scale by Google.
+    Turbo,
+    /// Blue through white to red, centered on zero; meant for signed distance fields.
+    Diverging,
+}
+
+impl Colormap {
+    /// Returns the color at `t`, which is clamped to `[0, 1]`.
+    pub fn color(&self, t: f32) -> [u8; 3] {
+        let t = t.clamp(0.0, 1.0);
+        let rgb = match self {
+            Self::Grayscale => [t; 3],
+            Self::Viridis => viridis(t),
+            Self::Turbo => turbo(t),
+            Self::Diverging => diverging(t),
+        };
+        rgb.map(|c| (c.clamp(0.0, 1.0) * 255.0).round() as u8)
+    }
+}
+
+/// Transfer functions applied to normalized values before color mapping.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Transfer {
+    #[default]
+    Linear,
+    /// Emphasizes small distances.
+    Sqrt,
+    /// Logarithmic scale, mapping `[0, 1]` to itself via `log10(1 + 9t)`.
+    Log,
+    /// Raises normalized values to the given power.
+    Gamma(f32),
+}
+
+impl Transfer {
+    fn apply(&self, t: f32) -> f32 {
+        match self {
+            Self::Linear => t,
+            Self::Sqrt => t.sqrt(),
+            Self::Log => (1.0 + 9.0 * t).log10(),
+            Self::Gamma(gamma) => t.powf(*gamma),
+        }
+    }
+}
+
+/// Configuration for rendering distance fields as color images.
+///
+/// Finite values are normalized to the configured range, which defaults to the finite
+/// minimum and maximum of the field, passed through the [`Transfer`] function and mapped with
+/// the [`Colormap`]. For the diverging colormap the default range is symmetric around zero,
+/// and the transfer function is applied to the magnitude on either side of it.
+///
+/// Isolines are drawn on the cells at or above a level that border a cell below it.
+/// Infinite and NaN cells are drawn in the unreachable color.
+#[derive(Debug, Clone)]
+pub struct Visualization {
+    colormap: Colormap,
+    range: Option<(f32, f32)>,
+    transfer: Transfer,
+    isolines: Vec<f32>,
+    isoline_color: [u8; 3],
+    unreachable_color: [u8; 3],
+}
+
+impl Visualization {
+    /// Sets the colormap.
+    #[must_use]
+    pub const fn with_colormap(mut self, colormap: Colormap) -> Self {
+        self.colormap = colormap;
+        self
+    }
+
+    /// Sets a fixed range of values mapped to the ends of the colormap.
+    ///
+    /// Values outside of the range are clamped.
+    #[must_use]
+    pub const fn with_range(mut self, min: f32, max: f32) -> Self {
+        self.range = Some((min, max));
+        self
+    }
+
+    /// Sets the transfer function.
+    #[must_use]
+    pub const fn with_transfer(mut self, transfer: Transfer) -> Self {
+        self.transfer = transfer;
+        self
+    }
+
+    /// Sets the values at which isolines are drawn.
+    #[must_use]
+    pub fn with_isolines(mut self, levels: impl IntoIterator<Item = f32>) -> Self {
+        self.isolines = levels.into_iter().collect();
+        self
+    }
+
+    /// Sets the color of isolines.
+    #[must_use]
+    pub const fn with_isoline_color(mut self, color: [u8; 3]) -> Self {
+        self.isoline_color = color;
+        self
+    }
+
+    /// Sets the color of infinite and NaN cells.
+    #[must_use]
+    pub const fn with_unreachable_color(mut self, color: [u8; 3]) -> Self {
+        self.unreachable_color = color;
+        self
+    }
+
+    /// Renders the distance field into row-major RGB pixels.
+    pub fn render(&self, distance_field: &DistanceField) -> Vec<[u8; 3]> {
+        let (min, max) = self
+            .range
+            .unwrap_or_else(|| self.auto_range(distance_field));
+        let scale = if max > min { 1.0 / (max - min) } else { 0.0 };
+        let (width, height) = (distance_field.width(), distance_field.height());
+
+        let mut pixels = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for x in 0..width {
+                let value = *distance_field.get_at(x, y);
+                let color = if !value.is_finite() {
+                    self.unreachable_color
+                } else if self.on_isoline(distance_field, x, y) {
+                    self.isoline_color
+                } else {
+                    let t = ((value - min) * scale).clamp(0.0, 1.0);
+                    self.colormap.color(self.transfer_value(t))
+                };
+                pixels.push(color);
+            }
+        }
+        pixels
+    }
+
+    /// Renders the distance field and saves it as a binary PPM image.
+    pub fn save_ppm<P: AsRef<Path>>(
+        &self,
+        distance_field: &DistanceField,
+        path: P,
+    ) -> std::io::Result<()> {
+        let mut file = BufWriter::new(File::create(path)?);
+
+        let comments = distance_field
+            .geometry()
+            .map(|g| g.pgm_comments())
+            .unwrap_or_default();
+        let header = format!(
+            "P6\n{}{} {}\n255\n",
+            comments,
+            distance_field.width(),
+            distance_field.height()
+        );
+        file.write_all(header.as_bytes())?;
+
+        for pixel in self.render(distance_field) {
+            file.write_all(&pixel)?;
+        }
+        file.flush()
+    }
+
+    fn auto_range(&self, distance_field: &DistanceField) -> (f32, f32) {
+        let finite = distance_field.iter().filter(|d| d.is_finite());
+        if self.colormap == Colormap::Diverging {
+            let max = finite.fold(0_f32, |acc, &d| acc.max(d.abs()));
+            (-max, max)
+        } else {
+            finite.fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), &d| {
+                (min.min(d), max.max(d))
+            })
+        }
+    }
+
+    fn transfer_value(&self, t: f32) -> f32 {
+        if self.colormap == Colormap::Diverging {
+            let s = 2.0 * t - 1.0;
+            (self.transfer.apply(s.abs()).copysign(s) + 1.0) * 0.5
+        } else {
+            self.transfer.apply(t)
+        }
+    }
+
+    fn on_isoline(&self, distance_field: &DistanceField, x: usize, y: usize) -> bool {
+        let value = *distance_field.get_at(x, y);
+        let (width, height) = (distance_field.width(), distance_field.height());
+        let neighbors = [
+            (x > 0).then(|| (x - 1, y)),
+            (x + 1 < width).then(|| (x + 1, y)),
+            (y > 0).then(|| (x, y - 1)),
+            (y + 1 < height).then(|| (x, y + 1)),
+        ];
+        self.isolines.iter().any(|&level| {
+            value >= level
+                && neighbors
+                    .iter()
+                    .flatten()
+                    .any(|&(nx, ny)| *distance_field.get_at(nx, ny) < level)
+        })
+    }
+}
+
+impl Default for Visualization {
+    fn default() -> Self {
+        Self {
+            colormap: Colormap::Grayscale,
+            range: None,
+            transfer: Transfer::Linear,
+            isolines: Vec::new(),
+            isoline_color: [255, 255, 255],
+            unreachable_color: [255, 0, 255],
+        }
+    }
+}
+
+/// Polynomial approximation of viridis by Matt Zucker.
+fn viridis(t: f32) -> [f32; 3] {
+    const C: [[f32; 3]; 7] = [
+        [0.277_727_33, 0.005_407_344_5, 0.334_099_8],
+        [0.105_093_04, 1.404_613_5, 1.384_590_2],
+        [-0.330_861_83, 0.214_847_56, 0.095_095_16],
+        [-4.634_230_6, -5.799_101, -19.332_441],
+        [6.228_27, 14.179_933, 56.690_55],
+        [4.776_385, -13.745_145, -65.353_03],
+        [-5.435_456, 4.645_852_6, 26.312_435],
+    ];
+    horner(&C, t)
+}
+
+/// Polynomial approximation of turbo by Anton Mikhailov.
+fn turbo(t: f32) -> [f32; 3] {
+    const C: [[f32; 3]; 6] = [
+        [0.135_721_38, 0.091_402_61, 0.106_673_3],
+        [4.615_392_6, 2.194_188_4, 12.641_946],
+        [-42.660_32, 4.842_966_6, -60.582_05],
+        [132.131_08, -14.185_033, 110.362_77],
+        [-152.942_4, 4.277_299, -89.903_11],
+        [59.286_38, 2.829_566, 27.348_25],
+    ];
+    horner(&C, t)
+}
+
+/// Blue to red through a light gray, with the endpoints of Kenneth Moreland's cool-warm map.
+fn diverging(t: f32) -> [f32; 3] {
+    const BLUE: [f32; 3] = [0.230, 0.299, 0.754];
+    const WHITE: [f32; 3] = [0.865, 0.865, 0.865];
+    const RED: [f32; 3] = [0.706, 0.016, 0.150];
+    let (from, to, s) = if t < 0.5 {
+        (BLUE, WHITE, 2.0 * t)
+    } else {
+        (WHITE, RED, 2.0 * t - 1.0)
+    };
+    [0, 1, 2].map(|i| from[i] + (to[i] - from[i]) * s)
+}
+
+fn horner<const N: usize>(coefficients: &[[f32; 3]; N], t: f32) -> [f32; 3] {
+    [0, 1, 2].map(|i| coefficients.iter().rev().fold(0.0, |acc, c| acc * t + c[i]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GridGeometry;
+    use std::fs;
+
+    /// A single row holding `0, 1, ..., len - 1`.
+    fn ramp(len: usize) -> DistanceField {
+        let mut df = DistanceField::new(len, 1);
+        for (i, d) in df.iter_mut().enumerate() {
+            *d = i as f32;
+        }
+        df
+    }
+
+    #[test]
+    fn test_default_values() {
+        let visualization = Visualization::default();
+        assert_eq!(visualization.colormap, Colormap::Grayscale);
+        assert_eq!(visualization.range, None);
+        assert_eq!(visualization.transfer, Transfer::Linear);
+        assert!(visualization.isolines.is_empty());
+    }
+
+    #[test]
+    fn test_colormap_endpoints() {
+        assert_eq!(Colormap::Grayscale.color(0.0), [0, 0, 0]);
+        assert_eq!(Colormap::Grayscale.color(2.0), [255, 255, 255]);
+
+        let close = |a: [u8; 3], b: [u8; 3]| a.iter().zip(b).all(|(&a, b)| a.abs_diff(b) <= 4);
+        // Reference values of matplotlib's viridis and turbo.
+        assert!(close(Colormap::Viridis.color(0.0), [68, 1, 84]));
+        assert!(close(Colormap::Viridis.color(0.5), [33, 145, 140]));
+        assert!(close(Colormap::Viridis.color(1.0), [253, 231, 37]));
+        // The turbo approximation deviates more at the ends, so only check its shape.
+        let [r, g, b] = Colormap::Turbo.color(0.0);
+        assert!(r < 64 && g < 64 && b < 64);
+        let [r, g, b] = Colormap::Turbo.color(0.5);
+        assert!(g > 200 && g > r && g > b);
+        let [r, g, b] = Colormap::Turbo.color(1.0);
+        assert!(r > 100 && g < 32 && b < 32);
+
+        let [r, g, b] = Colormap::Diverging.color(0.5);
+        assert!(r == g && g == b);
+        assert!(Colormap::Diverging.color(0.0)[2] > 150);
+        assert!(Colormap::Diverging.color(1.0)[0] > 150);
+    }
+
+    #[test]
+    fn test_auto_and_fixed_range() {
+        let df = ramp(5);
+        let pixels = Visualization::default().render(&df);
+        assert_eq!(pixels[0], [0, 0, 0]);
+        assert_eq!(pixels[2], [128, 128, 128]);
+        assert_eq!(pixels[4], [255, 255, 255]);
+
+        let pixels = Visualization::default().with_range(1.0, 3.0).render(&df);
+        assert_eq!(pixels[0], [0, 0, 0]);
+        assert_eq!(pixels[1], [0, 0, 0]);
+        assert_eq!(pixels[2], [128, 128, 128]);
+        assert_eq!(pixels[4], [255, 255, 255]);
+    }
+
+    #[test]
+    fn test_transfer_functions() {
+        let df = ramp(5);
+        let at = |transfer| Visualization::default().with_transfer(transfer).render(&df)[1][0];
+        assert_eq!(at(Transfer::Linear), 64);
+        assert_eq!(at(Transfer::Sqrt), 128);
+        assert_eq!(at(Transfer::Gamma(2.0)), 16);
+        assert_eq!(at(Transfer::Log), 131);
+    }
+
+    #[test]
+    fn test_diverging_is_centered_on_zero() {
+        let mut df = DistanceField::new(3, 1);
+        df.set_at(0, 0, -2.0);
+        df.set_at(1, 0, 0.0);
+        df.set_at(2, 0, 1.0);
+        let visualization = Visualization::default()
+            .with_colormap(Colormap::Diverging)
+            .with_transfer(Transfer::Sqrt);
+        let pixels = visualization.render(&df);
+        assert_eq!(pixels[0], Colormap::Diverging.color(0.0));
+        assert_eq!(pixels[1], Colormap::Diverging.color(0.5));
+        let t = (0.5_f32.sqrt() + 1.0) * 0.5;
+        assert_eq!(pixels[2], Colormap::Diverging.color(t));
+    }
+
+    #[test]
+    fn test_unreachable_and_isolines() {
+        let mut df = ramp(8);
+        df.set_at(7, 0, f32::INFINITY);
+        let visualization = Visualization::default()
+            .with_range(0.0, 6.0)
+            .with_isolines([2.5, 5.0])
+            .with_isoline_color([255, 0, 0])
+            .with_unreachable_color([0, 0, 255]);
+        let pixels = visualization.render(&df);
+        let red: Vec<usize> = (0..8).filter(|&i| pixels[i] == [255, 0, 0]).collect();
+        assert_eq!(red, vec![3, 5]);
+        assert_eq!(pixels[7], [0, 0, 255]);
+    }
+
+    #[test]
+    fn test_save_ppm() {
+        let df = ramp(3).with_geometry(GridGeometry::new(1.0, 2.0, 0.5));
+        let path = "test_visualization.ppm";
+        Visualization::default()
+            .with_colormap(Colormap::Viridis)
+            .save_ppm(&df, path)
+            .unwrap();
+        let content = fs::read(path).unwrap();
+        fs::remove_file(path).unwrap();
+
+        let header = "P6\n# resolution: 0.5\n# origin: 1 2 0\n3 1\n255\n";
+        assert!(content.starts_with(header.as_bytes()));
+        assert_eq!(
+            &content[header.len()..header.len() + 3],
+            &Colormap::Viridis.color(0.0)
+        );
+        assert_eq!(content.len(), header.len() + 9);
+    }
+}